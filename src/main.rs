@@ -14,8 +14,14 @@ mod runtime;
 
 use runtime::{
     built_ins::{
-        base_words::register_base_words, ffi_words::register_ffi_words,
-        io_words::register_io_words, terminal_words::register_terminal_words,
+        base_words::register_base_words, binary_layout_words::register_binary_layout_words,
+        csv_words::register_csv_words, encoding_words::register_encoding_words,
+        ffi_header::bind_header_file, ffi_words::register_ffi_words,
+        filesystem_words::register_filesystem_words, http_words::register_http_words,
+        io_words::register_io_words, json_words::register_json_words,
+        msgpack_words::register_msgpack_words, process_words::register_process_words,
+        random_words::register_random_words, regex_words::register_regex_words,
+        terminal_words::register_terminal_words, time_words::register_time_words,
         user_words::register_user_words,
     },
     data_structures::{contextual_data::ContextualData, value::Value},
@@ -24,7 +30,11 @@ use runtime::{
         CodeManagement, Interpreter, WordManagement, sorth_interpreter::SorthInterpreter,
     },
 };
-use std::env::{args, current_exe, var};
+use std::{
+    env::{args, current_exe, var},
+    fs::write,
+    path::Path,
+};
 
 /// Get a directory path for the standard library.  This is either in the directory of the
 /// executable or in a directory specified by the environment variable RSORTH_LIB_PATH.
@@ -63,7 +73,49 @@ fn std_lib_directory() -> error::Result<String> {
     }
 }
 
+/// Generate a sorth source file with the ffi bindings for a C header.
+///
+/// Usage: `sorth bind-header <library> <header.h> [output.f]`
+///
+/// The output defaults to the header's path with a .f extension.  Declarations that couldn't be
+/// mapped are reported as warnings.
+fn bind_header_command(args: &[String]) -> error::Result<()> {
+    if args.len() < 2 || args.len() > 3 {
+        return ScriptError::new_as_result(
+            None,
+            "Usage: sorth bind-header <library> <header.h> [output.f]".to_string(),
+            None,
+        );
+    }
+
+    let bindings = bind_header_file(&args[0], &args[1])?;
+
+    let output = match args.get(2) {
+        Some(output) => output.clone(),
+        None => Path::new(&args[1])
+            .with_extension("f")
+            .to_string_lossy()
+            .to_string(),
+    };
+
+    for message in &bindings.unmapped {
+        eprintln!("Warning: {}: could not map {}.", args[1], message);
+    }
+
+    write(&output, bindings.source)?;
+    println!("Wrote the bindings for {} to {}.", args[1], output);
+
+    Ok(())
+}
+
 fn main() -> error::Result<()> {
+    // Generating bindings from a C header doesn't need a running interpreter.
+    let args: Vec<String> = args().collect();
+
+    if args.len() >= 2 && args[1] == "bind-header" {
+        return bind_header_command(&args[2..]);
+    }
+
     // Create the core instance of the interpreter.  Then add the standard library's location to the
     // search path.
     let mut interpreter = SorthInterpreter::new();
@@ -74,9 +126,20 @@ fn main() -> error::Result<()> {
     // Rust.
     register_base_words(&mut interpreter);
     register_io_words(&mut interpreter);
+    register_filesystem_words(&mut interpreter);
+    register_process_words(&mut interpreter);
+    register_http_words(&mut interpreter);
+    register_time_words(&mut interpreter);
+    register_random_words(&mut interpreter);
     register_terminal_words(&mut interpreter);
     register_user_words(&mut interpreter);
     register_ffi_words(&mut interpreter);
+    register_regex_words(&mut interpreter);
+    register_json_words(&mut interpreter);
+    register_csv_words(&mut interpreter);
+    register_msgpack_words(&mut interpreter);
+    register_binary_layout_words(&mut interpreter);
+    register_encoding_words(&mut interpreter);
 
     // Find and process the standard library's main file.
     interpreter.process_source_file("std.f")?;
@@ -85,10 +148,8 @@ fn main() -> error::Result<()> {
     // interpreter to a solid state.
     interpreter.mark_context();
 
-    // Gather the arguments passed to the script.  If there are arguments then the script to run is
-    // the first argument and the rest are passed to the script as a list.
-    let args: Vec<String> = args().collect();
-
+    // If there are arguments then the script to run is the first argument and the rest are passed to
+    // the script as a list.
     if args.len() >= 2 {
         let script_args: Vec<&String> = args[2..].iter().collect();
         let script_args = Value::from(script_args);
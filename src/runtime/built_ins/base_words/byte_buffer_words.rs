@@ -2,10 +2,10 @@ use crate::{
     add_native_word,
     runtime::{
         data_structures::{
-            byte_buffer::{Buffer, ByteBuffer, ByteBufferPtr},
-            value::ToValue,
+            byte_buffer::{BufferPtr, ByteBuffer, Endian, SubBuffer},
+            value::{ToValue, Value},
         },
-        error::{self, script_error},
+        error::{self, script_error, script_error_str},
         interpreter::Interpreter,
     },
 };
@@ -13,7 +13,7 @@ use crate::{
 /// Make sure the next read or write will not violate the bounds of the buffer.
 fn check_buffer_index(
     interpreter: &mut dyn Interpreter,
-    buffer_ptr: &ByteBufferPtr,
+    buffer_ptr: &BufferPtr,
     byte_size: usize,
 ) -> error::Result<()> {
     if buffer_ptr.borrow().position() + byte_size > buffer_ptr.borrow().len() {
@@ -59,101 +59,445 @@ fn word_buffer_size(interpreter: &mut dyn Interpreter) -> error::Result<()> {
 ///
 /// Signature: `size buffer -- `
 fn word_buffer_resize(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    let size = interpreter.pop_as_usize()?;
     let buffer = interpreter.pop_as_byte_buffer()?;
+    let size = interpreter.pop_as_usize()?;
+
+    let result = buffer.borrow().check_resize(size);
+
+    if let Err(message) = result {
+        script_error(interpreter, message)?;
+    }
 
     buffer.borrow_mut().resize(size);
 
     Ok(())
 }
 
-/// Write an integer of a given size to the buffer.  The only valid sizes are 1, 2, 4, and 8 bytes.
-///
-/// Signature: `value buffer byte-size -- `
-fn word_buffer_write_int(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    let byte_size = interpreter.pop_as_usize()?;
-    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
-    let value = interpreter.pop_as_int()?;
+/// Make sure that an integer size is one that the buffer can read and write.
+fn check_int_size(interpreter: &mut dyn Interpreter, byte_size: usize) -> error::Result<()> {
+    if !(1..=8).contains(&byte_size) {
+        script_error(
+            interpreter,
+            format!("Invalid byte size {} for integer value.", byte_size),
+        )?;
+    }
 
-    check_buffer_index(interpreter, &buffer_ptr, byte_size)?;
+    Ok(())
+}
 
-    if (byte_size != 1) && (byte_size != 2) && (byte_size != 4) && (byte_size != 8) {
+/// Make sure that a float size is one that the buffer can read and write.
+fn check_float_size(interpreter: &mut dyn Interpreter, byte_size: usize) -> error::Result<()> {
+    if (byte_size != 4) && (byte_size != 8) {
         script_error(
             interpreter,
-            format!("Invalid byte size {} for integer value.", byte_size),
+            format!("Invalid byte size {} for floating point value.", byte_size),
         )?;
     }
 
-    buffer_ptr.borrow_mut().write_int(byte_size, value);
+    Ok(())
+}
+
+/// Common implementation for the integer write words.  If no byte order is given the buffer's
+/// default is used.
+fn write_int(interpreter: &mut dyn Interpreter, endian: Option<Endian>) -> error::Result<()> {
+    let byte_size = interpreter.pop_as_usize()?;
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+    let value = interpreter.pop_as_int()?;
+
+    check_int_size(interpreter, byte_size)?;
+    check_buffer_index(interpreter, &buffer_ptr, byte_size)?;
+
+    let mut buffer = buffer_ptr.borrow_mut();
+    let endian = endian.unwrap_or(buffer.endian());
+
+    buffer.write_int_endian(byte_size, value, endian);
 
     Ok(())
 }
 
-/// Read an integer of a given size from the buffer.  The only valid sizes are 1, 2, 4, and 8 bytes.
-/// If the value is signed and negative the value will be sign extended.
-///
-/// Signature: `buffer byte-size is-signed -- value`
-fn word_buffer_read_int(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+/// Common implementation for the integer read words.  If no byte order is given the buffer's
+/// default is used.
+fn read_int(interpreter: &mut dyn Interpreter, endian: Option<Endian>) -> error::Result<()> {
     let is_signed = interpreter.pop_as_bool()?;
     let byte_size = interpreter.pop_as_usize()?;
     let buffer_ptr = interpreter.pop_as_byte_buffer()?;
 
+    check_int_size(interpreter, byte_size)?;
     check_buffer_index(interpreter, &buffer_ptr, byte_size)?;
 
-    if (byte_size != 1) && (byte_size != 2) && (byte_size != 4) && (byte_size != 8) {
-        script_error(
-            interpreter,
-            format!("Invalid byte size {} for integer value.", byte_size),
-        )?;
-    }
+    let value = {
+        let mut buffer = buffer_ptr.borrow_mut();
+        let endian = endian.unwrap_or(buffer.endian());
+
+        buffer.read_int_endian(byte_size, is_signed, endian)
+    };
 
-    let value = buffer_ptr.borrow_mut().read_int(byte_size, is_signed);
     interpreter.push(value.to_value());
 
     Ok(())
 }
 
-/// Write a floating point value of a given size to the buffer.  The only valid sizes are 4 and 8
-/// bytes.
-///
-/// Signature: `value buffer byte-size -- `
-fn word_buffer_write_float(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+/// Common implementation for the float write words.  If no byte order is given the buffer's
+/// default is used.
+fn write_float(interpreter: &mut dyn Interpreter, endian: Option<Endian>) -> error::Result<()> {
     let byte_size = interpreter.pop_as_usize()?;
     let buffer_ptr = interpreter.pop_as_byte_buffer()?;
     let value = interpreter.pop_as_float()?;
 
-    if (byte_size != 4) && (byte_size != 8) {
-        script_error(
-            interpreter,
-            format!("Invalid byte size {} for floating point value.", byte_size),
-        )?;
-    }
+    check_float_size(interpreter, byte_size)?;
+    check_buffer_index(interpreter, &buffer_ptr, byte_size)?;
 
-    buffer_ptr.borrow_mut().write_float(byte_size, value);
+    let mut buffer = buffer_ptr.borrow_mut();
+    let endian = endian.unwrap_or(buffer.endian());
+
+    buffer.write_float_endian(byte_size, value, endian);
 
     Ok(())
 }
 
-/// Read a floating point value of a given size from the buffer.  The only valid sizes are 4 and 8
-/// bytes.
+/// Common implementation for the float read words.  If no byte order is given the buffer's default
+/// is used.
+fn read_float(interpreter: &mut dyn Interpreter, endian: Option<Endian>) -> error::Result<()> {
+    let byte_size = interpreter.pop_as_usize()?;
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+
+    check_float_size(interpreter, byte_size)?;
+    check_buffer_index(interpreter, &buffer_ptr, byte_size)?;
+
+    let value = {
+        let mut buffer = buffer_ptr.borrow_mut();
+        let endian = endian.unwrap_or(buffer.endian());
+
+        buffer.read_float_endian(byte_size, endian)
+    };
+
+    interpreter.push(value.to_value());
+
+    Ok(())
+}
+
+/// Write an integer of a given size to the buffer in the buffer's default byte order.  Valid sizes
+/// are 1 through 8 bytes, so odd sizes such as 24-bit integers are supported.
+///
+/// Signature: `value buffer byte-size -- `
+fn word_buffer_write_int(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    write_int(interpreter, None)
+}
+
+/// Read an integer of a given size from the buffer in the buffer's default byte order.  Valid sizes
+/// are 1 through 8 bytes.  If the value is signed and negative the value will be sign extended.
+///
+/// Signature: `buffer byte-size is-signed -- value`
+fn word_buffer_read_int(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    read_int(interpreter, None)
+}
+
+/// Write a big-endian integer of a given size to the buffer.
+///
+/// Signature: `value buffer byte-size -- `
+fn word_buffer_write_int_be(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    write_int(interpreter, Some(Endian::Big))
+}
+
+/// Read a big-endian integer of a given size from the buffer.
+///
+/// Signature: `buffer byte-size is-signed -- value`
+fn word_buffer_read_int_be(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    read_int(interpreter, Some(Endian::Big))
+}
+
+/// Write a little-endian integer of a given size to the buffer.
+///
+/// Signature: `value buffer byte-size -- `
+fn word_buffer_write_int_le(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    write_int(interpreter, Some(Endian::Little))
+}
+
+/// Read a little-endian integer of a given size from the buffer.
+///
+/// Signature: `buffer byte-size is-signed -- value`
+fn word_buffer_read_int_le(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    read_int(interpreter, Some(Endian::Little))
+}
+
+/// Write a floating point value of a given size to the buffer in the buffer's default byte order.
+/// The only valid sizes are 4 and 8 bytes.
+///
+/// Signature: `value buffer byte-size -- `
+fn word_buffer_write_float(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    write_float(interpreter, None)
+}
+
+/// Read a floating point value of a given size from the buffer in the buffer's default byte order.
+/// The only valid sizes are 4 and 8 bytes.
 ///
 /// Signature: `buffer byte-size -- value`
 fn word_buffer_read_float(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    let byte_size = interpreter.pop_as_usize()?;
+    read_float(interpreter, None)
+}
+
+/// Write a big-endian floating point value of a given size to the buffer.
+///
+/// Signature: `value buffer byte-size -- `
+fn word_buffer_write_float_be(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    write_float(interpreter, Some(Endian::Big))
+}
+
+/// Read a big-endian floating point value of a given size from the buffer.
+///
+/// Signature: `buffer byte-size -- value`
+fn word_buffer_read_float_be(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    read_float(interpreter, Some(Endian::Big))
+}
+
+/// Write a little-endian floating point value of a given size to the buffer.
+///
+/// Signature: `value buffer byte-size -- `
+fn word_buffer_write_float_le(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    write_float(interpreter, Some(Endian::Little))
+}
+
+/// Read a little-endian floating point value of a given size from the buffer.
+///
+/// Signature: `buffer byte-size -- value`
+fn word_buffer_read_float_le(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    read_float(interpreter, Some(Endian::Little))
+}
+
+/// Set the buffer's default byte order.  True selects big-endian, false little-endian.
+///
+/// Signature: `is-big-endian buffer -- `
+fn word_buffer_set_big_endian(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let is_big_endian = interpreter.pop_as_bool()?;
+
+    buffer.borrow_mut().set_endian(if is_big_endian {
+        Endian::Big
+    } else {
+        Endian::Little
+    });
+
+    Ok(())
+}
+
+/// Is the buffer's default byte order big-endian?
+///
+/// Signature: `buffer -- is-big-endian`
+fn word_buffer_get_big_endian(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let is_big_endian = buffer.borrow().endian() == Endian::Big;
+
+    interpreter.push(is_big_endian.to_value());
+
+    Ok(())
+}
+
+/// Write an unsigned integer to the buffer as a LEB128 variable length integer.  Negative values
+/// are written as their 64-bit two's complement, taking the full 10 bytes.
+///
+/// Signature: `value buffer -- `
+fn word_buffer_write_uvarint(interpreter: &mut dyn Interpreter) -> error::Result<()> {
     let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+    let value = interpreter.pop_as_int()?;
 
-    check_buffer_index(interpreter, &buffer_ptr, byte_size)?;
+    write_varint(interpreter, &buffer_ptr, value as u64)
+}
 
-    if (byte_size != 4) && (byte_size != 8) {
+/// Read a LEB128 unsigned variable length integer from the buffer.
+///
+/// Signature: `buffer -- value`
+fn word_buffer_read_uvarint(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+    let value = read_varint(interpreter, &buffer_ptr)?;
+
+    interpreter.push((value as i64).to_value());
+
+    Ok(())
+}
+
+/// Write a signed integer to the buffer as a zig-zag encoded LEB128 variable length integer.  Small
+/// negative values stay small on the wire.
+///
+/// Signature: `value buffer -- `
+fn word_buffer_write_svarint(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+    let value = interpreter.pop_as_int()?;
+    let encoded = ((value << 1) ^ (value >> 63)) as u64;
+
+    write_varint(interpreter, &buffer_ptr, encoded)
+}
+
+/// Read a zig-zag encoded LEB128 signed variable length integer from the buffer.
+///
+/// Signature: `buffer -- value`
+fn word_buffer_read_svarint(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+    let encoded = read_varint(interpreter, &buffer_ptr)?;
+    let value = (encoded >> 1) as i64 ^ -((encoded & 1) as i64);
+
+    interpreter.push(value.to_value());
+
+    Ok(())
+}
+
+/// Write the LEB128 encoding of a value, seven bits at a time with the high bit marking that more
+/// bytes follow.
+fn write_varint(
+    interpreter: &mut dyn Interpreter,
+    buffer_ptr: &BufferPtr,
+    mut value: u64,
+) -> error::Result<()> {
+    let mut bytes = Vec::new();
+
+    loop {
+        let byte = (value & 0x7f) as u8;
+
+        value >>= 7;
+
+        if value == 0 {
+            bytes.push(byte);
+            break;
+        }
+
+        bytes.push(byte | 0x80);
+    }
+
+    check_buffer_index(interpreter, buffer_ptr, bytes.len())?;
+
+    let mut buffer = buffer_ptr.borrow_mut();
+
+    for byte in bytes {
+        buffer.write_int_endian(1, byte as i64, Endian::Little);
+    }
+
+    Ok(())
+}
+
+/// Read a LEB128 encoded value.  It's an error if the encoding runs past the end of the buffer or
+/// doesn't fit in 64 bits.  On error the buffer position is left unchanged.
+fn read_varint(interpreter: &mut dyn Interpreter, buffer_ptr: &BufferPtr) -> error::Result<u64> {
+    let start = buffer_ptr.borrow().position();
+    let mut value = 0u64;
+    let mut shift = 0;
+
+    loop {
+        if buffer_ptr.borrow().position() >= buffer_ptr.borrow().len() {
+            buffer_ptr.borrow_mut().set_position(start);
+            return script_error(
+                interpreter,
+                format!(
+                    "Variable length integer at position {} runs past the end of the buffer.",
+                    start
+                ),
+            );
+        }
+
+        let byte = buffer_ptr
+            .borrow_mut()
+            .read_int_endian(1, false, Endian::Little) as u64;
+
+        if shift == 63 && byte > 1 {
+            buffer_ptr.borrow_mut().set_position(start);
+            return script_error(
+                interpreter,
+                format!(
+                    "Variable length integer at position {} overflows 64 bits.",
+                    start
+                ),
+            );
+        }
+
+        value |= (byte & 0x7f) << shift;
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+
+        shift += 7;
+    }
+
+    Ok(value)
+}
+
+/// Write the low bits of an integer at the buffer's bit cursor, most significant bit first.  Up to
+/// 64 bits can be written at a time.
+///
+/// Signature: `value buffer bit-count -- `
+fn word_buffer_write_bits(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let bit_count = interpreter.pop_as_usize()?;
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+    let value = interpreter.pop_as_int()?;
+
+    check_bit_count(interpreter, &buffer_ptr, bit_count)?;
+    buffer_ptr.borrow_mut().write_bits(bit_count, value as u64);
+
+    Ok(())
+}
+
+/// Read an unsigned integer of up to 64 bits from the buffer's bit cursor, most significant bit
+/// first.
+///
+/// Signature: `buffer bit-count -- value`
+fn word_buffer_read_bits(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let bit_count = interpreter.pop_as_usize()?;
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+
+    check_bit_count(interpreter, &buffer_ptr, bit_count)?;
+
+    let value = buffer_ptr.borrow_mut().read_bits(bit_count);
+    interpreter.push((value as i64).to_value());
+
+    Ok(())
+}
+
+/// Move the bit cursor up to the start of the next whole byte, if it's part way through a byte.
+///
+/// Signature: `buffer -- `
+fn word_buffer_align_bits(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+    let mut buffer = buffer_ptr.borrow_mut();
+
+    if buffer.bit_offset() > 0 {
+        buffer.increment_position(1);
+    }
+
+    Ok(())
+}
+
+/// Get the bit cursor's offset within the byte at the buffer's current position.
+///
+/// Signature: `buffer -- bit-offset`
+fn word_buffer_get_bit_offset(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let bit_offset = buffer.borrow().bit_offset();
+
+    interpreter.push(bit_offset.to_value());
+
+    Ok(())
+}
+
+/// Make sure a bit read or write is a valid size and will not pass the end of the buffer.
+fn check_bit_count(
+    interpreter: &mut dyn Interpreter,
+    buffer_ptr: &BufferPtr,
+    bit_count: usize,
+) -> error::Result<()> {
+    if bit_count > 64 {
+        script_error_str(interpreter, "At most 64 bits can be accessed at a time.")?;
+    }
+
+    let remaining = buffer_ptr.borrow().bits_remaining();
+
+    if bit_count > remaining {
         script_error(
             interpreter,
-            format!("Invalid byte size {} for floating point value.", byte_size),
+            format!(
+                "Accessing {} bits would exceed the buffer, only {} bits remain.",
+                bit_count, remaining
+            ),
         )?;
     }
 
-    let value = buffer_ptr.borrow_mut().read_float(byte_size);
-    interpreter.push(value.to_value());
-
     Ok(())
 }
 
@@ -189,6 +533,167 @@ fn word_buffer_read_string(interpreter: &mut dyn Interpreter) -> error::Result<(
     Ok(())
 }
 
+/// Make sure a range of bytes is within the bounds of the buffer.
+fn check_buffer_range(
+    interpreter: &mut dyn Interpreter,
+    buffer_ptr: &BufferPtr,
+    offset: usize,
+    size: usize,
+) -> error::Result<()> {
+    let len = buffer_ptr.borrow().len();
+
+    if offset + size > len {
+        script_error(
+            interpreter,
+            format!(
+                "Range of size {} at offset {} exceeds the buffer size {}.",
+                size, offset, len
+            ),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Create a slice of an existing buffer.  The slice shares storage with the buffer, so writes to
+/// either are visible in both, but the slice has it's own cursor starting at 0.
+///
+/// Signature: `offset size buffer -- slice`
+fn word_buffer_slice(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let size = interpreter.pop_as_usize()?;
+    let offset = interpreter.pop_as_usize()?;
+
+    check_buffer_range(interpreter, &buffer, offset, size)?;
+
+    let slice = SubBuffer::new_ptr(buffer, offset, offset + size);
+    interpreter.push(slice.to_value());
+
+    Ok(())
+}
+
+/// Copy a range of a buffer into a brand new buffer.
+///
+/// Signature: `offset size buffer -- new_buffer`
+fn word_buffer_copy(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let size = interpreter.pop_as_usize()?;
+    let offset = interpreter.pop_as_usize()?;
+
+    check_buffer_range(interpreter, &buffer, offset, size)?;
+
+    let bytes = buffer.borrow().get_bytes(offset, size);
+    let new_buffer = ByteBuffer::new_ptr(size);
+
+    new_buffer.borrow_mut().buffer_mut().copy_from_slice(&bytes);
+    interpreter.push(new_buffer.to_value());
+
+    Ok(())
+}
+
+/// Fill a range of a buffer with a byte value.
+///
+/// Signature: `value offset size buffer -- `
+fn word_buffer_fill(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let size = interpreter.pop_as_usize()?;
+    let offset = interpreter.pop_as_usize()?;
+    let value = interpreter.pop_as_int()?;
+
+    check_buffer_range(interpreter, &buffer, offset, size)?;
+
+    if !(-128..=255).contains(&value) {
+        script_error(
+            interpreter,
+            format!("Fill value {} does not fit in a byte.", value),
+        )?;
+    }
+
+    buffer
+        .borrow_mut()
+        .set_bytes(offset, &vec![value as u8; size]);
+
+    Ok(())
+}
+
+/// Compare the contents of two buffers byte by byte.  The result is -1 if the first buffer sorts
+/// before the second, 1 if it sorts after, and 0 if they are the same.
+///
+/// Signature: `buffer_a buffer_b -- order`
+fn word_buffer_compare(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer_b = interpreter.pop_as_byte_buffer()?;
+    let buffer_a = interpreter.pop_as_byte_buffer()?;
+
+    let bytes_a = buffer_a.borrow().get_bytes(0, buffer_a.borrow().len());
+    let bytes_b = buffer_b.borrow().get_bytes(0, buffer_b.borrow().len());
+
+    let order = bytes_a.cmp(&bytes_b) as i64;
+    interpreter.push(order.to_value());
+
+    Ok(())
+}
+
+/// Find the first instance of a byte pattern within a buffer, starting the search at the given
+/// offset.  The pattern can be another buffer or a string.  The index is pushed if found, -1 if
+/// not.
+///
+/// Signature: `pattern offset buffer -- index`
+fn word_buffer_find(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let offset = interpreter.pop_as_usize()?;
+    let pattern = interpreter.pop()?;
+
+    let pattern = match pattern {
+        Value::ByteBuffer(pattern) => {
+            let pattern = pattern.borrow();
+
+            if let Err(message) = pattern.check_bounds() {
+                return script_error(interpreter, message);
+            }
+
+            pattern.get_bytes(0, pattern.len())
+        }
+
+        Value::String(pattern) => pattern.into_bytes(),
+
+        _ => {
+            return script_error(
+                interpreter,
+                format!("Expected a buffer or string pattern, found {}.", pattern),
+            );
+        }
+    };
+
+    let len = buffer.borrow().len();
+
+    if offset > len {
+        script_error(
+            interpreter,
+            format!(
+                "Search offset {} is beyond the buffer size {}.",
+                offset, len
+            ),
+        )?;
+    }
+
+    let bytes = buffer.borrow().get_bytes(offset, len - offset);
+
+    let index = if pattern.is_empty() {
+        Some(0)
+    } else {
+        bytes
+            .windows(pattern.len())
+            .position(|window| window == pattern.as_slice())
+    };
+
+    match index {
+        Some(index) => interpreter.push((offset + index).to_value()),
+        None => interpreter.push((-1_i64).to_value()),
+    }
+
+    Ok(())
+}
+
 /// Set the position of the cursor in the buffer.  This is the position that the next read or write
 /// will occur at.
 ///
@@ -267,6 +772,38 @@ pub fn register_byte_buffer_words(interpreter: &mut dyn Interpreter) {
         "buffer byte_size is_signed -- value"
     );
 
+    add_native_word!(
+        interpreter,
+        "buffer.int-be!",
+        word_buffer_write_int_be,
+        "Write a big-endian integer of a given size to the buffer.",
+        "value buffer byte_size -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.int-be@",
+        word_buffer_read_int_be,
+        "Read a big-endian integer of a given size from the buffer.",
+        "buffer byte_size is_signed -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.int-le!",
+        word_buffer_write_int_le,
+        "Write a little-endian integer of a given size to the buffer.",
+        "value buffer byte_size -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.int-le@",
+        word_buffer_read_int_le,
+        "Read a little-endian integer of a given size from the buffer.",
+        "buffer byte_size is_signed -- value"
+    );
+
     add_native_word!(
         interpreter,
         "buffer.float!",
@@ -283,6 +820,118 @@ pub fn register_byte_buffer_words(interpreter: &mut dyn Interpreter) {
         "buffer byte_size -- value"
     );
 
+    add_native_word!(
+        interpreter,
+        "buffer.float-be!",
+        word_buffer_write_float_be,
+        "Write a big-endian float of a given size to the buffer.",
+        "value buffer byte_size -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.float-be@",
+        word_buffer_read_float_be,
+        "Read a big-endian float of a given size from the buffer.",
+        "buffer byte_size -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.float-le!",
+        word_buffer_write_float_le,
+        "Write a little-endian float of a given size to the buffer.",
+        "value buffer byte_size -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.float-le@",
+        word_buffer_read_float_le,
+        "Read a little-endian float of a given size from the buffer.",
+        "buffer byte_size -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.big-endian!",
+        word_buffer_set_big_endian,
+        "Set the buffer's default byte order, true for big-endian.",
+        "is_big_endian buffer -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.big-endian@",
+        word_buffer_get_big_endian,
+        "Is the buffer's default byte order big-endian?",
+        "buffer -- is_big_endian"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.uvarint!",
+        word_buffer_write_uvarint,
+        "Write an unsigned LEB128 variable length integer to the buffer.",
+        "value buffer -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.uvarint@",
+        word_buffer_read_uvarint,
+        "Read an unsigned LEB128 variable length integer from the buffer.",
+        "buffer -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.svarint!",
+        word_buffer_write_svarint,
+        "Write a zig-zag encoded signed variable length integer to the buffer.",
+        "value buffer -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.svarint@",
+        word_buffer_read_svarint,
+        "Read a zig-zag encoded signed variable length integer from the buffer.",
+        "buffer -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.bits!",
+        word_buffer_write_bits,
+        "Write the low bits of a value at the bit cursor, high bit first.",
+        "value buffer bit_count -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.bits@",
+        word_buffer_read_bits,
+        "Read an unsigned value of up to 64 bits from the bit cursor.",
+        "buffer bit_count -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.bits-align",
+        word_buffer_align_bits,
+        "Move the bit cursor to the start of the next whole byte.",
+        "buffer -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.bit-offset@",
+        word_buffer_get_bit_offset,
+        "Get the bit cursor's offset within the current byte.",
+        "buffer -- bit_offset"
+    );
+
     add_native_word!(
         interpreter,
         "buffer.string!",
@@ -299,6 +948,46 @@ pub fn register_byte_buffer_words(interpreter: &mut dyn Interpreter) {
         "size buffer -- value"
     );
 
+    add_native_word!(
+        interpreter,
+        "buffer.slice",
+        word_buffer_slice,
+        "Create a view of a range of the buffer that shares it's storage.",
+        "offset size buffer -- slice"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.copy",
+        word_buffer_copy,
+        "Copy a range of the buffer into a new buffer.",
+        "offset size buffer -- new_buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.fill",
+        word_buffer_fill,
+        "Fill a range of the buffer with a byte value.",
+        "value offset size buffer -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.compare",
+        word_buffer_compare,
+        "Compare two buffers byte by byte, giving -1, 0, or 1.",
+        "buffer_a buffer_b -- order"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.find",
+        word_buffer_find,
+        "Find a buffer or string byte pattern, giving the index or -1.",
+        "pattern offset buffer -- index"
+    );
+
     add_native_word!(
         interpreter,
         "buffer.position!",
@@ -0,0 +1,812 @@
+use crate::{
+    add_native_word,
+    runtime::{
+        data_structures::value::{ToValue, Value},
+        error::{self, script_error, script_error_str},
+        interpreter::Interpreter,
+    },
+};
+
+/// Round to the nearest whole number, halfway values round away from zero.
+const ROUND_NEAREST: i64 = 0;
+
+/// Round to the nearest whole number, halfway values round to the even number.
+const ROUND_EVEN: i64 = 1;
+
+/// Round down towards negative infinity.
+const ROUND_FLOOR: i64 = 2;
+
+/// Round up towards positive infinity.
+const ROUND_CEIL: i64 = 3;
+
+/// Round towards zero, dropping the fractional part.
+const ROUND_TRUNC: i64 = 4;
+
+/// Pop a number off of the stack, leaving it as an int or float value.  Booleans and none are
+/// treated as ints the same way as the arithmetic words treat them.
+fn pop_number(interpreter: &mut dyn Interpreter) -> error::Result<Value> {
+    let value = interpreter.pop()?;
+
+    match value {
+        Value::Int(_) | Value::Float(_) => Ok(value),
+        _ if value.is_numeric() => Ok(value.get_int_val().to_value()),
+        _ => script_error(
+            interpreter,
+            format!("Expected a numeric value, found {}.", value),
+        ),
+    }
+}
+
+/// Helper function to handle the float only functions.  Ints are converted to floats before the
+/// function is called, and the result is always a float.
+fn float_op(interpreter: &mut dyn Interpreter, fop: fn(f64) -> f64) -> error::Result<()> {
+    let value = interpreter.pop_as_float()?;
+
+    interpreter.push(fop(value).to_value());
+
+    Ok(())
+}
+
+/// Helper function to handle the two argument float only functions.
+fn float_op2(interpreter: &mut dyn Interpreter, fop: fn(f64, f64) -> f64) -> error::Result<()> {
+    let b = interpreter.pop_as_float()?;
+    let a = interpreter.pop_as_float()?;
+
+    interpreter.push(fop(a, b).to_value());
+
+    Ok(())
+}
+
+/// Helper function to handle functions that keep the type of their value.  Ints are passed to the
+/// int handler, which can fail if the result overflows.
+fn keep_type_op(
+    interpreter: &mut dyn Interpreter,
+    fop: fn(f64) -> f64,
+    iop: fn(i64) -> Option<i64>,
+) -> error::Result<()> {
+    let result = match pop_number(interpreter)? {
+        Value::Float(value) => fop(value).to_value(),
+        value => match iop(value.get_int_val()) {
+            Some(result) => result.to_value(),
+            None => return script_error_str(interpreter, "Integer overflow in math operation."),
+        },
+    };
+
+    interpreter.push(result);
+
+    Ok(())
+}
+
+/// Helper function to pick between two numbers.  If either is a float both are compared as floats
+/// and the result is a float.
+fn pick_op(
+    interpreter: &mut dyn Interpreter,
+    fop: fn(f64, f64) -> f64,
+    iop: fn(i64, i64) -> i64,
+) -> error::Result<()> {
+    let b = pop_number(interpreter)?;
+    let a = pop_number(interpreter)?;
+
+    let result = if Value::either_is_float(&a, &b) {
+        fop(a.get_float_val(), b.get_float_val()).to_value()
+    } else {
+        iop(a.get_int_val(), b.get_int_val()).to_value()
+    };
+
+    interpreter.push(result);
+
+    Ok(())
+}
+
+/// Helper function to test a property of a number.  Ints are always finite.
+fn float_test_op(interpreter: &mut dyn Interpreter, test: fn(f64) -> bool) -> error::Result<()> {
+    let value = interpreter.pop_as_float()?;
+
+    interpreter.push(test(value).to_value());
+
+    Ok(())
+}
+
+/// Helper function for the int only functions, they report an error if the result doesn't fit in
+/// an int.
+fn checked_int_op(
+    interpreter: &mut dyn Interpreter,
+    iop: fn(i64, i64) -> Result<i64, &'static str>,
+) -> error::Result<()> {
+    let b = interpreter.pop_as_int()?;
+    let a = interpreter.pop_as_int()?;
+
+    match iop(a, b) {
+        Ok(result) => {
+            interpreter.push(result.to_value());
+            Ok(())
+        }
+
+        Err(message) => script_error_str(interpreter, message),
+    }
+}
+
+/// Find the greatest common divisor of two numbers, it's always positive.
+fn gcd(a: i64, b: i64) -> Result<i64, &'static str> {
+    let mut a = a.unsigned_abs();
+    let mut b = b.unsigned_abs();
+
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    i64::try_from(a).map_err(|_| "Integer overflow in math.gcd.")
+}
+
+/// Find the least common multiple of two numbers, it's always positive.
+fn lcm(a: i64, b: i64) -> Result<i64, &'static str> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
+
+    let divisor = gcd(a, b)?;
+
+    (a / divisor)
+        .checked_mul(b)
+        .and_then(i64::checked_abs)
+        .ok_or("Integer overflow in math.lcm.")
+}
+
+/// Raise an int to a non-negative int power.
+fn int_pow(base: i64, exponent: i64) -> Result<i64, &'static str> {
+    let exponent = u32::try_from(exponent).map_err(|_| {
+        if exponent < 0 {
+            "The exponent of math.int-pow can not be negative."
+        } else {
+            "Integer overflow in math.int-pow."
+        }
+    })?;
+
+    base.checked_pow(exponent)
+        .ok_or("Integer overflow in math.int-pow.")
+}
+
+/// Get the square root of a number.
+///
+/// Signature: `value -- result`
+fn word_math_sqrt(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::sqrt)
+}
+
+/// Get the cube root of a number.
+///
+/// Signature: `value -- result`
+fn word_math_cbrt(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::cbrt)
+}
+
+/// Raise a number to a power, the result is always a float.
+///
+/// Signature: `base exponent -- result`
+fn word_math_pow(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op2(interpreter, f64::powf)
+}
+
+/// Raise an int to a non-negative int power, failing if the result is too big for an int.
+///
+/// Signature: `base exponent -- result`
+fn word_math_int_pow(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    checked_int_op(interpreter, int_pow)
+}
+
+/// Raise e to the power of a number.
+///
+/// Signature: `value -- result`
+fn word_math_exp(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::exp)
+}
+
+/// Get the natural logarithm of a number.
+///
+/// Signature: `value -- result`
+fn word_math_ln(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::ln)
+}
+
+/// Get the base 10 logarithm of a number.
+///
+/// Signature: `value -- result`
+fn word_math_log10(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::log10)
+}
+
+/// Get the base 2 logarithm of a number.
+///
+/// Signature: `value -- result`
+fn word_math_log2(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::log2)
+}
+
+/// Get the sine of an angle in radians.
+///
+/// Signature: `radians -- result`
+fn word_math_sin(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::sin)
+}
+
+/// Get the cosine of an angle in radians.
+///
+/// Signature: `radians -- result`
+fn word_math_cos(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::cos)
+}
+
+/// Get the tangent of an angle in radians.
+///
+/// Signature: `radians -- result`
+fn word_math_tan(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::tan)
+}
+
+/// Get the arcsine of a number, in radians.
+///
+/// Signature: `value -- radians`
+fn word_math_asin(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::asin)
+}
+
+/// Get the arccosine of a number, in radians.
+///
+/// Signature: `value -- radians`
+fn word_math_acos(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::acos)
+}
+
+/// Get the arctangent of a number, in radians.
+///
+/// Signature: `value -- radians`
+fn word_math_atan(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op(interpreter, f64::atan)
+}
+
+/// Get the angle of the point x, y from the x axis, using the signs of both to find the quadrant.
+///
+/// Signature: `y x -- radians`
+fn word_math_atan2(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op2(interpreter, f64::atan2)
+}
+
+/// Get the length of the hypotenuse of a right triangle with sides a and b.
+///
+/// Signature: `a b -- result`
+fn word_math_hypot(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_op2(interpreter, f64::hypot)
+}
+
+/// Round a number down, ints are left as they are.
+///
+/// Signature: `value -- result`
+fn word_math_floor(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    keep_type_op(interpreter, f64::floor, Some)
+}
+
+/// Round a number up, ints are left as they are.
+///
+/// Signature: `value -- result`
+fn word_math_ceil(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    keep_type_op(interpreter, f64::ceil, Some)
+}
+
+/// Round a number to the nearest whole number, halfway values round away from zero.
+///
+/// Signature: `value -- result`
+fn word_math_round(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    keep_type_op(interpreter, f64::round, Some)
+}
+
+/// Drop the fractional part of a number, ints are left as they are.
+///
+/// Signature: `value -- result`
+fn word_math_trunc(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    keep_type_op(interpreter, f64::trunc, Some)
+}
+
+/// Get the absolute value of a number.
+///
+/// Signature: `value -- result`
+fn word_math_abs(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    keep_type_op(interpreter, f64::abs, i64::checked_abs)
+}
+
+/// Get -1, 0 or 1 depending on the sign of a number.  Floats give a float, and NaN stays NaN.
+///
+/// Signature: `value -- sign`
+fn word_math_sign(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    keep_type_op(
+        interpreter,
+        |value| {
+            if value == 0.0 || value.is_nan() {
+                value
+            } else {
+                value.signum()
+            }
+        },
+        |value| Some(value.signum()),
+    )
+}
+
+/// Get the smaller of two numbers.
+///
+/// Signature: `a b -- result`
+fn word_math_min(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    pick_op(interpreter, f64::min, i64::min)
+}
+
+/// Get the larger of two numbers.
+///
+/// Signature: `a b -- result`
+fn word_math_max(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    pick_op(interpreter, f64::max, i64::max)
+}
+
+/// Get the greatest common divisor of two ints.
+///
+/// Signature: `a b -- result`
+fn word_math_gcd(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    checked_int_op(interpreter, gcd)
+}
+
+/// Get the least common multiple of two ints.
+///
+/// Signature: `a b -- result`
+fn word_math_lcm(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    checked_int_op(interpreter, lcm)
+}
+
+/// Is the number not a number?
+///
+/// Signature: `value -- bool`
+fn word_math_is_nan(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_test_op(interpreter, f64::is_nan)
+}
+
+/// Is the number positive or negative infinity?
+///
+/// Signature: `value -- bool`
+fn word_math_is_infinite(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_test_op(interpreter, f64::is_infinite)
+}
+
+/// Is the number neither infinite nor NaN?
+///
+/// Signature: `value -- bool`
+fn word_math_is_finite(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    float_test_op(interpreter, f64::is_finite)
+}
+
+/// Convert a number to an int, rounding floats with one of the math.round/ modes.  Floats that are
+/// NaN, infinite or too big for an int are an error.
+///
+/// Signature: `value mode -- int`
+fn word_math_to_int(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let mode = interpreter.pop_as_int()?;
+    let value = pop_number(interpreter)?;
+
+    let round = match mode {
+        ROUND_NEAREST => f64::round,
+        ROUND_EVEN => f64::round_ties_even,
+        ROUND_FLOOR => f64::floor,
+        ROUND_CEIL => f64::ceil,
+        ROUND_TRUNC => f64::trunc,
+        _ => {
+            return script_error(interpreter, format!("Unknown rounding mode {}.", mode));
+        }
+    };
+
+    let result = match value {
+        Value::Float(value) => {
+            let rounded = round(value);
+
+            // i64::MAX isn't exactly representable as a float, so check against 2^63 instead.
+            if !(-9_223_372_036_854_775_808.0..9_223_372_036_854_775_808.0).contains(&rounded) {
+                return script_error(
+                    interpreter,
+                    format!("The value {} can not be converted to an int.", value),
+                );
+            }
+
+            rounded as i64
+        }
+
+        value => value.get_int_val(),
+    };
+
+    interpreter.push(result.to_value());
+
+    Ok(())
+}
+
+/// Convert a number to a float.
+///
+/// Signature: `value -- float`
+fn word_math_to_float(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let value = pop_number(interpreter)?;
+
+    interpreter.push(value.get_float_val().to_value());
+
+    Ok(())
+}
+
+/// Register the math function words and constants.
+pub fn register_math_words(interpreter: &mut dyn Interpreter) {
+    // Constants.
+    add_native_word!(
+        interpreter,
+        "math.pi",
+        |interpreter| {
+            interpreter.push(std::f64::consts::PI.to_value());
+            Ok(())
+        },
+        "The ratio of a circle's circumference to it's diameter.",
+        " -- pi"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.tau",
+        |interpreter| {
+            interpreter.push(std::f64::consts::TAU.to_value());
+            Ok(())
+        },
+        "The number of radians in a full turn, 2 pi.",
+        " -- tau"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.e",
+        |interpreter| {
+            interpreter.push(std::f64::consts::E.to_value());
+            Ok(())
+        },
+        "Euler's number, the base of the natural logarithm.",
+        " -- e"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.epsilon",
+        |interpreter| {
+            interpreter.push(f64::EPSILON.to_value());
+            Ok(())
+        },
+        "The difference between 1.0 and the next larger float.",
+        " -- epsilon"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.infinity",
+        |interpreter| {
+            interpreter.push(f64::INFINITY.to_value());
+            Ok(())
+        },
+        "Positive infinity.",
+        " -- infinity"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.nan",
+        |interpreter| {
+            interpreter.push(f64::NAN.to_value());
+            Ok(())
+        },
+        "A float that is not a number.",
+        " -- nan"
+    );
+
+    // Powers, roots and logarithms.
+    add_native_word!(
+        interpreter,
+        "math.sqrt",
+        word_math_sqrt,
+        "Get the square root of a number.",
+        "value -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.cbrt",
+        word_math_cbrt,
+        "Get the cube root of a number.",
+        "value -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.pow",
+        word_math_pow,
+        "Raise a number to a power, giving a float.",
+        "base exponent -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.int-pow",
+        word_math_int_pow,
+        "Raise an int to a non-negative int power, failing on overflow.",
+        "base exponent -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.exp",
+        word_math_exp,
+        "Raise e to the power of a number.",
+        "value -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.ln",
+        word_math_ln,
+        "Get the natural logarithm of a number.",
+        "value -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.log10",
+        word_math_log10,
+        "Get the base 10 logarithm of a number.",
+        "value -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.log2",
+        word_math_log2,
+        "Get the base 2 logarithm of a number.",
+        "value -- result"
+    );
+
+    // Trigonometry.
+    add_native_word!(
+        interpreter,
+        "math.sin",
+        word_math_sin,
+        "Get the sine of an angle in radians.",
+        "radians -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.cos",
+        word_math_cos,
+        "Get the cosine of an angle in radians.",
+        "radians -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.tan",
+        word_math_tan,
+        "Get the tangent of an angle in radians.",
+        "radians -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.asin",
+        word_math_asin,
+        "Get the arcsine of a number, in radians.",
+        "value -- radians"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.acos",
+        word_math_acos,
+        "Get the arccosine of a number, in radians.",
+        "value -- radians"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.atan",
+        word_math_atan,
+        "Get the arctangent of a number, in radians.",
+        "value -- radians"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.atan2",
+        word_math_atan2,
+        "Get the angle of the point x, y from the x axis.",
+        "y x -- radians"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.hypot",
+        word_math_hypot,
+        "Get the length of the hypotenuse of a right triangle.",
+        "a b -- result"
+    );
+
+    // Rounding and signs.
+    add_native_word!(
+        interpreter,
+        "math.floor",
+        word_math_floor,
+        "Round a number down.",
+        "value -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.ceil",
+        word_math_ceil,
+        "Round a number up.",
+        "value -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.round",
+        word_math_round,
+        "Round a number to the nearest whole number, halfway values away from zero.",
+        "value -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.trunc",
+        word_math_trunc,
+        "Drop the fractional part of a number.",
+        "value -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.abs",
+        word_math_abs,
+        "Get the absolute value of a number.",
+        "value -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.sign",
+        word_math_sign,
+        "Get -1, 0 or 1 depending on the sign of a number.",
+        "value -- sign"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.min",
+        word_math_min,
+        "Get the smaller of two numbers.",
+        "a b -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.max",
+        word_math_max,
+        "Get the larger of two numbers.",
+        "a b -- result"
+    );
+
+    // Int functions.
+    add_native_word!(
+        interpreter,
+        "math.gcd",
+        word_math_gcd,
+        "Get the greatest common divisor of two ints.",
+        "a b -- result"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.lcm",
+        word_math_lcm,
+        "Get the least common multiple of two ints.",
+        "a b -- result"
+    );
+
+    // Float predicates.
+    add_native_word!(
+        interpreter,
+        "math.nan?",
+        word_math_is_nan,
+        "Is the number not a number?",
+        "value -- bool"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.infinite?",
+        word_math_is_infinite,
+        "Is the number positive or negative infinity?",
+        "value -- bool"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.finite?",
+        word_math_is_finite,
+        "Is the number neither infinite nor NaN?",
+        "value -- bool"
+    );
+
+    // Conversions.
+    add_native_word!(
+        interpreter,
+        "math.round/nearest",
+        |interpreter| {
+            interpreter.push(ROUND_NEAREST.to_value());
+            Ok(())
+        },
+        "Rounding mode for math.to-int, halfway values round away from zero.",
+        " -- mode"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.round/even",
+        |interpreter| {
+            interpreter.push(ROUND_EVEN.to_value());
+            Ok(())
+        },
+        "Rounding mode for math.to-int, halfway values round to the even number.",
+        " -- mode"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.round/floor",
+        |interpreter| {
+            interpreter.push(ROUND_FLOOR.to_value());
+            Ok(())
+        },
+        "Rounding mode for math.to-int, round down.",
+        " -- mode"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.round/ceil",
+        |interpreter| {
+            interpreter.push(ROUND_CEIL.to_value());
+            Ok(())
+        },
+        "Rounding mode for math.to-int, round up.",
+        " -- mode"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.round/trunc",
+        |interpreter| {
+            interpreter.push(ROUND_TRUNC.to_value());
+            Ok(())
+        },
+        "Rounding mode for math.to-int, round towards zero.",
+        " -- mode"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.to-int",
+        word_math_to_int,
+        "Convert a number to an int, rounding floats with a math.round/ mode.",
+        "value mode -- int"
+    );
+
+    add_native_word!(
+        interpreter,
+        "math.to-float",
+        word_math_to_float,
+        "Convert a number to a float.",
+        "value -- float"
+    );
+}
@@ -37,6 +37,9 @@ mod hash_table_words;
 /// Words that work with math, logic, bit manipulation and Value equality.
 mod math_logic_and_bit_words;
 
+/// Words for math functions, rounding, number conversions and constants.
+mod math_words;
+
 use crate::runtime::{
     built_ins::base_words::{
         array_words::register_array_words, byte_buffer_words::register_byte_buffer_words,
@@ -44,8 +47,9 @@ use crate::runtime::{
         data_structure_words::register_data_structure_words,
         hash_table_words::register_hash_table_words,
         math_logic_and_bit_words::register_math_logic_and_bit_words,
-        sorth_words::register_sorth_words, stack_words::register_stack_words,
-        string_words::register_string_words, value_type_words::register_value_type_words,
+        math_words::register_math_words, sorth_words::register_sorth_words,
+        stack_words::register_stack_words, string_words::register_string_words,
+        value_type_words::register_value_type_words,
         word_creation_words::register_word_creation_words, word_words::register_word_words,
     },
     interpreter::Interpreter,
@@ -66,4 +70,5 @@ pub fn register_base_words(interpreter: &mut dyn Interpreter) {
     register_byte_buffer_words(interpreter);
     register_hash_table_words(interpreter);
     register_math_logic_and_bit_words(interpreter);
+    register_math_words(interpreter);
 }
@@ -44,17 +44,13 @@ fn word_if_im(interpreter: &mut dyn Interpreter) -> error::Result<()> {
         let mut matched = String::new();
 
         while !done {
-            match interpreter.next_token() {
-                Ok(found) => {
-                    if let Ok(text) = found.word(interpreter)
-                        && is_one_of(text, words)
-                    {
-                        done = true;
-                        matched = text.clone()
-                    }
-                }
-
-                Err(err) => return Err(err),
+            let found = interpreter.next_token()?;
+
+            if let Ok(text) = found.word(interpreter)
+                && is_one_of(text, words)
+            {
+                done = true;
+                matched = text.clone()
             }
         }
 
@@ -66,19 +62,15 @@ fn word_if_im(interpreter: &mut dyn Interpreter) -> error::Result<()> {
         let mut matched = String::new();
 
         while !done {
-            match interpreter.next_token() {
-                Ok(found) => {
-                    if let Ok(text) = found.word(interpreter)
-                        && is_one_of(text, words)
-                    {
-                        done = true;
-                        matched = text.clone()
-                    } else {
-                        process_token(interpreter, found)?;
-                    }
-                }
-
-                Err(err) => return Err(err),
+            let found = interpreter.next_token()?;
+
+            if let Ok(text) = found.word(interpreter)
+                && is_one_of(text, words)
+            {
+                done = true;
+                matched = text.clone()
+            } else {
+                process_token(interpreter, found)?;
             }
         }
 
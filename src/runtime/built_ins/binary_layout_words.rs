@@ -0,0 +1,767 @@
+use crate::{
+    add_native_word,
+    runtime::{
+        data_structures::{
+            byte_buffer::ByteBuffer,
+            data_object::{DataObject, DataObjectDefinition, DataObjectDefinitionPtr},
+            dictionary::{WordRuntime, WordType, WordVisibility},
+            value::{ToValue, Value},
+            value_vec::ValueVec,
+        },
+        error::{self, script_error},
+        interpreter::Interpreter,
+    },
+};
+use std::{collections::HashMap, rc::Rc};
+
+/// The type of a single field within a binary layout.
+enum LayoutType {
+    /// An integer of the given byte size.
+    Int { size: usize, is_signed: bool },
+
+    /// A floating point value of the given byte size.
+    Float(usize),
+
+    /// A string stored in a fixed number of bytes, padded with zeros.
+    FixedString(usize),
+
+    /// A string stored with a length prefix of the given byte size.
+    PrefixedString(usize),
+
+    /// A structure stored using another binary layout.
+    Nested(Rc<BinaryLayout>),
+}
+
+/// A single item within a binary layout.
+enum LayoutItem {
+    /// A number of zero bytes that are skipped over when unpacking.
+    Padding(usize),
+
+    /// A structure field, with an optional fixed count if the field is an array.
+    Field {
+        name: String,
+        field_index: usize,
+        field_type: LayoutType,
+        count: Option<usize>,
+    },
+}
+
+/// A binary layout describes how a structure's fields are packed into and unpacked from a byte
+/// buffer.
+pub struct BinaryLayout {
+    /// The structure definition the layout is tied to.
+    definition: DataObjectDefinitionPtr,
+
+    /// The items of the layout, in the order that they appear in the binary data.
+    items: Vec<LayoutItem>,
+
+    /// Are multi-byte values stored as big-endian?
+    is_big_endian: bool,
+}
+
+/// The per-interpreter list of binary layouts, so that layouts can refer to each other by name.
+pub struct BinaryLayoutList {
+    layouts: HashMap<String, Rc<BinaryLayout>>,
+}
+
+impl Default for BinaryLayoutList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BinaryLayoutList {
+    /// Create a new and empty layout list.
+    pub fn new() -> BinaryLayoutList {
+        BinaryLayoutList {
+            layouts: HashMap::new(),
+        }
+    }
+
+    /// Forget all of the defined layouts.  Words that were already created keep their layouts.
+    pub fn reset(&mut self) {
+        self.layouts.clear();
+    }
+}
+
+impl BinaryLayout {
+    /// The name of the structure this layout is for.
+    fn name(&self) -> String {
+        self.definition.borrow().name().clone()
+    }
+
+    /// Write an integer of the given size to the output using the layout's byte order.
+    fn pack_int(&self, output: &mut Vec<u8>, size: usize, value: i64) {
+        let bytes = if self.is_big_endian {
+            value.to_be_bytes()[8 - size..].to_vec()
+        } else {
+            value.to_le_bytes()[..size].to_vec()
+        };
+
+        output.extend_from_slice(&bytes);
+    }
+
+    /// Pack a single value of the given type.
+    fn pack_value(
+        &self,
+        output: &mut Vec<u8>,
+        name: &str,
+        field_type: &LayoutType,
+        value: &Value,
+    ) -> Result<(), String> {
+        match field_type {
+            LayoutType::Int { size, is_signed } => {
+                if !value.is_numeric() {
+                    return Err(format!(
+                        "Field {} of {} expected a number, found {}.",
+                        name,
+                        self.name(),
+                        value
+                    ));
+                }
+
+                let value = value.get_int_val();
+
+                // Every i64 fits in a u64 field, as u64 values come back from unpacking as the
+                // same bits.
+                let (min, max) = match (size, is_signed) {
+                    (8, _) => (i64::MIN, i64::MAX),
+                    (_, true) => (-(1 << (size * 8 - 1)), (1 << (size * 8 - 1)) - 1),
+                    (_, false) => (0, (1 << (size * 8)) - 1),
+                };
+
+                if value < min || value > max {
+                    return Err(format!(
+                        "Field {} of {} can only hold values from {} to {}, found {}.",
+                        name,
+                        self.name(),
+                        min,
+                        max,
+                        value
+                    ));
+                }
+
+                self.pack_int(output, *size, value);
+            }
+
+            LayoutType::Float(size) => {
+                if !value.is_numeric() {
+                    return Err(format!(
+                        "Field {} of {} expected a number, found {}.",
+                        name,
+                        self.name(),
+                        value
+                    ));
+                }
+
+                let value = value.get_float_val();
+
+                match (size, self.is_big_endian) {
+                    (4, false) => output.extend_from_slice(&(value as f32).to_le_bytes()),
+                    (4, true) => output.extend_from_slice(&(value as f32).to_be_bytes()),
+                    (_, false) => output.extend_from_slice(&value.to_le_bytes()),
+                    (_, true) => output.extend_from_slice(&value.to_be_bytes()),
+                }
+            }
+
+            LayoutType::FixedString(size) => {
+                if !value.is_stringable() {
+                    return Err(format!(
+                        "Field {} of {} expected a string, found {}.",
+                        name,
+                        self.name(),
+                        value
+                    ));
+                }
+
+                let text = value.get_string_val();
+
+                if text.len() > *size {
+                    return Err(format!(
+                        "Field {} of {} can hold {} bytes, but the string is {} bytes long.",
+                        name,
+                        self.name(),
+                        size,
+                        text.len()
+                    ));
+                }
+
+                output.extend_from_slice(text.as_bytes());
+                output.resize(output.len() + size - text.len(), 0);
+            }
+
+            LayoutType::PrefixedString(prefix_size) => {
+                if !value.is_stringable() {
+                    return Err(format!(
+                        "Field {} of {} expected a string, found {}.",
+                        name,
+                        self.name(),
+                        value
+                    ));
+                }
+
+                let text = value.get_string_val();
+
+                if *prefix_size < 8 && text.len() >= 1 << (prefix_size * 8) {
+                    return Err(format!(
+                        "Field {} of {} is too long for a {} byte length prefix.",
+                        name,
+                        self.name(),
+                        prefix_size
+                    ));
+                }
+
+                self.pack_int(output, *prefix_size, text.len() as i64);
+                output.extend_from_slice(text.as_bytes());
+            }
+
+            LayoutType::Nested(layout) => match value {
+                Value::DataObject(data_ptr)
+                    if data_ptr.borrow().definition_ptr.borrow().name() == &layout.name() =>
+                {
+                    layout.pack(output, &data_ptr.borrow().fields)?;
+                }
+
+                _ => {
+                    return Err(format!(
+                        "Field {} of {} expected a {} structure, found {}.",
+                        name,
+                        self.name(),
+                        layout.name(),
+                        value
+                    ));
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Pack all of the fields of a structure.
+    fn pack(&self, output: &mut Vec<u8>, fields: &[Value]) -> Result<(), String> {
+        for item in &self.items {
+            match item {
+                LayoutItem::Padding(size) => output.resize(output.len() + size, 0),
+
+                LayoutItem::Field {
+                    name,
+                    field_index,
+                    field_type,
+                    count: None,
+                } => self.pack_value(output, name, field_type, &fields[*field_index])?,
+
+                LayoutItem::Field {
+                    name,
+                    field_index,
+                    field_type,
+                    count: Some(count),
+                } => match &fields[*field_index] {
+                    Value::Vec(items) if items.borrow().len() == *count => {
+                        for value in items.borrow().iter() {
+                            self.pack_value(output, name, field_type, value)?;
+                        }
+                    }
+
+                    value => {
+                        return Err(format!(
+                            "Field {} of {} expected an array of {} items, found {}.",
+                            name,
+                            self.name(),
+                            count,
+                            value
+                        ));
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Take the next bytes from the input, reporting an error if the input is too short.
+    fn take<'a>(
+        &self,
+        input: &'a [u8],
+        position: &mut usize,
+        size: usize,
+    ) -> Result<&'a [u8], String> {
+        if input.len() - *position < size {
+            return Err(format!(
+                "Buffer is too short to unpack {}, needed {} bytes at offset {}.",
+                self.name(),
+                size,
+                *position
+            ));
+        }
+
+        let bytes = &input[*position..*position + size];
+
+        *position += size;
+        Ok(bytes)
+    }
+
+    /// Read an integer of the given size using the layout's byte order.
+    fn unpack_int(
+        &self,
+        input: &[u8],
+        position: &mut usize,
+        size: usize,
+        is_signed: bool,
+    ) -> Result<i64, String> {
+        let bytes = self.take(input, position, size)?;
+        let mut full = [0u8; 8];
+
+        if self.is_big_endian {
+            full[8 - size..].copy_from_slice(bytes);
+        } else {
+            full[..size].copy_from_slice(bytes);
+        }
+
+        let value = if self.is_big_endian {
+            u64::from_be_bytes(full)
+        } else {
+            u64::from_le_bytes(full)
+        };
+
+        // Sign extend the value if needed.
+        let shift = 64 - size * 8;
+
+        Ok(if is_signed && shift > 0 {
+            ((value << shift) as i64) >> shift
+        } else {
+            value as i64
+        })
+    }
+
+    /// Unpack a single value of the given type.
+    fn unpack_value(
+        &self,
+        interpreter: &dyn Interpreter,
+        input: &[u8],
+        position: &mut usize,
+        field_type: &LayoutType,
+    ) -> Result<Value, String> {
+        let value = match field_type {
+            LayoutType::Int { size, is_signed } => self
+                .unpack_int(input, position, *size, *is_signed)?
+                .to_value(),
+
+            LayoutType::Float(size) => {
+                let bytes = self.take(input, position, *size)?;
+
+                match (size, self.is_big_endian) {
+                    (4, false) => (f32::from_le_bytes(bytes.try_into().unwrap()) as f64).to_value(),
+                    (4, true) => (f32::from_be_bytes(bytes.try_into().unwrap()) as f64).to_value(),
+                    (_, false) => f64::from_le_bytes(bytes.try_into().unwrap()).to_value(),
+                    (_, true) => f64::from_be_bytes(bytes.try_into().unwrap()).to_value(),
+                }
+            }
+
+            LayoutType::FixedString(size) => {
+                let bytes = self.take(input, position, *size)?;
+                let end = bytes.iter().position(|&byte| byte == 0).unwrap_or(*size);
+
+                String::from_utf8_lossy(&bytes[..end])
+                    .to_string()
+                    .to_value()
+            }
+
+            LayoutType::PrefixedString(prefix_size) => {
+                let size = self.unpack_int(input, position, *prefix_size, false)? as usize;
+                let bytes = self.take(input, position, size)?;
+
+                String::from_utf8_lossy(bytes).to_string().to_value()
+            }
+
+            LayoutType::Nested(layout) => layout.unpack(interpreter, input, position)?,
+        };
+
+        Ok(value)
+    }
+
+    /// Unpack a new structure from the input.
+    fn unpack(
+        &self,
+        interpreter: &dyn Interpreter,
+        input: &[u8],
+        position: &mut usize,
+    ) -> Result<Value, String> {
+        let data_ptr = DataObject::new(&self.definition);
+
+        for item in &self.items {
+            match item {
+                LayoutItem::Padding(size) => {
+                    self.take(input, position, *size)?;
+                }
+
+                LayoutItem::Field {
+                    field_index,
+                    field_type,
+                    count,
+                    ..
+                } => {
+                    let value = match count {
+                        None => self.unpack_value(interpreter, input, position, field_type)?,
+
+                        Some(count) => {
+                            let mut values = Vec::with_capacity(*count);
+
+                            for _ in 0..*count {
+                                values.push(self.unpack_value(
+                                    interpreter,
+                                    input,
+                                    position,
+                                    field_type,
+                                )?);
+                            }
+
+                            ValueVec::from_vec(values).to_value()
+                        }
+                    };
+
+                    data_ptr.borrow_mut().fields[*field_index] = value;
+                }
+            }
+        }
+
+        Ok(data_ptr.to_value())
+    }
+}
+
+/// Parse a size given within square brackets, for example the 16 in string[16].
+fn parse_bracket_size(text: &str) -> Option<(&str, usize)> {
+    let base_end = text.strip_suffix(']')?.rfind('[')?;
+    let size = text[base_end + 1..text.len() - 1].parse().ok()?;
+
+    Some((&text[..base_end], size))
+}
+
+/// Parse a base type name, without any array count.
+fn parse_base_type(interpreter: &dyn Interpreter, text: &str) -> Option<LayoutType> {
+    let layout_type = match text {
+        "u8" => LayoutType::Int {
+            size: 1,
+            is_signed: false,
+        },
+        "u16" => LayoutType::Int {
+            size: 2,
+            is_signed: false,
+        },
+        "u32" => LayoutType::Int {
+            size: 4,
+            is_signed: false,
+        },
+        "u64" => LayoutType::Int {
+            size: 8,
+            is_signed: false,
+        },
+        "i8" => LayoutType::Int {
+            size: 1,
+            is_signed: true,
+        },
+        "i16" => LayoutType::Int {
+            size: 2,
+            is_signed: true,
+        },
+        "i32" => LayoutType::Int {
+            size: 4,
+            is_signed: true,
+        },
+        "i64" => LayoutType::Int {
+            size: 8,
+            is_signed: true,
+        },
+        "f32" => LayoutType::Float(4),
+        "f64" => LayoutType::Float(8),
+        "string/u8" => LayoutType::PrefixedString(1),
+        "string/u16" => LayoutType::PrefixedString(2),
+        "string/u32" => LayoutType::PrefixedString(4),
+
+        _ => match parse_bracket_size(text) {
+            Some(("string", size)) => LayoutType::FixedString(size),
+            Some(_) => return None,
+            None => LayoutType::Nested(interpreter.binary_layouts().layouts.get(text)?.clone()),
+        },
+    };
+
+    Some(layout_type)
+}
+
+/// Parse a full field type, including an optional array count.  For example u16[4] is an array of
+/// four 16 bit integers, while string[16][2] is an array of two fixed size strings.
+fn parse_type(interpreter: &dyn Interpreter, text: &str) -> Option<(LayoutType, Option<usize>)> {
+    if let Some(layout_type) = parse_base_type(interpreter, text) {
+        return Some((layout_type, None));
+    }
+
+    let (base, count) = parse_bracket_size(text)?;
+
+    Some((parse_base_type(interpreter, base)?, Some(count)))
+}
+
+/// The default value for a newly created structure field of the given type.
+fn default_value(field_type: &LayoutType, count: Option<usize>) -> Value {
+    if count.is_some() {
+        return Value::None;
+    }
+
+    match field_type {
+        LayoutType::Int { .. } => 0i64.to_value(),
+        LayoutType::Float(_) => 0.0.to_value(),
+        LayoutType::FixedString(_) | LayoutType::PrefixedString(_) => String::new().to_value(),
+        LayoutType::Nested(_) => Value::None,
+    }
+}
+
+/// Create a new binary layout and it's pack and unpack words.  This word is not intended to be
+/// called directly by user code.  Instead the Forth word binary-layout handles the definition
+/// syntax and calls this word.
+///
+/// If a structure with the layout's name already exists the layout is tied to it, otherwise a new
+/// structure is defined with the layout's fields.
+///
+/// Signature: `name items -- `
+fn word_binary_layout_define(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let location = interpreter.current_location().clone();
+    let items = interpreter.pop_as_array()?;
+    let name = interpreter.pop_as_string()?;
+
+    let tokens: Vec<String> = items
+        .borrow()
+        .iter()
+        .map(|item| item.get_string_val())
+        .collect();
+
+    let mut is_big_endian = false;
+    let mut parsed = Vec::new();
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let token = &tokens[index];
+
+        if token == "endian" {
+            is_big_endian = match tokens.get(index + 1).map(|text| text.as_str()) {
+                Some("big") => true,
+                Some("little") => false,
+                _ => {
+                    return script_error(
+                        interpreter,
+                        format!("Layout {} endian must be big or little.", name),
+                    );
+                }
+            };
+
+            index += 2;
+            continue;
+        }
+
+        if let Some(("pad", size)) = parse_bracket_size(token) {
+            parsed.push((None, None, Some(size)));
+            index += 1;
+            continue;
+        }
+
+        let Some(type_text) = tokens.get(index + 1) else {
+            return script_error(
+                interpreter,
+                format!("Layout {} field {} is missing it's type.", name, token),
+            );
+        };
+
+        let Some((field_type, count)) = parse_type(interpreter, type_text) else {
+            return script_error(
+                interpreter,
+                format!(
+                    "Layout {} field {} has unknown type {}.",
+                    name, token, type_text
+                ),
+            );
+        };
+
+        parsed.push((Some(token.clone()), Some((field_type, count)), None));
+        index += 2;
+    }
+
+    // Find the structure to tie the layout to, creating it if it doesn't exist yet.
+    let existing = interpreter
+        .structure_definitions()
+        .iter()
+        .filter(|definition| *definition.borrow().name() == name)
+        .last()
+        .cloned();
+
+    let definition = match existing {
+        Some(definition) => definition,
+
+        None => {
+            let mut field_names = Vec::new();
+            let mut defaults = Vec::new();
+
+            for (field_name, field, _) in &parsed {
+                if let (Some(field_name), Some((field_type, count))) = (field_name, field) {
+                    field_names.push(field_name.clone());
+                    defaults.push(default_value(field_type, *count));
+                }
+            }
+
+            let definition =
+                DataObjectDefinition::new(interpreter, name.clone(), field_names, defaults, false);
+
+            DataObjectDefinition::create_data_definition_words(
+                interpreter,
+                location.clone(),
+                definition.clone(),
+                false,
+            );
+
+            definition
+        }
+    };
+
+    let mut layout_items = Vec::new();
+
+    for (field_name, field, padding) in parsed {
+        if let Some(size) = padding {
+            layout_items.push(LayoutItem::Padding(size));
+            continue;
+        }
+
+        let (Some(field_name), Some((field_type, count))) = (field_name, field) else {
+            continue;
+        };
+
+        let field_index = definition
+            .borrow()
+            .field_names()
+            .iter()
+            .position(|existing| *existing == field_name);
+
+        let Some(field_index) = field_index else {
+            return script_error(
+                interpreter,
+                format!("Structure {} has no field named {}.", name, field_name),
+            );
+        };
+
+        layout_items.push(LayoutItem::Field {
+            name: field_name,
+            field_index,
+            field_type,
+            count,
+        });
+    }
+
+    let layout = Rc::new(BinaryLayout {
+        definition,
+        items: layout_items,
+        is_big_endian,
+    });
+
+    interpreter
+        .binary_layouts_mut()
+        .layouts
+        .insert(name.clone(), layout.clone());
+
+    let (path, line, column) = match location {
+        Some(location) => (location.path().clone(), location.line(), location.column()),
+        None => (file!().to_string(), line!() as usize, column!() as usize),
+    };
+
+    let pack_layout = layout.clone();
+
+    interpreter.add_word(
+        path.clone(),
+        line,
+        column,
+        format!("{}.pack", name),
+        Rc::new(
+            move |interpreter: &mut dyn Interpreter| -> error::Result<()> {
+                let data_ptr = interpreter.pop_as_data_object()?;
+
+                if data_ptr.borrow().definition_ptr.borrow().name() != &pack_layout.name() {
+                    return script_error(
+                        interpreter,
+                        format!(
+                            "Expected a {} structure to pack, found {}.",
+                            pack_layout.name(),
+                            data_ptr.borrow().definition_ptr.borrow().name()
+                        ),
+                    );
+                }
+
+                let mut bytes = Vec::new();
+
+                if let Err(message) = pack_layout.pack(&mut bytes, &data_ptr.borrow().fields) {
+                    return script_error(interpreter, message);
+                }
+
+                let buffer = ByteBuffer::new_ptr(bytes.len());
+
+                buffer.borrow_mut().buffer_mut().copy_from_slice(&bytes);
+                interpreter.push(buffer.to_value());
+
+                Ok(())
+            },
+        ),
+        format!("Pack a {} structure into a new byte buffer.", name),
+        "structure -- buffer".to_string(),
+        WordRuntime::Normal,
+        WordVisibility::Visible,
+        WordType::Native,
+    );
+
+    let unpack_layout = layout;
+
+    interpreter.add_word(
+        path,
+        line,
+        column,
+        format!("{}.unpack", name),
+        Rc::new(
+            move |interpreter: &mut dyn Interpreter| -> error::Result<()> {
+                let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+
+                let result = {
+                    let buffer = buffer_ptr.borrow();
+                    let bytes = buffer.get_bytes(0, buffer.len());
+                    let mut position = buffer.position();
+
+                    unpack_layout
+                        .unpack(interpreter, &bytes, &mut position)
+                        .map(|value| (value, position))
+                };
+
+                match result {
+                    Ok((value, position)) => {
+                        buffer_ptr.borrow_mut().set_position(position);
+                        interpreter.push(value);
+                        Ok(())
+                    }
+
+                    Err(message) => script_error(interpreter, message),
+                }
+            },
+        ),
+        format!(
+            "Unpack a {} structure from the buffer's current position.",
+            name
+        ),
+        "buffer -- structure".to_string(),
+        WordRuntime::Normal,
+        WordVisibility::Visible,
+        WordType::Native,
+    );
+
+    Ok(())
+}
+
+/// Register the binary layout words.
+pub fn register_binary_layout_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "binary-layout.define",
+        word_binary_layout_define,
+        "Create a binary layout and it's pack and unpack words.  Used by binary-layout.",
+        "name items -- "
+    );
+}
@@ -0,0 +1,425 @@
+use crate::{
+    add_native_word,
+    runtime::{
+        built_ins::io_words::{read_fd_byte, write_fd_bytes},
+        data_structures::{
+            value::{ToValue, Value},
+            value_hash::ValueHash,
+            value_vec::ValueVec,
+        },
+        error::{self, script_error, script_error_str},
+        interpreter::Interpreter,
+    },
+};
+
+/// The options that control how CSV text is read and written.
+struct CsvOptions {
+    /// The character that separates fields within a row.
+    delimiter: u8,
+
+    /// The character used to quote fields.
+    quote: u8,
+
+    /// Is the first row a header row?
+    header: bool,
+}
+
+/// Pop the CSV options from the stack.  The options can either be none to use the defaults, or a
+/// hash table with any of the keys "delimiter", "quote", and "header".
+fn pop_csv_options(interpreter: &mut dyn Interpreter) -> error::Result<CsvOptions> {
+    fn get_char(
+        interpreter: &mut dyn Interpreter,
+        options: &ValueHash,
+        key: &str,
+        default: u8,
+    ) -> error::Result<u8> {
+        match options.get(&key.to_string().to_value()) {
+            None | Some(Value::None) => Ok(default),
+            Some(Value::String(text)) if text.len() == 1 && text.is_ascii() => {
+                Ok(text.as_bytes()[0])
+            }
+            Some(value) => script_error(
+                interpreter,
+                format!(
+                    "CSV {} must be a single ASCII character, found {}.",
+                    key, value
+                ),
+            ),
+        }
+    }
+
+    let mut csv_options = CsvOptions {
+        delimiter: b',',
+        quote: b'"',
+        header: false,
+    };
+
+    let value = interpreter.pop()?;
+
+    match value {
+        Value::None => (),
+
+        Value::HashMap(options) => {
+            let options = options.borrow();
+
+            csv_options.delimiter = get_char(interpreter, &options, "delimiter", b',')?;
+            csv_options.quote = get_char(interpreter, &options, "quote", b'"')?;
+
+            if let Some(header) = options.get(&"header".to_string().to_value()) {
+                csv_options.header = header.get_bool_val();
+            }
+
+            if csv_options.delimiter == csv_options.quote {
+                return script_error_str(
+                    interpreter,
+                    "CSV delimiter and quote characters must be different.",
+                );
+            }
+        }
+
+        _ => {
+            return script_error(
+                interpreter,
+                format!("CSV options must be none or a hash table, found {}.", value),
+            );
+        }
+    }
+
+    Ok(csv_options)
+}
+
+/// Where the row reader is within the current field.
+#[derive(PartialEq)]
+enum CsvState {
+    /// Reading an unquoted field, or at the start of a field.
+    Normal,
+
+    /// Inside of a quoted field.
+    Quoted,
+
+    /// Found a quote inside of a quoted field.  It's either the end of the field or the first half
+    /// of an escaped quote.
+    QuoteInQuoted,
+
+    /// Found a carriage return outside of quotes, which may be the first half of a line ending.
+    CarriageReturn,
+}
+
+/// Read a single row of fields from a source of bytes, following RFC 4180 quoting.  Quoted fields
+/// may contain delimiters, doubled quotes, and line breaks.  Blank lines are skipped and None is
+/// returned once the source is exhausted.
+fn read_csv_row<F>(options: &CsvOptions, next_byte: &mut F) -> Result<Option<Vec<String>>, String>
+where
+    F: FnMut() -> Result<Option<u8>, String>,
+{
+    let mut fields = Vec::new();
+    let mut field = Vec::new();
+    let mut field_started = false;
+    let mut state = CsvState::Normal;
+
+    fn finish_field(fields: &mut Vec<String>, field: &mut Vec<u8>) {
+        fields.push(String::from_utf8_lossy(field).to_string());
+        field.clear();
+    }
+
+    loop {
+        let byte = match next_byte()? {
+            Some(byte) => byte,
+
+            None => {
+                if state == CsvState::Quoted {
+                    return Err("Unterminated quoted field in CSV data.".to_string());
+                }
+
+                if fields.is_empty() && !field_started {
+                    return Ok(None);
+                }
+
+                finish_field(&mut fields, &mut field);
+                return Ok(Some(fields));
+            }
+        };
+
+        match state {
+            CsvState::Quoted => {
+                if byte == options.quote {
+                    state = CsvState::QuoteInQuoted;
+                } else {
+                    field.push(byte);
+                }
+
+                continue;
+            }
+
+            CsvState::QuoteInQuoted => {
+                if byte == options.quote {
+                    field.push(byte);
+                    state = CsvState::Quoted;
+                    continue;
+                }
+
+                state = CsvState::Normal;
+            }
+
+            CsvState::CarriageReturn => {
+                state = CsvState::Normal;
+
+                if byte != b'\n' {
+                    field.push(b'\r');
+                    field_started = true;
+                }
+            }
+
+            CsvState::Normal => (),
+        }
+
+        if byte == options.delimiter {
+            finish_field(&mut fields, &mut field);
+            field_started = true;
+        } else if byte == b'\n' {
+            if fields.is_empty() && !field_started {
+                continue;
+            }
+
+            finish_field(&mut fields, &mut field);
+            return Ok(Some(fields));
+        } else if byte == b'\r' {
+            state = CsvState::CarriageReturn;
+        } else if byte == options.quote && field.is_empty() {
+            state = CsvState::Quoted;
+            field_started = true;
+        } else {
+            field.push(byte);
+            field_started = true;
+        }
+    }
+}
+
+/// Convert a value to the text of a CSV field.  None becomes an empty field.
+fn field_text(value: &Value) -> String {
+    match value {
+        Value::None => String::new(),
+        Value::String(text) => text.clone(),
+        _ => value.to_string(),
+    }
+}
+
+/// Format a single row of fields as CSV text, including the line ending.  Fields are only quoted
+/// when they contain a delimiter, quote, or line break.
+fn format_csv_row(options: &CsvOptions, fields: &[String]) -> String {
+    let delimiter = options.delimiter as char;
+    let quote = options.quote as char;
+    let mut row = String::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            row.push(delimiter);
+        }
+
+        if field.contains([delimiter, quote, '\n', '\r']) {
+            row.push(quote);
+            row.push_str(&field.replace(quote, &format!("{}{}", quote, quote)));
+            row.push(quote);
+        } else {
+            row.push_str(field);
+        }
+    }
+
+    row.push_str("\r\n");
+    row
+}
+
+/// Convert an array row value into a list of field strings.
+fn array_row_fields(interpreter: &mut dyn Interpreter, row: &Value) -> error::Result<Vec<String>> {
+    match row {
+        Value::Vec(row) => Ok(row.borrow().iter().map(field_text).collect()),
+        _ => script_error(
+            interpreter,
+            format!("Expected a CSV row array, found {}.", row),
+        ),
+    }
+}
+
+/// Parse CSV text into an array of rows.  Each row is an array of strings, unless the header option
+/// is set, in which case each row is a hash table keyed by the header's column names.
+///
+/// Signature: `text options -- rows`
+fn word_csv_parse(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let options = pop_csv_options(interpreter)?;
+    let text = interpreter.pop_as_string()?;
+
+    let mut bytes = text.bytes();
+    let mut next_byte = || Ok(bytes.next());
+    let mut rows = Vec::new();
+
+    loop {
+        match read_csv_row(&options, &mut next_byte) {
+            Ok(Some(row)) => rows.push(row),
+            Ok(None) => break,
+            Err(message) => return script_error(interpreter, message),
+        }
+    }
+
+    let rows: Vec<Value> = if options.header && !rows.is_empty() {
+        let header = rows.remove(0);
+        let mut hashes = Vec::new();
+
+        for (row_index, row) in rows.into_iter().enumerate() {
+            if row.len() > header.len() {
+                return script_error(
+                    interpreter,
+                    format!(
+                        "CSV row {} has {} fields, but the header only has {}.",
+                        row_index + 2,
+                        row.len(),
+                        header.len()
+                    ),
+                );
+            }
+
+            let hash = ValueHash::new();
+
+            for (index, name) in header.iter().enumerate() {
+                let value = match row.get(index) {
+                    Some(field) => field.to_value(),
+                    None => Value::None,
+                };
+
+                hash.borrow_mut().insert(name.to_value(), value);
+            }
+
+            hashes.push(hash.to_value());
+        }
+
+        hashes
+    } else {
+        rows.into_iter().map(Value::from).collect()
+    };
+
+    interpreter.push(ValueVec::from_vec(rows).to_value());
+    Ok(())
+}
+
+/// Read the next row from an open file as an array of strings.  Rows may span multiple lines if
+/// they contain quoted line breaks.  None is pushed at the end of the file.
+///
+/// Signature: `fd options -- row_or_none`
+fn word_csv_read_row(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let options = pop_csv_options(interpreter)?;
+    let fd = interpreter.pop_as_int()?;
+
+    let row = {
+        let mut next_byte = || read_fd_byte(interpreter, fd).map_err(|error| error.to_string());
+        read_csv_row(&options, &mut next_byte)
+    };
+
+    match row {
+        Ok(Some(row)) => interpreter.push(Value::from(row)),
+        Ok(None) => interpreter.push(Value::None),
+        Err(message) => return script_error(interpreter, message),
+    }
+
+    Ok(())
+}
+
+/// Format an array of rows as CSV text.  Rows can either be arrays, or hash tables.  Hash table
+/// columns are every key used by any of the hash table rows, in sorted order.  Rows without one
+/// of the keys leave that column empty.  If the header option is set a header row of the keys is
+/// written first.
+///
+/// Signature: `rows options -- text`
+fn word_csv_format(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let options = pop_csv_options(interpreter)?;
+    let rows = interpreter.pop_as_array()?;
+    let rows: Vec<Value> = rows.borrow().iter().cloned().collect();
+
+    let mut columns: Vec<Value> = Vec::new();
+
+    for row in rows.iter() {
+        if let Value::HashMap(hash) = row {
+            columns.extend(hash.borrow().iter().map(|(key, _)| key.clone()));
+        }
+    }
+
+    columns.sort_by_key(field_text);
+    columns.dedup();
+
+    let mut text = String::new();
+    let mut needs_header = options.header;
+
+    for row in rows.iter() {
+        let fields = match row {
+            Value::HashMap(hash) => {
+                let hash = hash.borrow();
+
+                if needs_header {
+                    let names: Vec<String> = columns.iter().map(field_text).collect();
+
+                    text.push_str(&format_csv_row(&options, &names));
+                    needs_header = false;
+                }
+
+                columns
+                    .iter()
+                    .map(|key| hash.get(key).map(field_text).unwrap_or_default())
+                    .collect()
+            }
+
+            _ => array_row_fields(interpreter, row)?,
+        };
+
+        text.push_str(&format_csv_row(&options, &fields));
+    }
+
+    interpreter.push(text.to_value());
+    Ok(())
+}
+
+/// Write a single array row to an open file as CSV text.
+///
+/// Signature: `row fd options -- `
+fn word_csv_write_row(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let options = pop_csv_options(interpreter)?;
+    let fd = interpreter.pop_as_int()?;
+    let row = interpreter.pop()?;
+
+    let fields = array_row_fields(interpreter, &row)?;
+    let text = format_csv_row(&options, &fields);
+
+    write_fd_bytes(interpreter, fd, text.as_bytes())
+}
+
+/// Register the CSV words with the interpreter.
+pub fn register_csv_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "csv.parse",
+        word_csv_parse,
+        "Parse CSV text into an array of rows.  Options are none or a hash table.",
+        "text options -- rows"
+    );
+
+    add_native_word!(
+        interpreter,
+        "csv.read-row",
+        word_csv_read_row,
+        "Read the next row from an open file, or none at the end of the file.",
+        "fd options -- row_or_none"
+    );
+
+    add_native_word!(
+        interpreter,
+        "csv.format",
+        word_csv_format,
+        "Format an array of array or hash table rows as CSV text.",
+        "rows options -- text"
+    );
+
+    add_native_word!(
+        interpreter,
+        "csv.write-row",
+        word_csv_write_row,
+        "Write a single row array to an open file as CSV text.",
+        "row fd options -- "
+    );
+}
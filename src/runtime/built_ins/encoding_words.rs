@@ -0,0 +1,405 @@
+use crate::{
+    add_native_word,
+    runtime::{
+        data_structures::{
+            byte_buffer::ByteBuffer,
+            value::{ToValue, Value},
+        },
+        error::{self, script_error},
+        interpreter::Interpreter,
+    },
+};
+use base64::{
+    Engine,
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+};
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+/// Pop a value to encode or hash.  Byte buffers use their full contents, regardless of their
+/// cursor, and strings use their UTF-8 bytes.
+fn pop_as_bytes(interpreter: &mut dyn Interpreter) -> error::Result<Vec<u8>> {
+    let value = interpreter.pop()?;
+
+    match value {
+        Value::ByteBuffer(buffer) => {
+            let buffer = buffer.borrow();
+
+            if let Err(message) = buffer.check_bounds() {
+                return script_error(interpreter, message);
+            }
+
+            Ok(buffer.get_bytes(0, buffer.len()))
+        }
+
+        Value::String(text) => Ok(text.into_bytes()),
+
+        _ => script_error(
+            interpreter,
+            format!("Expected a byte buffer or string, found {}.", value),
+        ),
+    }
+}
+
+/// Push raw bytes as a new byte buffer.
+fn push_bytes(interpreter: &mut dyn Interpreter, bytes: &[u8]) {
+    let buffer = ByteBuffer::new_ptr(bytes.len());
+
+    buffer.borrow_mut().buffer_mut().copy_from_slice(bytes);
+    interpreter.push(buffer.to_value());
+}
+
+/// Convert bytes to a lower case hex string.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Convert a hex string, of either case, back into bytes.
+fn from_hex(text: &str) -> Result<Vec<u8>, String> {
+    if !text.len().is_multiple_of(2) {
+        return Err(format!(
+            "Hex string has an odd number of digits, {}.",
+            text.len()
+        ));
+    }
+
+    text.as_bytes()
+        .chunks(2)
+        .enumerate()
+        .map(|(index, pair)| {
+            // Parsing alone would also accept a leading sign, so each character is checked first.
+            if !pair.iter().all(u8::is_ascii_hexdigit) {
+                return Err(format!("Invalid hex digits at position {}.", index * 2));
+            }
+
+            let pair = std::str::from_utf8(pair).unwrap();
+
+            Ok(u8::from_str_radix(pair, 16).unwrap())
+        })
+        .collect()
+}
+
+/// Convert a buffer or string to a string of lower case hex digits.
+///
+/// Signature: `value -- hex_string`
+fn word_buffer_to_hex(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let bytes = pop_as_bytes(interpreter)?;
+
+    interpreter.push(to_hex(&bytes).to_value());
+
+    Ok(())
+}
+
+/// Decode a string of hex digits into a new byte buffer.
+///
+/// Signature: `hex_string -- buffer`
+fn word_hex_decode(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let text = interpreter.pop_as_string()?;
+
+    match from_hex(text.trim()) {
+        Ok(bytes) => push_bytes(interpreter, &bytes),
+        Err(message) => script_error(interpreter, message)?,
+    }
+
+    Ok(())
+}
+
+/// Encode a buffer or string as standard, padded, base64 text.
+///
+/// Signature: `value -- base64_string`
+fn word_base64_encode(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let bytes = pop_as_bytes(interpreter)?;
+
+    interpreter.push(STANDARD.encode(bytes).to_value());
+
+    Ok(())
+}
+
+/// Decode standard base64 text into a new byte buffer.
+///
+/// Signature: `base64_string -- buffer`
+fn word_base64_decode(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let text = interpreter.pop_as_string()?;
+
+    match STANDARD.decode(text.trim()) {
+        Ok(bytes) => push_bytes(interpreter, &bytes),
+        Err(error) => script_error(interpreter, format!("Invalid base64 text: {}.", error))?,
+    }
+
+    Ok(())
+}
+
+/// Encode a buffer or string as URL-safe base64 text, without padding.
+///
+/// Signature: `value -- base64_string`
+fn word_base64_url_encode(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let bytes = pop_as_bytes(interpreter)?;
+
+    interpreter.push(URL_SAFE_NO_PAD.encode(bytes).to_value());
+
+    Ok(())
+}
+
+/// Decode URL-safe base64 text into a new byte buffer.  Trailing padding is accepted but not
+/// required.
+///
+/// Signature: `base64_string -- buffer`
+fn word_base64_url_decode(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let text = interpreter.pop_as_string()?;
+
+    match URL_SAFE_NO_PAD.decode(text.trim().trim_end_matches('=')) {
+        Ok(bytes) => push_bytes(interpreter, &bytes),
+        Err(error) => script_error(interpreter, format!("Invalid base64 text: {}.", error))?,
+    }
+
+    Ok(())
+}
+
+/// Compute the CRC-32 checksum of a buffer or string.
+///
+/// Signature: `value -- checksum`
+fn word_crc32(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let bytes = pop_as_bytes(interpreter)?;
+
+    interpreter.push((crc32fast::hash(&bytes) as i64).to_value());
+
+    Ok(())
+}
+
+/// Compute a digest of the value on the stack, pushing it as either a buffer or a hex string.
+fn digest_word<D: Digest>(interpreter: &mut dyn Interpreter, as_hex: bool) -> error::Result<()> {
+    let bytes = pop_as_bytes(interpreter)?;
+    let digest = D::digest(&bytes);
+
+    if as_hex {
+        interpreter.push(to_hex(&digest).to_value());
+    } else {
+        push_bytes(interpreter, &digest);
+    }
+
+    Ok(())
+}
+
+/// Compute the SHA-256 digest of a buffer or string.
+///
+/// Signature: `value -- digest_buffer`
+fn word_sha256(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    digest_word::<Sha256>(interpreter, false)
+}
+
+/// Compute the SHA-256 digest of a buffer or string as a hex string.
+///
+/// Signature: `value -- hex_string`
+fn word_sha256_hex(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    digest_word::<Sha256>(interpreter, true)
+}
+
+/// Compute the SHA-1 digest of a buffer or string.
+///
+/// Signature: `value -- digest_buffer`
+fn word_sha1(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    digest_word::<Sha1>(interpreter, false)
+}
+
+/// Compute the SHA-1 digest of a buffer or string as a hex string.
+///
+/// Signature: `value -- hex_string`
+fn word_sha1_hex(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    digest_word::<Sha1>(interpreter, true)
+}
+
+/// Compute the MD5 digest of a buffer or string.  Only meant for legacy formats, MD5 is not secure.
+///
+/// Signature: `value -- digest_buffer`
+fn word_md5(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    digest_word::<Md5>(interpreter, false)
+}
+
+/// Compute the MD5 digest of a buffer or string as a hex string.
+///
+/// Signature: `value -- hex_string`
+fn word_md5_hex(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    digest_word::<Md5>(interpreter, true)
+}
+
+/// Compute the HMAC-SHA256 of a message with the given key, pushing it as either a buffer or a hex
+/// string.
+fn hmac_sha256(interpreter: &mut dyn Interpreter, as_hex: bool) -> error::Result<()> {
+    let key = pop_as_bytes(interpreter)?;
+    let message = pop_as_bytes(interpreter)?;
+
+    // HMAC accepts keys of any length, so this can not fail.
+    let mut mac = Hmac::<Sha256>::new_from_slice(&key).expect("HMAC accepts any key length.");
+
+    mac.update(&message);
+
+    let digest = mac.finalize().into_bytes();
+
+    if as_hex {
+        interpreter.push(to_hex(&digest).to_value());
+    } else {
+        push_bytes(interpreter, &digest);
+    }
+
+    Ok(())
+}
+
+/// Compute the HMAC-SHA256 of a message with the given key.  Both can be buffers or strings.
+///
+/// Signature: `message key -- digest_buffer`
+fn word_hmac_sha256(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    hmac_sha256(interpreter, false)
+}
+
+/// Compute the HMAC-SHA256 of a message with the given key as a hex string.
+///
+/// Signature: `message key -- hex_string`
+fn word_hmac_sha256_hex(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    hmac_sha256(interpreter, true)
+}
+
+/// Print a hex dump of a buffer along with it's cursor position, for debugging.
+///
+/// Signature: `buffer -- `
+fn word_buffer_hexdump(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let buffer = buffer.borrow();
+
+    print!("{}", &*buffer);
+    println!("Size: {}, position: {}", buffer.len(), buffer.position());
+
+    Ok(())
+}
+
+/// Register the encoding and hashing words with the interpreter.
+pub fn register_encoding_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "buffer.to-hex",
+        word_buffer_to_hex,
+        "Convert a buffer or string to a string of hex digits.",
+        "value -- hex_string"
+    );
+
+    add_native_word!(
+        interpreter,
+        "hex.decode",
+        word_hex_decode,
+        "Decode a string of hex digits into a new buffer.",
+        "hex_string -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "base64.encode",
+        word_base64_encode,
+        "Encode a buffer or string as standard base64 text.",
+        "value -- base64_string"
+    );
+
+    add_native_word!(
+        interpreter,
+        "base64.decode",
+        word_base64_decode,
+        "Decode standard base64 text into a new buffer.",
+        "base64_string -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "base64.url-encode",
+        word_base64_url_encode,
+        "Encode a buffer or string as unpadded URL-safe base64 text.",
+        "value -- base64_string"
+    );
+
+    add_native_word!(
+        interpreter,
+        "base64.url-decode",
+        word_base64_url_decode,
+        "Decode URL-safe base64 text, with or without padding, into a new buffer.",
+        "base64_string -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "crc32",
+        word_crc32,
+        "Compute the CRC-32 checksum of a buffer or string.",
+        "value -- checksum"
+    );
+
+    add_native_word!(
+        interpreter,
+        "sha256",
+        word_sha256,
+        "Compute the SHA-256 digest of a buffer or string.",
+        "value -- digest_buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "sha256.hex",
+        word_sha256_hex,
+        "Compute the SHA-256 digest of a buffer or string as hex.",
+        "value -- hex_string"
+    );
+
+    add_native_word!(
+        interpreter,
+        "sha1",
+        word_sha1,
+        "Compute the SHA-1 digest of a buffer or string.",
+        "value -- digest_buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "sha1.hex",
+        word_sha1_hex,
+        "Compute the SHA-1 digest of a buffer or string as hex.",
+        "value -- hex_string"
+    );
+
+    add_native_word!(
+        interpreter,
+        "md5",
+        word_md5,
+        "Compute the MD5 digest of a buffer or string, for legacy formats.",
+        "value -- digest_buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "md5.hex",
+        word_md5_hex,
+        "Compute the MD5 digest of a buffer or string as hex.",
+        "value -- hex_string"
+    );
+
+    add_native_word!(
+        interpreter,
+        "hmac-sha256",
+        word_hmac_sha256,
+        "Compute the HMAC-SHA256 of a message with a key.",
+        "message key -- digest_buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "hmac-sha256.hex",
+        word_hmac_sha256_hex,
+        "Compute the HMAC-SHA256 of a message with a key as hex.",
+        "message key -- hex_string"
+    );
+
+    add_native_word!(
+        interpreter,
+        "buffer.hexdump",
+        word_buffer_hexdump,
+        "Print a hex dump of a buffer and it's position for debugging.",
+        "buffer -- "
+    );
+}
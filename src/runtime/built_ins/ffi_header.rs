@@ -0,0 +1,1307 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs::read_to_string,
+    os::raw::c_long,
+    path::Path,
+};
+
+/// The sorth bindings generated from a C header.
+pub struct HeaderBindings {
+    /// Sorth source code that loads the library and registers the header's functions, structures
+    /// and constants.
+    pub source: String,
+
+    /// A description of each declaration in the header that couldn't be mapped to the ffi.
+    pub unmapped: Vec<String>,
+}
+
+/// Read a C header from disk and generate the bindings for the library that implements it.
+pub fn bind_header_file(lib_path: &str, header_path: &str) -> std::io::Result<HeaderBindings> {
+    let header = read_to_string(header_path)?;
+
+    Ok(generate_bindings(lib_path, header_path, &header))
+}
+
+/// Generate the sorth bindings for a C header.  A practical subset of C is understood, function
+/// prototypes, typedefs, structures, enumerations and #define integer constants.  Anything else is
+/// skipped and reported in the unmapped list.
+pub fn generate_bindings(lib_path: &str, header_path: &str, header: &str) -> HeaderBindings {
+    let lib_alias = library_alias(header_path);
+    let mut generator = Generator::new(lib_alias.clone());
+
+    generator.source.push_str(&format!(
+        "( Generated from {} by ffi.bind-header. )\n\nffi.load {} as {}\n",
+        header_path.replace(['(', ')'], "_"),
+        quote(lib_path),
+        lib_alias
+    ));
+
+    // Directives are handled a line at a time, everything else is gathered up to be tokenized as
+    // one block of code.
+    let mut code = String::new();
+
+    for line in strip_comments(&header.replace("\\\r\n", " ").replace("\\\n", " ")).lines() {
+        let trimmed = line.trim_start();
+
+        if let Some(directive) = trimmed.strip_prefix('#') {
+            generator.directive(directive.trim_start());
+        } else {
+            code.push_str(line);
+            code.push('\n');
+        }
+    }
+
+    let tokens = tokenize(&code);
+
+    for (declaration, has_body) in split_declarations(&tokens) {
+        if let Err(message) = generator.declaration(declaration, has_body) {
+            generator.unmapped.push(message);
+        }
+    }
+
+    HeaderBindings {
+        source: generator.source,
+        unmapped: generator.unmapped,
+    }
+}
+
+/// A C type as it was declared in the header.
+#[derive(Clone, Debug)]
+enum CType {
+    Void,
+    Bool,
+
+    /// A plain char, pointers to these are treated as strings.
+    Char,
+
+    /// An integer type with it's size in bytes and whether it's signed.
+    Int(usize, bool),
+
+    Float,
+    Double,
+    LongDouble,
+    Enum,
+    Struct(String),
+    Union(String),
+
+    /// A type name the generator doesn't know, only usable as an opaque pointer.
+    Named(String),
+
+    /// A pointer, and whether the type pointed to is const.
+    Pointer(Box<CType>, bool),
+
+    /// An array of items, with a count if it was given.
+    Array(Box<CType>, Option<usize>),
+
+    Function(Box<CFunction>),
+}
+
+/// The signature of a C function.
+#[derive(Clone, Debug)]
+struct CFunction {
+    return_type: CType,
+    params: Vec<CType>,
+    is_variadic: bool,
+}
+
+/// Where a type is being used, which changes how some types are mapped.
+#[derive(Clone, Copy, PartialEq)]
+enum Usage {
+    Param,
+    Return,
+    Field,
+}
+
+/// The kind of a struct, union or enum in a declaration's type.
+#[derive(Clone, Copy, PartialEq)]
+enum AggregateKind {
+    Struct,
+    Union,
+    Enum,
+}
+
+/// A struct, union or enum found in a declaration's type, with it's body if it was defined there.
+struct Aggregate<'a> {
+    kind: AggregateKind,
+    tag: Option<String>,
+    body: Option<&'a [String]>,
+}
+
+/// The type specifiers at the start of a declaration.
+struct Specifiers<'a> {
+    ctype: CType,
+    is_const: bool,
+    is_static: bool,
+    aggregate: Option<Aggregate<'a>>,
+}
+
+/// A simple cursor for walking through a declaration's tokens.
+struct Cursor<'a> {
+    tokens: &'a [String],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(tokens: &'a [String]) -> Cursor<'a> {
+        Cursor {
+            tokens,
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&'a str> {
+        self.tokens
+            .get(self.position + offset)
+            .map(|token| token.as_str())
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let token = self.peek();
+
+        if token.is_some() {
+            self.position += 1;
+        }
+
+        token
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("expected {} but found {}", expected, token)),
+            None => Err(format!("expected {}", expected)),
+        }
+    }
+
+    fn remaining(&self) -> &'a [String] {
+        &self.tokens[self.position.min(self.tokens.len())..]
+    }
+
+    /// Consume a bracketed group of tokens, starting at the opening token, and return the tokens
+    /// between the brackets.
+    fn group(&mut self) -> Result<&'a [String], String> {
+        let start = self.position + 1;
+        let end = matching_close(self.tokens, self.position)
+            .ok_or_else(|| "unbalanced brackets".to_string())?;
+
+        self.position = end + 1;
+
+        Ok(&self.tokens[start..end])
+    }
+}
+
+/// The kinds of definition in the generated source, a blank line separates each run of them.
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    Header,
+    Constant,
+    Struct,
+    Function,
+}
+
+/// Builds up the generated source as the header's declarations are processed.
+struct Generator {
+    lib_alias: String,
+    section: Section,
+    typedefs: HashMap<String, CType>,
+    structs: HashSet<String>,
+    arrays: HashSet<String>,
+    constants: HashMap<String, i64>,
+    empty_macros: HashSet<String>,
+    source: String,
+    unmapped: Vec<String>,
+}
+
+impl Generator {
+    fn new(lib_alias: String) -> Generator {
+        Generator {
+            lib_alias,
+            section: Section::Header,
+            typedefs: HashMap::new(),
+            structs: HashSet::new(),
+            arrays: HashSet::new(),
+            constants: HashMap::new(),
+            empty_macros: HashSet::new(),
+            source: String::new(),
+            unmapped: Vec::new(),
+        }
+    }
+
+    /// Handle a preprocessor directive.  Only #define is looked at, conditionals and includes are
+    /// ignored.
+    fn directive(&mut self, directive: &str) {
+        let Some(definition) = directive.strip_prefix("define") else {
+            return;
+        };
+
+        let definition = definition.trim_start();
+        let name_end = definition
+            .find(|character: char| !(character.is_alphanumeric() || character == '_'))
+            .unwrap_or(definition.len());
+        let (name, value) = definition.split_at(name_end);
+
+        if name.is_empty() {
+            return;
+        }
+
+        if value.starts_with('(') {
+            self.unmapped.push(format!(
+                "macro {}: function-like macros are not supported",
+                name
+            ));
+            return;
+        }
+
+        let value = value.trim();
+
+        // Empty macros are usually include guards or export decorations, the latter are skipped
+        // when they show up in declarations.
+        if value.is_empty() {
+            self.empty_macros.insert(name.to_string());
+            return;
+        }
+
+        match evaluate(&tokenize(value), &self.constants) {
+            Some(constant) => self.define_constant(name, constant),
+            None => self.unmapped.push(format!(
+                "#define {}: {} is not an integer constant",
+                name, value
+            )),
+        }
+    }
+
+    /// Process one top level declaration of the header.
+    fn declaration(&mut self, tokens: &[String], has_body: bool) -> Result<(), String> {
+        let mut cursor = Cursor::new(tokens);
+        let is_typedef = cursor.peek() == Some("typedef");
+
+        if is_typedef {
+            cursor.next();
+        }
+
+        let specifiers = self
+            .specifiers(&mut cursor)
+            .map_err(|message| format!("{}: {}", describe(tokens), message))?;
+        let mut base = specifiers.ctype.clone();
+        let declarators = split_top_level(cursor.remaining(), ",");
+
+        if let Some(aggregate) = &specifiers.aggregate {
+            match aggregate.kind {
+                AggregateKind::Enum => {
+                    if let Some(body) = aggregate.body {
+                        self.define_enum(body)?;
+                    }
+                }
+
+                AggregateKind::Struct => {
+                    // Anonymous structures take the name of their typedef.
+                    let name = match (&aggregate.tag, is_typedef, declarators.first()) {
+                        (Some(tag), _, _) => tag.clone(),
+                        (None, true, Some(first)) => self
+                            .declarator(&mut Cursor::new(first), CType::Void, false)?
+                            .0
+                            .ok_or_else(|| "anonymous struct has no name".to_string())?,
+                        _ => return Err("anonymous struct: it has no name".to_string()),
+                    };
+
+                    if let Some(body) = aggregate.body {
+                        self.define_struct(&name, body)
+                            .map_err(|message| format!("struct {}: {}", name, message))?;
+                    }
+
+                    base = CType::Struct(name);
+                }
+
+                AggregateKind::Union => {
+                    let name = aggregate.tag.clone().unwrap_or("anonymous".to_string());
+
+                    if aggregate.body.is_some() {
+                        self.unmapped
+                            .push(format!("union {}: unions are not supported", name));
+                    }
+
+                    base = CType::Union(name);
+                }
+            }
+        }
+
+        for declarator in declarators {
+            let mut cursor = Cursor::new(declarator);
+            let (name, ctype) = self
+                .declarator(&mut cursor, base.clone(), specifiers.is_const)
+                .map_err(|message| format!("{}: {}", describe(tokens), message))?;
+
+            let Some(name) = name else {
+                return Err(format!("{}: declaration has no name", describe(tokens)));
+            };
+
+            if is_typedef {
+                self.typedefs.insert(name, ctype);
+                continue;
+            }
+
+            match ctype {
+                CType::Function(_) if has_body || specifiers.is_static => {
+                    self.unmapped.push(format!(
+                        "function {}: inline and static functions are not exported",
+                        name
+                    ));
+                }
+
+                CType::Function(function) => {
+                    if let Err(message) = self.define_function(&name, &function) {
+                        self.unmapped
+                            .push(format!("function {}: {}", name, message));
+                    }
+                }
+
+                _ => self.unmapped.push(format!(
+                    "variable {}: global variables can not be bound",
+                    name
+                )),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse the type specifiers and qualifiers at the start of a declaration.
+    fn specifiers<'a>(&self, cursor: &mut Cursor<'a>) -> Result<Specifiers<'a>, String> {
+        let mut is_const = false;
+        let mut is_static = false;
+        let mut signed = None;
+        let mut shorts = 0;
+        let mut longs = 0;
+        let mut base = None;
+        let mut named = None;
+        let mut aggregate = None;
+
+        while let Some(token) = cursor.peek() {
+            match token {
+                "const" => is_const = true,
+                "static" => is_static = true,
+
+                "volatile" | "restrict" | "__restrict" | "__restrict__" | "inline" | "__inline"
+                | "__inline__" | "extern" | "register" | "auto" | "__extension__" | "_Noreturn"
+                | "__cdecl" | "__stdcall" => {}
+
+                "__attribute__" | "__declspec" | "__asm__" | "__asm" | "_Alignas" => {
+                    cursor.next();
+
+                    if cursor.peek() == Some("(") {
+                        cursor.group()?;
+                    }
+
+                    continue;
+                }
+
+                "signed" => signed = Some(true),
+                "unsigned" => signed = Some(false),
+                "short" => shorts += 1,
+                "long" => longs += 1,
+
+                "void" | "char" | "int" | "float" | "double" | "_Bool" | "bool" => {
+                    base = Some(token)
+                }
+
+                "struct" | "union" | "enum" => {
+                    cursor.next();
+
+                    let kind = match token {
+                        "struct" => AggregateKind::Struct,
+                        "union" => AggregateKind::Union,
+                        _ => AggregateKind::Enum,
+                    };
+
+                    let tag = match cursor.peek() {
+                        Some(tag) if is_identifier(tag) => {
+                            cursor.next();
+                            Some(tag.to_string())
+                        }
+                        _ => None,
+                    };
+
+                    let body = match cursor.peek() {
+                        Some("{") => Some(cursor.group()?),
+                        _ => None,
+                    };
+
+                    if tag.is_none() && body.is_none() {
+                        return Err(format!("expected a name or body after {}", token));
+                    }
+
+                    aggregate = Some(Aggregate { kind, tag, body });
+                    continue;
+                }
+
+                _ if self.empty_macros.contains(token) => {}
+
+                _ if is_identifier(token)
+                    && base.is_none()
+                    && named.is_none()
+                    && aggregate.is_none()
+                    && signed.is_none()
+                    && shorts == 0
+                    && longs == 0 =>
+                {
+                    named = Some(self.named_type(token));
+                }
+
+                _ => break,
+            }
+
+            cursor.next();
+        }
+
+        let ctype = match (&aggregate, named, base) {
+            (Some(aggregate), _, _) => {
+                let tag = aggregate.tag.clone().unwrap_or_default();
+
+                match aggregate.kind {
+                    AggregateKind::Struct => CType::Struct(tag),
+                    AggregateKind::Union => CType::Union(tag),
+                    AggregateKind::Enum => CType::Enum,
+                }
+            }
+
+            (None, Some(named), _) => named,
+            (None, None, Some("void")) => CType::Void,
+            (None, None, Some("_Bool" | "bool")) => CType::Bool,
+            (None, None, Some("float")) => CType::Float,
+            (None, None, Some("double")) if longs > 0 => CType::LongDouble,
+            (None, None, Some("double")) => CType::Double,
+
+            (None, None, Some("char")) => match signed {
+                None => CType::Char,
+                Some(signed) => CType::Int(1, signed),
+            },
+
+            (None, None, _) => {
+                if signed.is_none() && shorts == 0 && longs == 0 && base.is_none() {
+                    return Err("expected a type".to_string());
+                }
+
+                let size = match (shorts, longs) {
+                    (0, 0) => 4,
+                    (_, 0) => 2,
+                    (_, 1) => size_of::<c_long>(),
+                    _ => 8,
+                };
+
+                CType::Int(size, signed.unwrap_or(true))
+            }
+        };
+
+        Ok(Specifiers {
+            ctype,
+            is_const,
+            is_static,
+            aggregate,
+        })
+    }
+
+    /// Look up a type name, either a typedef from the header or one of the standard integer types.
+    fn named_type(&self, name: &str) -> CType {
+        if let Some(ctype) = self.typedefs.get(name) {
+            return ctype.clone();
+        }
+
+        let pointer_size = size_of::<usize>();
+
+        match name {
+            "int8_t" => CType::Int(1, true),
+            "uint8_t" => CType::Int(1, false),
+            "int16_t" => CType::Int(2, true),
+            "uint16_t" => CType::Int(2, false),
+            "int32_t" => CType::Int(4, true),
+            "uint32_t" => CType::Int(4, false),
+            "int64_t" => CType::Int(8, true),
+            "uint64_t" => CType::Int(8, false),
+            "size_t" | "uintptr_t" => CType::Int(pointer_size, false),
+            "ssize_t" | "ptrdiff_t" | "intptr_t" => CType::Int(pointer_size, true),
+            _ => CType::Named(name.to_string()),
+        }
+    }
+
+    /// Parse a declarator, the part of a declaration that names the item and adds pointers, arrays
+    /// and function parameters to the base type.  Abstract declarators, without a name, are
+    /// allowed for parameters.
+    fn declarator(
+        &self,
+        cursor: &mut Cursor,
+        base: CType,
+        is_const: bool,
+    ) -> Result<(Option<String>, CType), String> {
+        let mut ctype = base;
+        let mut const_pointee = is_const;
+
+        while cursor.peek() == Some("*") {
+            cursor.next();
+
+            ctype = CType::Pointer(Box::new(ctype), const_pointee);
+            const_pointee = false;
+
+            while let Some(qualifier) = cursor.peek() {
+                match qualifier {
+                    "const" => const_pointee = true,
+                    "volatile" | "restrict" | "__restrict" | "__restrict__" => {}
+                    _ => break,
+                }
+
+                cursor.next();
+            }
+        }
+
+        // Function pointers, such as int (*compare)(int, int).
+        if cursor.peek() == Some("(") && cursor.peek_at(1) == Some("*") {
+            cursor.next();
+            cursor.next();
+
+            let name = match cursor.peek() {
+                Some(name) if is_identifier(name) => {
+                    cursor.next();
+                    Some(name.to_string())
+                }
+                _ => None,
+            };
+
+            cursor.expect(")")?;
+
+            if cursor.peek() != Some("(") {
+                return Err("only pointers to functions are supported in parentheses".to_string());
+            }
+
+            let (params, is_variadic) = self.params(cursor.group()?)?;
+            let function = CFunction {
+                return_type: ctype,
+                params,
+                is_variadic,
+            };
+
+            return Ok((
+                name,
+                CType::Pointer(Box::new(CType::Function(Box::new(function))), false),
+            ));
+        }
+
+        let name = match cursor.peek() {
+            Some(name) if is_identifier(name) && !self.empty_macros.contains(name) => {
+                cursor.next();
+                Some(name.to_string())
+            }
+            _ => None,
+        };
+
+        let mut dimensions = Vec::new();
+
+        while let Some(token) = cursor.peek() {
+            match token {
+                "[" => {
+                    let size = cursor.group()?;
+
+                    if size.is_empty() {
+                        dimensions.push(None);
+                    } else {
+                        match evaluate(size, &self.constants) {
+                            Some(count) if count >= 0 => dimensions.push(Some(count as usize)),
+                            _ => {
+                                return Err(format!(
+                                    "array size {} is not a constant",
+                                    size.join(" ")
+                                ));
+                            }
+                        }
+                    }
+                }
+
+                "(" => {
+                    let (params, is_variadic) = self.params(cursor.group()?)?;
+
+                    ctype = CType::Function(Box::new(CFunction {
+                        return_type: ctype,
+                        params,
+                        is_variadic,
+                    }));
+                }
+
+                "__attribute__" | "__asm__" | "__asm" => {
+                    cursor.next();
+
+                    if cursor.peek() == Some("(") {
+                        cursor.group()?;
+                    }
+                }
+
+                // Initializers are only found on variables, which aren't bound.
+                "=" => break,
+
+                _ if self.empty_macros.contains(token) => {
+                    cursor.next();
+                }
+
+                _ => return Err(format!("unexpected {}", token)),
+            }
+        }
+
+        for dimension in dimensions.into_iter().rev() {
+            ctype = CType::Array(Box::new(ctype), dimension);
+        }
+
+        Ok((name, ctype))
+    }
+
+    /// Parse a function's parameter list.
+    fn params(&self, tokens: &[String]) -> Result<(Vec<CType>, bool), String> {
+        let mut params = Vec::new();
+        let mut is_variadic = false;
+
+        if tokens.is_empty() || (tokens.len() == 1 && tokens[0] == "void") {
+            return Ok((params, is_variadic));
+        }
+
+        for param in split_top_level(tokens, ",") {
+            if param.len() == 1 && param[0] == "..." {
+                is_variadic = true;
+                continue;
+            }
+
+            let mut cursor = Cursor::new(param);
+            let specifiers = self.specifiers(&mut cursor)?;
+
+            if specifiers
+                .aggregate
+                .is_some_and(|aggregate| aggregate.body.is_some())
+            {
+                return Err("types can not be defined in parameters".to_string());
+            }
+
+            let (_, ctype) = self.declarator(&mut cursor, specifiers.ctype, specifiers.is_const)?;
+
+            // Arrays and functions are passed to C functions as pointers.
+            let ctype = match ctype {
+                CType::Array(element, _) => CType::Pointer(element, specifiers.is_const),
+                CType::Function(_) => CType::Pointer(Box::new(ctype), false),
+                ctype => ctype,
+            };
+
+            params.push(ctype);
+        }
+
+        Ok((params, is_variadic))
+    }
+
+    /// Map a C type to the name of the ffi type used to pass it.
+    fn map_type(&self, ctype: &CType, usage: Usage) -> Result<String, String> {
+        let name = match ctype {
+            CType::Void if usage == Usage::Return => "ffi.void",
+            CType::Void => return Err("void can only be returned".to_string()),
+            CType::Bool => "ffi.bool",
+            CType::Char | CType::Int(1, true) => "ffi.i8",
+            CType::Int(1, false) => "ffi.u8",
+            CType::Int(2, true) => "ffi.i16",
+            CType::Int(2, false) => "ffi.u16",
+            CType::Int(4, true) | CType::Enum => "ffi.i32",
+            CType::Int(4, false) => "ffi.u32",
+            CType::Int(8, true) => "ffi.i64",
+            CType::Int(8, false) => "ffi.u64",
+            CType::Int(size, _) => return Err(format!("{} byte integers are not supported", size)),
+            CType::Float => "ffi.f32",
+            CType::Double => "ffi.f64",
+            CType::LongDouble => return Err("long double is not supported".to_string()),
+
+            CType::Struct(name) if self.structs.contains(name) => name,
+            CType::Struct(name) => return Err(format!("struct {} is not defined", name)),
+            CType::Union(name) => return Err(format!("union {} is not supported", name)),
+            CType::Named(name) => return Err(format!("unknown type {}", name)),
+
+            CType::Function(_) => return Err("functions can only be passed by pointer".to_string()),
+
+            CType::Array(element, count) => {
+                return match (usage, count) {
+                    (Usage::Param, _) => {
+                        self.map_type(&CType::Pointer(element.clone(), false), usage)
+                    }
+
+                    (Usage::Field, Some(count)) => match **element {
+                        CType::Array(_, _) => {
+                            Err("multi-dimensional arrays are not supported".to_string())
+                        }
+                        _ => Ok(format!(
+                            "{}[{}]",
+                            self.map_type(element, Usage::Field)?,
+                            count
+                        )),
+                    },
+
+                    (Usage::Field, None) => Err("flexible array members are not supported".into()),
+                    (Usage::Return, _) => Err("arrays can not be returned".to_string()),
+                };
+            }
+
+            CType::Pointer(pointee, const_pointee) => match (&**pointee, usage) {
+                // Strings that are only read are passed as strings, otherwise C can write to them
+                // so they're passed as buffers.
+                (CType::Char, Usage::Param) if !*const_pointee => "ffi.buffer",
+                (CType::Char, _) => "ffi.string",
+
+                (CType::Function(_), _) => "ffi.fn-ptr",
+
+                (CType::Struct(name), _) if self.structs.contains(name) => {
+                    return Ok(format!("{}-ptr", name));
+                }
+
+                // Pointers to single values are copied back after the call so the function can
+                // write to them.
+                (
+                    CType::Bool | CType::Int(_, _) | CType::Float | CType::Double | CType::Enum,
+                    Usage::Param,
+                ) => return Ok(format!("{}-ptr", self.map_type(pointee, usage)?)),
+
+                (CType::Pointer(_, _), Usage::Param) => "ffi.ptr-ptr",
+
+                _ => "ffi.ptr",
+            },
+        };
+
+        Ok(name.to_string())
+    }
+
+    /// Generate the ffi.fn registration for a function.
+    fn define_function(&mut self, name: &str, function: &CFunction) -> Result<(), String> {
+        let mut line = format!("ffi.fn {} {}", self.lib_alias, name);
+
+        for (index, param) in function.params.iter().enumerate() {
+            let param = self
+                .map_type(param, Usage::Param)
+                .map_err(|message| format!("parameter {}: {}", index + 1, message))?;
+
+            line.push(' ');
+            line.push_str(&param);
+        }
+
+        if function.is_variadic {
+            line.push_str(" ...");
+        }
+
+        let return_type = self
+            .map_type(&function.return_type, Usage::Return)
+            .map_err(|message| format!("return type: {}", message))?;
+
+        line.push_str(" -> ");
+        line.push_str(&return_type);
+
+        self.start_section(Section::Function);
+        self.source.push_str(&line);
+        self.source.push('\n');
+
+        Ok(())
+    }
+
+    /// Generate the ffi.# structure for a struct definition, registering any array types used by
+    /// it's fields first.
+    fn define_struct(&mut self, name: &str, body: &[String]) -> Result<(), String> {
+        let mut fields = Vec::new();
+
+        for member in split_top_level(body, ";") {
+            if member.is_empty() {
+                continue;
+            }
+
+            let mut cursor = Cursor::new(member);
+            let specifiers = self.specifiers(&mut cursor)?;
+
+            if specifiers
+                .aggregate
+                .is_some_and(|aggregate| aggregate.body.is_some())
+            {
+                return Err("nested struct, union or enum definitions are not supported".into());
+            }
+
+            for declarator in split_top_level(cursor.remaining(), ",") {
+                if declarator.iter().any(|token| token == ":") {
+                    return Err("bit fields are not supported".to_string());
+                }
+
+                let (field_name, ctype) = self.declarator(
+                    &mut Cursor::new(declarator),
+                    specifiers.ctype.clone(),
+                    specifiers.is_const,
+                )?;
+
+                let field_name = field_name.ok_or_else(|| "field has no name".to_string())?;
+                let field_type = self
+                    .map_type(&ctype, Usage::Field)
+                    .map_err(|message| format!("field {}: {}", field_name, message))?;
+
+                fields.push((field_type, field_name));
+            }
+        }
+
+        if fields.is_empty() {
+            return Err("structures must have at least one field".to_string());
+        }
+
+        self.start_section(Section::Struct);
+
+        for (field_type, _) in &fields {
+            if field_type.ends_with(']') && self.arrays.insert(field_type.clone()) {
+                self.source.push_str(&format!("ffi.[] {}\n", field_type));
+            }
+        }
+
+        self.source.push_str(&format!("ffi.# {}\n", name));
+
+        for (field_type, field_name) in &fields {
+            self.source
+                .push_str(&format!("    {} {}\n", field_type, field_name));
+        }
+
+        self.source.push_str(";\n");
+        self.structs.insert(name.to_string());
+
+        Ok(())
+    }
+
+    /// Generate the constants for an enum definition.
+    fn define_enum(&mut self, body: &[String]) -> Result<(), String> {
+        let mut next = 0;
+
+        for item in split_top_level(body, ",") {
+            let Some(name) = item.first() else {
+                continue;
+            };
+
+            let value = match item.len() {
+                1 => next,
+                _ if item[1] == "=" => evaluate(&item[2..], &self.constants).ok_or_else(|| {
+                    format!(
+                        "enum value {}: {} is not a constant",
+                        name,
+                        item[2..].join(" ")
+                    )
+                })?,
+                _ => return Err(format!("enum value {}: unexpected {}", name, item[1])),
+            };
+
+            self.define_constant(name, value);
+            next = value.wrapping_add(1);
+        }
+
+        Ok(())
+    }
+
+    /// Separate the definitions that follow from the previous ones if they're of a different kind.
+    /// Structures are always separated from each other.
+    fn start_section(&mut self, section: Section) {
+        if section != self.section || section == Section::Struct {
+            self.source.push('\n');
+            self.section = section;
+        }
+    }
+
+    /// Generate a constant.
+    fn define_constant(&mut self, name: &str, value: i64) {
+        self.start_section(Section::Constant);
+        self.constants.insert(name.to_string(), value);
+        self.source
+            .push_str(&format!("{} constant {}\n", value, name));
+    }
+}
+
+/// Get the name used to register the library, based on the header's file name.
+fn library_alias(header_path: &str) -> String {
+    let stem = Path::new(header_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("header");
+
+    let alias: String = stem
+        .chars()
+        .map(|character| match character.is_alphanumeric() {
+            true => character,
+            false => '_',
+        })
+        .collect();
+
+    format!("lib_{}", alias)
+}
+
+/// Quote a string for use as a sorth string literal.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A short description of a declaration for reporting declarations that couldn't be mapped.
+fn describe(tokens: &[String]) -> String {
+    let mut description = tokens.iter().take(8).cloned().collect::<Vec<_>>().join(" ");
+
+    if tokens.len() > 8 {
+        description.push_str(" ...");
+    }
+
+    description
+}
+
+fn is_identifier(token: &str) -> bool {
+    token
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_')
+}
+
+/// Replace the comments in C source with whitespace, leaving string and character literals alone.
+fn strip_comments(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+
+    while let Some(character) = chars.next() {
+        match character {
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.peek().is_some_and(|next| *next != '\n') {
+                    chars.next();
+                }
+            }
+
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+
+                let mut previous = ' ';
+
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+
+                    if next == '\n' {
+                        result.push('\n');
+                    }
+
+                    previous = next;
+                }
+
+                result.push(' ');
+            }
+
+            '"' | '\'' => {
+                result.push(character);
+
+                while let Some(next) = chars.next() {
+                    result.push(next);
+
+                    if next == '\\' {
+                        if let Some(escaped) = chars.next() {
+                            result.push(escaped);
+                        }
+                    } else if next == character || next == '\n' {
+                        break;
+                    }
+                }
+            }
+
+            _ => result.push(character),
+        }
+    }
+
+    result
+}
+
+/// Split C source into tokens.  Identifiers, numbers and string literals are kept whole, as are
+/// the multi-character operators used in constant expressions.
+fn tokenize(code: &str) -> Vec<String> {
+    const OPERATORS: [&str; 10] = ["...", "<<", ">>", "&&", "||", "==", "!=", "<=", ">=", "->"];
+
+    let chars: Vec<char> = code.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let character = chars[index];
+        let start = index;
+
+        if character.is_whitespace() {
+            index += 1;
+            continue;
+        }
+
+        if character.is_alphanumeric()
+            || character == '_'
+            || (character == '.'
+                && chars
+                    .get(index + 1)
+                    .is_some_and(|next| next.is_ascii_digit()))
+        {
+            while index < chars.len()
+                && (chars[index].is_alphanumeric() || chars[index] == '_' || chars[index] == '.')
+            {
+                index += 1;
+            }
+        } else if character == '"' || character == '\'' {
+            index += 1;
+
+            while index < chars.len() && chars[index] != character {
+                index += if chars[index] == '\\' { 2 } else { 1 };
+            }
+
+            index = (index + 1).min(chars.len());
+        } else if let Some(operator) = OPERATORS
+            .iter()
+            .find(|operator| chars[index..].starts_with(&operator.chars().collect::<Vec<_>>()))
+        {
+            index += operator.len();
+        } else {
+            index += 1;
+        }
+
+        tokens.push(chars[start..index].iter().collect());
+    }
+
+    tokens
+}
+
+/// Find the index of the bracket that closes the one at the given index.
+fn matching_close(tokens: &[String], open_index: usize) -> Option<usize> {
+    let mut depth = 0;
+
+    for (index, token) in tokens.iter().enumerate().skip(open_index) {
+        match token.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Split tokens on a separator that isn't nested in any brackets.
+fn split_top_level<'a>(tokens: &'a [String], separator: &str) -> Vec<&'a [String]> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, token) in tokens.iter().enumerate() {
+        match token.as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => depth -= 1,
+            _ if depth == 0 && token == separator => {
+                parts.push(&tokens[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if start < tokens.len() {
+        parts.push(&tokens[start..]);
+    }
+
+    parts
+}
+
+/// Split the header's tokens into top level declarations.  Function definitions end at their
+/// closing brace rather than a semicolon and are flagged as having a body, which is left out.
+/// C++ extern "C" blocks are looked through.
+fn split_declarations(tokens: &[String]) -> Vec<(&[String], bool)> {
+    let mut declarations = Vec::new();
+    let mut start = 0;
+    let mut index = 0;
+
+    while index < tokens.len() {
+        let token = tokens[index].as_str();
+
+        if index == start {
+            if token == "extern"
+                && tokens
+                    .get(index + 1)
+                    .is_some_and(|next| next.starts_with('"'))
+            {
+                index += if tokens.get(index + 2).is_some_and(|next| next == "{") {
+                    3
+                } else {
+                    2
+                };
+                start = index;
+                continue;
+            }
+
+            if token == "}" || token == ";" {
+                index += 1;
+                start = index;
+                continue;
+            }
+        }
+
+        match token {
+            ";" => {
+                declarations.push((&tokens[start..index], false));
+                index += 1;
+                start = index;
+            }
+
+            "(" | "[" | "{" => {
+                let close = matching_close(tokens, index).unwrap_or(tokens.len() - 1);
+
+                if token == "{" && index > start && tokens[index - 1] == ")" {
+                    declarations.push((&tokens[start..index], true));
+                    start = close + 1;
+                }
+
+                index = close + 1;
+            }
+
+            _ => index += 1,
+        }
+    }
+
+    if start < tokens.len() {
+        declarations.push((&tokens[start..], false));
+    }
+
+    declarations
+}
+
+/// Evaluate an integer constant expression, returning None if it isn't one.
+fn evaluate(tokens: &[String], constants: &HashMap<String, i64>) -> Option<i64> {
+    let mut cursor = Cursor::new(tokens);
+    let value = evaluate_binary(&mut cursor, constants, 0)?;
+
+    match cursor.peek() {
+        None => Some(value),
+        Some(_) => None,
+    }
+}
+
+/// Evaluate binary operators using precedence climbing.
+fn evaluate_binary(
+    cursor: &mut Cursor,
+    constants: &HashMap<String, i64>,
+    min_precedence: u8,
+) -> Option<i64> {
+    fn precedence(operator: &str) -> Option<u8> {
+        Some(match operator {
+            "||" => 1,
+            "&&" => 2,
+            "|" => 3,
+            "^" => 4,
+            "&" => 5,
+            "==" | "!=" => 6,
+            "<" | ">" | "<=" | ">=" => 7,
+            "<<" | ">>" => 8,
+            "+" | "-" => 9,
+            "*" | "/" | "%" => 10,
+            _ => return None,
+        })
+    }
+
+    let mut left = evaluate_unary(cursor, constants)?;
+
+    while let Some(operator) = cursor.peek() {
+        let Some(operator_precedence) = precedence(operator) else {
+            break;
+        };
+
+        if operator_precedence < min_precedence {
+            break;
+        }
+
+        cursor.next();
+
+        let right = evaluate_binary(cursor, constants, operator_precedence + 1)?;
+
+        left = match operator {
+            "||" => ((left != 0) || (right != 0)) as i64,
+            "&&" => ((left != 0) && (right != 0)) as i64,
+            "|" => left | right,
+            "^" => left ^ right,
+            "&" => left & right,
+            "==" => (left == right) as i64,
+            "!=" => (left != right) as i64,
+            "<" => (left < right) as i64,
+            ">" => (left > right) as i64,
+            "<=" => (left <= right) as i64,
+            ">=" => (left >= right) as i64,
+            "<<" => left.checked_shl(right as u32)?,
+            ">>" => left.checked_shr(right as u32)?,
+            "+" => left.wrapping_add(right),
+            "-" => left.wrapping_sub(right),
+            "*" => left.wrapping_mul(right),
+            "/" => left.checked_div(right)?,
+            _ => left.checked_rem(right)?,
+        };
+    }
+
+    Some(left)
+}
+
+/// Evaluate unary operators, literals, known constants and parenthesized expressions.
+fn evaluate_unary(cursor: &mut Cursor, constants: &HashMap<String, i64>) -> Option<i64> {
+    let token = cursor.next()?;
+
+    match token {
+        "-" => Some(evaluate_unary(cursor, constants)?.wrapping_neg()),
+        "+" => evaluate_unary(cursor, constants),
+        "~" => Some(!evaluate_unary(cursor, constants)?),
+        "!" => Some((evaluate_unary(cursor, constants)? == 0) as i64),
+
+        "(" => {
+            let value = evaluate_binary(cursor, constants, 0)?;
+
+            match cursor.next() {
+                Some(")") => Some(value),
+                _ => None,
+            }
+        }
+
+        _ if token.starts_with('\'') => parse_char_literal(token),
+        _ if token.starts_with(|first: char| first.is_ascii_digit()) => parse_int_literal(token),
+        _ => constants.get(token).copied(),
+    }
+}
+
+/// Parse a C integer literal, in decimal, hex, octal or binary, ignoring any suffix.
+fn parse_int_literal(token: &str) -> Option<i64> {
+    let digits = token.trim_end_matches(['u', 'U', 'l', 'L']);
+
+    let value = if let Some(hex) = digits.strip_prefix("0x").or(digits.strip_prefix("0X")) {
+        u64::from_str_radix(hex, 16)
+    } else if let Some(binary) = digits.strip_prefix("0b").or(digits.strip_prefix("0B")) {
+        u64::from_str_radix(binary, 2)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        u64::from_str_radix(&digits[1..], 8)
+    } else {
+        digits.parse::<u64>()
+    };
+
+    value.ok().map(|value| value as i64)
+}
+
+/// Parse a simple C character literal such as 'a' or '\n'.
+fn parse_char_literal(token: &str) -> Option<i64> {
+    let inner = token.strip_prefix('\'')?.strip_suffix('\'')?;
+    let mut chars = inner.chars();
+
+    let value = match (chars.next()?, chars.next()) {
+        ('\\', Some('n')) => '\n',
+        ('\\', Some('r')) => '\r',
+        ('\\', Some('t')) => '\t',
+        ('\\', Some('0')) => '\0',
+        ('\\', Some(escaped)) => escaped,
+        (character, None) => character,
+        _ => return None,
+    };
+
+    match chars.next() {
+        None => Some(value as i64),
+        Some(_) => None,
+    }
+}
@@ -1,28 +1,36 @@
 use crate::{
-    add_native_word,
+    add_native_immediate_word, add_native_word, location_here,
     runtime::{
+        built_ins::ffi_header::{HeaderBindings, bind_header_file},
         data_structures::{
-            byte_buffer::{BufferPtr, ByteBuffer},
+            byte_buffer::{BufferPtr, ByteBuffer, NativeBuffer, NativeFree},
+            data_object::{DataObject, DataObjectDefinition},
             dictionary::{WordRuntime, WordType, WordVisibility},
             value::{ToValue, Value},
             value_vec::ValueVec,
         },
-        error::{self, script_error, script_error_str},
+        error::{self, ScriptError, script_error, script_error_str},
         interpreter::Interpreter,
     },
 };
 use libffi::{
     low::{ffi_abi_FFI_DEFAULT_ABI, ffi_cif, ffi_type, types},
-    raw::{ffi_call, ffi_prep_cif, ffi_status_FFI_OK},
+    raw::{
+        self, FFI_TYPE_SINT8, FFI_TYPE_SINT16, FFI_TYPE_SINT32, FFI_TYPE_STRUCT, FFI_TYPE_UINT8,
+        FFI_TYPE_UINT16, FFI_TYPE_UINT32, ffi_abi, ffi_call, ffi_closure, ffi_closure_alloc,
+        ffi_closure_free, ffi_prep_cif, ffi_prep_cif_var, ffi_prep_closure_loc, ffi_status_FFI_OK,
+    },
 };
 use libloading::{Library, Symbol};
 use std::{
     borrow::Cow,
-    cell::RefCell,
-    collections::HashMap,
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
     ffi::CStr,
     os::raw::{c_char, c_void},
+    panic::{self, AssertUnwindSafe},
     rc::Rc,
+    sync::Arc,
 };
 
 /// The calculated size of a type and any extra space needed for referenced data.
@@ -45,9 +53,25 @@ type ConversionTo =
 type ConversionSize =
     Rc<dyn Fn(&mut dyn Interpreter, usize, &Value) -> error::Result<CalculatedSize> + Send + Sync>;
 
+/// The converted parameters of a call.  Pointers to the native values, the original values, and the
+/// positions in the parameter and extra buffers each value was converted at.
+type ParamValues = (Vec<*mut c_void>, Vec<Value>, Vec<(usize, usize)>);
+
+/// The variable arguments of a call, the types worked out for them and their values.
+type VariadicArgs = (Vec<Rc<RefCell<TypeInfo>>>, Vec<Value>);
+
 // Calculate the base size of a type without any extra space needed for referenced data.
 type BaseSize = Rc<dyn Fn(usize) -> usize + Send + Sync>;
 
+/// Conversion function run after a foreign function call, to copy data the function may have
+/// written through a pointer back into the original Value.  It's called with the buffers positioned
+/// where they were when the Value was originally converted.
+type ConversionBack = Rc<
+    dyn Fn(&mut dyn Interpreter, &Value, usize, &BufferPtr, &BufferPtr) -> error::Result<()>
+        + Send
+        + Sync,
+>;
+
 /// Structure that holds information about a type that can be used in the ffi interface, as well as
 /// conversion handler functions for that type.
 #[derive(Clone)]
@@ -75,6 +99,10 @@ struct TypeInfo {
     /// referenced data.  This doesn't allow for variable sized type data.  Primarily used for
     /// calculating the size of data being returned from a foreign function.
     base_size: BaseSize,
+
+    /// Optional function to copy data written by the foreign function back into the original
+    /// Value.  Used by types that are passed by pointer.
+    conversion_back: Option<ConversionBack>,
 }
 
 /// Structure that holds the ffi interface libraries and the types that can be used with those
@@ -82,6 +110,31 @@ struct TypeInfo {
 pub struct FfiInterface {
     libs: HashMap<String, Rc<RefCell<Library>>>,
     types: HashMap<String, Rc<RefCell<TypeInfo>>>,
+
+    /// The libffi type descriptions of registered structures.  These are referenced by raw pointer
+    /// from the type information and any words bound to them, so they're kept for the life of the
+    /// interface, even if the structure is redefined.
+    struct_types: Vec<NativeStructType>,
+
+    /// The structure types, and arrays of them, whose packing changes their natural layout.
+    /// libffi always uses the natural layout for values, so these can only be passed by pointer.
+    pointer_only: HashSet<String>,
+
+    /// The live callbacks, keyed by their native function address.
+    callbacks: HashMap<usize, Box<FfiCallback>>,
+
+    /// Callbacks freed during a foreign function call.  Native code may still be running them, or
+    /// hold on to their address until it returns, so they're only released once the outermost
+    /// foreign function call returns.  They stay boxed, as native code holds their address.
+    #[allow(clippy::vec_box)]
+    freed_callbacks: Vec<Box<FfiCallback>>,
+
+    /// The first error raised by a callback during the current foreign function call.
+    callback_error: Option<ScriptError>,
+
+    /// The value of errno, (or GetLastError on Windows,) captured just after the last foreign
+    /// function call.
+    errno: i32,
 }
 
 impl Default for FfiInterface {
@@ -96,6 +149,12 @@ impl FfiInterface {
         FfiInterface {
             libs: HashMap::new(),
             types: FfiInterface::default_types(),
+            struct_types: Vec::new(),
+            pointer_only: HashSet::new(),
+            callbacks: HashMap::new(),
+            freed_callbacks: Vec::new(),
+            callback_error: None,
+            errno: 0,
         }
     }
 
@@ -103,11 +162,34 @@ impl FfiInterface {
     pub fn reset(&mut self) {
         self.libs.clear();
         self.types = FfiInterface::default_types();
+        self.struct_types.clear();
+        self.pointer_only.clear();
+
+        let callbacks: Vec<_> = self
+            .callbacks
+            .drain()
+            .map(|(_, callback)| callback)
+            .collect();
+
+        for callback in callbacks {
+            self.release_callback(callback);
+        }
+
+        self.callback_error = None;
+        self.errno = 0;
+    }
+
+    /// Release a callback that has been freed.  If a foreign function call is in progress on this
+    /// thread the callback could still be in use, so it's kept until the call returns.
+    fn release_callback(&mut self, callback: Box<FfiCallback>) {
+        if ACTIVE_INTERPRETER.get().is_some() {
+            self.freed_callbacks.push(callback);
+        }
     }
 
     /// Create the default type information for the ffi interface.
     fn default_types() -> HashMap<String, Rc<RefCell<TypeInfo>>> {
-        HashMap::from_iter(vec![
+        let mut types = HashMap::from_iter(vec![
             (
                 "ffi.void".to_string(),
                 Rc::new(RefCell::new(TypeInfo {
@@ -119,6 +201,7 @@ impl FfiInterface {
                     conversion_to: Rc::new(|_interpreter, _align, _buffer| Ok(Value::None)),
                     conversion_size: Rc::new(|_interpreter, _align, _value| Ok((0, 0))),
                     base_size: Rc::new(|_align| 0),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -146,6 +229,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<bool>(), align);
                         size_of::<bool>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -173,6 +257,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<i8>(), align);
                         size_of::<i8>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -200,6 +285,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<u8>(), align);
                         size_of::<u8>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -227,6 +313,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<i16>(), align);
                         size_of::<i16>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -254,6 +341,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<u16>(), align);
                         size_of::<u16>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -281,6 +369,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<i32>(), align);
                         size_of::<i32>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -308,6 +397,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<u32>(), align);
                         size_of::<u32>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -335,6 +425,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<i64>(), align);
                         size_of::<i64>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -362,6 +453,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<u64>(), align);
                         size_of::<u64>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -389,6 +481,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<f32>(), align);
                         size_of::<f32>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -416,6 +509,7 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(size_of::<f64>(), align);
                         size_of::<f64>() + padding
                     }),
+                    conversion_back: None,
                 })),
             ),
             (
@@ -433,12 +527,13 @@ impl FfiInterface {
                         let ptr_size = size_of::<*const c_void>();
                         let ptr_padding = FfiInterface::alignment(ptr_size, align);
 
-                        let str_size = string.len();
+                        // Include the null terminator in the string's size.
+                        let str_size = string.len() + 1;
                         let str_padding = FfiInterface::alignment(str_size, align);
 
-                        buffer
-                            .borrow_mut()
-                            .write_int(ptr_size, extra.borrow_mut().position_ptr_mut() as i64);
+                        let str_ptr = extra.borrow_mut().position_ptr_mut();
+
+                        buffer.borrow_mut().write_int(ptr_size, str_ptr as i64);
                         buffer.borrow_mut().increment_position(ptr_padding);
 
                         extra
@@ -456,13 +551,15 @@ impl FfiInterface {
 
                         buffer.borrow_mut().increment_position(padding);
 
+                        if raw_ptr.is_null() {
+                            return Ok(Value::None);
+                        }
+
                         let string = match unsafe { CStr::from_ptr(raw_ptr).to_string_lossy() } {
                             Cow::Borrowed(string) => string.to_string(),
                             Cow::Owned(string) => string,
                         };
 
-                        buffer.borrow_mut().increment_position(string.len() + 1);
-
                         Ok(string.to_value())
                     }),
                     conversion_size: Rc::new(|interpreter, align, value| {
@@ -485,9 +582,205 @@ impl FfiInterface {
                         let padding = FfiInterface::alignment(2, align);
                         size_of::<*const c_void>() + padding
                     }),
+                    conversion_back: None,
+                })),
+            ),
+            (
+                "ffi.buffer".to_string(),
+                Rc::new(RefCell::new(TypeInfo {
+                    name: "ffi.buffer".to_string(),
+                    ffi_type: &raw mut types::pointer,
+                    conversion_from: Rc::new(|interpreter, value, align, buffer, _extra| {
+                        // Pass a pointer to the start of the buffer, or slice, data.  The Value
+                        // keeps the buffer alive for the duration of the call.
+                        let Value::ByteBuffer(byte_buffer) = value else {
+                            return script_error_str(interpreter, "Value is not a byte buffer.");
+                        };
+
+                        if let Err(message) = byte_buffer.borrow().check_bounds() {
+                            return script_error(interpreter, message);
+                        }
+
+                        let ptr_size = size_of::<*const c_void>();
+                        let ptr_padding = FfiInterface::alignment(ptr_size, align);
+                        let raw_ptr = byte_buffer.borrow_mut().byte_ptr_mut();
+
+                        buffer.borrow_mut().write_int(ptr_size, raw_ptr as i64);
+                        buffer.borrow_mut().increment_position(ptr_padding);
+
+                        Ok(())
+                    }),
+                    conversion_to: Rc::new(|interpreter, _align, _buffer| {
+                        script_error_str(
+                            interpreter,
+                            "A byte buffer can not be returned from a native function.",
+                        )
+                    }),
+                    conversion_size: Rc::new(|_interpreter, align, _value| {
+                        let padding = FfiInterface::alignment(size_of::<*const c_void>(), align);
+                        Ok((size_of::<*const c_void>() + padding, 0))
+                    }),
+                    base_size: Rc::new(|align| {
+                        let padding = FfiInterface::alignment(size_of::<*const c_void>(), align);
+                        size_of::<*const c_void>() + padding
+                    }),
+                    conversion_back: None,
+                })),
+            ),
+            (
+                "ffi.fn-ptr".to_string(),
+                Rc::new(RefCell::new(TypeInfo {
+                    name: "ffi.fn-ptr".to_string(),
+                    ffi_type: &raw mut types::pointer,
+                    conversion_from: Rc::new(|interpreter, value, align, buffer, _extra| {
+                        // Function pointers, such as those from ffi.callback, are passed as their
+                        // address.  None is passed as a null pointer.
+                        let address = match value {
+                            Value::None => 0,
+                            _ if value.is_numeric() => value.get_int_val(),
+                            _ => {
+                                return script_error(
+                                    interpreter,
+                                    format!("Expected a function pointer, found {}.", value),
+                                );
+                            }
+                        };
+
+                        let ptr_size = size_of::<*const c_void>();
+
+                        buffer.borrow_mut().write_int(ptr_size, address);
+                        buffer
+                            .borrow_mut()
+                            .increment_position(FfiInterface::alignment(ptr_size, align));
+
+                        Ok(())
+                    }),
+                    conversion_to: Rc::new(|_interpreter, align, buffer| {
+                        let ptr_size = size_of::<*const c_void>();
+                        let address = buffer.borrow_mut().read_int(ptr_size, false);
+
+                        buffer
+                            .borrow_mut()
+                            .increment_position(FfiInterface::alignment(ptr_size, align));
+
+                        match address {
+                            0 => Ok(Value::None),
+                            _ => Ok(address.to_value()),
+                        }
+                    }),
+                    conversion_size: Rc::new(|_interpreter, align, _value| {
+                        let padding = FfiInterface::alignment(size_of::<*const c_void>(), align);
+                        Ok((size_of::<*const c_void>() + padding, 0))
+                    }),
+                    base_size: Rc::new(|align| {
+                        let padding = FfiInterface::alignment(size_of::<*const c_void>(), align);
+                        size_of::<*const c_void>() + padding
+                    }),
+                    conversion_back: None,
                 })),
             ),
-        ])
+        ]);
+
+        // Untyped pointers, then typed pointers to each of the base types.
+        for name in ["ffi.ptr", "ffi.void-ptr"] {
+            let type_info = FfiInterface::pointer_type(name);
+            types.insert(name.to_string(), Rc::new(RefCell::new(type_info)));
+        }
+
+        for element in [
+            "ffi.bool",
+            "ffi.i8",
+            "ffi.u8",
+            "ffi.i16",
+            "ffi.u16",
+            "ffi.i32",
+            "ffi.u32",
+            "ffi.i64",
+            "ffi.u64",
+            "ffi.f32",
+            "ffi.f64",
+            "ffi.string",
+            "ffi.ptr",
+        ] {
+            let type_info = typed_pointer_type(element, &types[element].borrow());
+            types.insert(type_info.name.clone(), Rc::new(RefCell::new(type_info)));
+        }
+
+        types
+    }
+
+    /// Create the type information for an untyped pointer.  Addresses are passed as integers, and
+    /// none is passed as a null pointer.  Byte buffers pass the address of their data.
+    fn pointer_type(name: &str) -> TypeInfo {
+        TypeInfo {
+            name: name.to_string(),
+            ffi_type: &raw mut types::pointer,
+            conversion_from: Rc::new(|interpreter, value, align, buffer, _extra| {
+                let address = match value {
+                    Value::ByteBuffer(byte_buffer) => {
+                        if let Err(message) = byte_buffer.borrow().check_bounds() {
+                            return script_error(interpreter, message);
+                        }
+
+                        byte_buffer.borrow_mut().byte_ptr_mut() as i64
+                    }
+
+                    _ if value.is_numeric() => value.get_int_val(),
+
+                    _ => {
+                        return script_error(
+                            interpreter,
+                            format!("Expected an address, found {}.", value),
+                        );
+                    }
+                };
+
+                FfiInterface::write_address(address, align, buffer);
+                Ok(())
+            }),
+            conversion_to: Rc::new(|_interpreter, align, buffer| {
+                Ok(FfiInterface::read_address(align, buffer).to_value())
+            }),
+            conversion_size: Rc::new(|_interpreter, align, _value| {
+                Ok((FfiInterface::address_size(align), 0))
+            }),
+            base_size: Rc::new(FfiInterface::address_size),
+            conversion_back: None,
+        }
+    }
+
+    /// Is the value a raw address?  Pointer types accept these, such as those from ffi.malloc or
+    /// returned by another function, and pass them as is.
+    fn is_address(value: &Value) -> bool {
+        value.is_numeric() && !value.is_none()
+    }
+
+    /// The size of a native pointer, plus it's padding for the given alignment.
+    fn address_size(align: usize) -> usize {
+        let ptr_size = size_of::<*const c_void>();
+        ptr_size + FfiInterface::alignment(ptr_size, align)
+    }
+
+    /// Write a native address into the buffer.
+    fn write_address(address: i64, align: usize, buffer: &BufferPtr) {
+        let ptr_size = size_of::<*const c_void>();
+
+        buffer.borrow_mut().write_int(ptr_size, address);
+        buffer
+            .borrow_mut()
+            .increment_position(FfiInterface::alignment(ptr_size, align));
+    }
+
+    /// Read a native address from the buffer.
+    fn read_address(align: usize, buffer: &BufferPtr) -> i64 {
+        let ptr_size = size_of::<*const c_void>();
+        let address = buffer.borrow_mut().read_int(ptr_size, false);
+
+        buffer
+            .borrow_mut()
+            .increment_position(FfiInterface::alignment(ptr_size, align));
+
+        address
     }
 
     /// Calculate the padding needed to align a value to the given alignment.
@@ -567,391 +860,2259 @@ impl FfiInterface {
     }
 }
 
-/// Structure that handles a word that calls a foreign function.
-struct FfiWord {
-    /// The library that contains the function.
-    library: Rc<RefCell<Library>>,
-
-    /// The name of the library.
-    library_name: String,
-
-    /// The name of the function to call in the library.
-    function_name: String,
-
-    /// The types of the arguments to the function.
-    arg_types: Vec<Rc<RefCell<TypeInfo>>>,
-
-    /// The function's return type.
-    return_type: Rc<RefCell<TypeInfo>>,
-
-    /// The alignment of the function's arguments and return value.
-    alignment: usize,
-}
-
-/// Implement the Fn trait for FfiWord to make the struct callable.
-impl Fn<(&mut dyn Interpreter,)> for FfiWord {
-    extern "rust-call" fn call(&self, args: (&mut dyn Interpreter,)) -> error::Result<()> {
-        self.handle_word(args.0)
+/// Create a typed pointer to a single value, element-ptr.  As well as raw addresses, a one item
+/// array can be passed, it's value is placed in temporary memory and whatever the function writes
+/// there is copied back into the array.  Returned pointers are read as addresses.
+fn typed_pointer_type(element: &str, element_info: &TypeInfo) -> TypeInfo {
+    let name = format!("{}-ptr", element);
+    let layout = Arc::new(ArrayLayout::for_element(
+        name.clone(),
+        element,
+        Some(1),
+        element_info,
+    ));
+
+    TypeInfo {
+        conversion_to: Rc::new(|_interpreter, align, buffer| {
+            Ok(FfiInterface::read_address(align, buffer).to_value())
+        }),
+        ..ArrayLayout::by_pointer_type(&layout, name)
     }
 }
 
-/// Implement the FnMut trait for FfiWord to make the struct callable.
-impl FnMut<(&mut dyn Interpreter,)> for FfiWord {
-    extern "rust-call" fn call_mut(&mut self, args: (&mut dyn Interpreter,)) -> error::Result<()> {
-        self.handle_word(args.0)
+/// Look up the type information for a registered type, such as a structure field or array element.
+fn find_type(
+    interpreter: &mut dyn Interpreter,
+    type_name: &str,
+) -> error::Result<Rc<RefCell<TypeInfo>>> {
+    match interpreter.ffi().types.get(type_name) {
+        Some(type_info) => Ok(type_info.clone()),
+        None => script_error(interpreter, format!("Unknown ffi type name {}.", type_name)),
     }
 }
 
-/// Implement the FnOnce trait for the FfiWord to make the struct callable.
-impl FnOnce<(&mut dyn Interpreter,)> for FfiWord {
-    type Output = error::Result<()>;
-
-    extern "rust-call" fn call_once(self, args: (&mut dyn Interpreter,)) -> error::Result<()> {
-        self.handle_word(args.0)
+/// Make sure a type can be passed to or returned from a native function by value.  Packed
+/// structures with a different layout than their natural one can only be passed by pointer.
+fn check_by_value(interpreter: &mut dyn Interpreter, type_name: &str) -> error::Result<()> {
+    if interpreter.ffi().pointer_only.contains(type_name) {
+        return script_error(
+            interpreter,
+            format!(
+                "Type {} has a packed layout that can not be passed by value, use {}-ptr.",
+                type_name, type_name
+            ),
+        );
     }
+
+    Ok(())
 }
 
-impl FfiWord {
-    /// Create a new FfiWord handler.
-    pub fn new(
-        library: Rc<RefCell<Library>>,
-        library_name: String,
-        function_name: String,
-        arg_types: Vec<Rc<RefCell<TypeInfo>>>,
-        return_type: Rc<RefCell<TypeInfo>>,
-    ) -> FfiWord {
-        FfiWord {
-            library,
-            library_name,
-            function_name,
-            arg_types,
-            return_type,
-            alignment: 8,
-        }
-    }
+/// The libffi description of a structure or fixed size array type, along with the element list it
+/// points to.
+struct NativeStructType {
+    /// The structure's ffi_type record.  Boxed so that it's address is stable.
+    ffi_type: Box<ffi_type>,
 
-    /// Handle the word by calling the foreign function.
-    fn handle_word(&self, interpreter: &mut dyn Interpreter) -> error::Result<()> {
-        let library = self.library.borrow();
+    /// The null terminated list of the structure's field types.
+    _elements: Vec<*mut ffi_type>,
+}
 
-        // Get the function from the library.
-        let function: Symbol<*mut c_void> =
-            match unsafe { library.get(self.function_name.as_bytes()) } {
-                Ok(function) => function,
-                Err(error) => {
-                    return script_error(
-                        interpreter,
-                        format!(
-                            "Failed to get library {} symbol {}: {}.",
-                            self.library_name, self.function_name, error
-                        ),
-                    );
-                }
-            };
+impl NativeStructType {
+    /// Create the libffi description of an aggregate with the given size, alignment and null
+    /// terminated list of element types.
+    fn new(size: usize, alignment: usize, elements: Vec<*mut ffi_type>) -> NativeStructType {
+        let mut native_type = NativeStructType {
+            ffi_type: Box::new(ffi_type {
+                size,
+                alignment: alignment as u16,
+                type_: FFI_TYPE_STRUCT as u16,
+                elements: std::ptr::null_mut(),
+            }),
+            _elements: elements,
+        };
 
-        // Allocate the buffers for the parameters and populate them.  We also get the array of
-        // pointers to the parameters.
-        let buffer: BufferPtr = ByteBuffer::new_ptr(0);
-        let extra_buffer: BufferPtr = ByteBuffer::new_ptr(0);
+        native_type.ffi_type.elements = native_type._elements.as_mut_ptr();
+        native_type
+    }
+}
 
-        let mut param_value_ptrs =
-            self.get_param_value_ptrs(interpreter, &buffer, &extra_buffer)?;
+/// The native memory layout of a structure registered with ffi.#.  Field types are kept by name
+/// and looked up when converting, so that the conversion handlers remain Send and Sync.
+struct StructLayout {
+    /// The name of the structure, and of it's matching sorth structure definition.
+    name: String,
 
-        // Allocate the buffer for the return value.
-        let return_buffer =
-            ByteBuffer::new_ptr((self.return_type.borrow().base_size)(self.alignment));
-        let mut return_buffer: BufferPtr = return_buffer.clone();
+    /// The type name and byte offset of each field.
+    fields: Vec<(String, usize)>,
 
-        // Create the array of raw ffi_type pointers for creating the ffi_cif.
-        let mut arg_types = self
-            .arg_types
-            .iter()
-            .map(|type_info| type_info.borrow().ffi_type)
-            .collect::<Vec<_>>();
+    /// The total size of the structure, including any trailing padding.
+    size: usize,
 
-        // Create teh ffi cif and if successful call the function.
-        let mut cif: ffi_cif = unsafe { std::mem::zeroed() };
-        let code_ptr = unsafe {
-            Some(std::mem::transmute::<
-                *mut std::ffi::c_void,
-                unsafe extern "C" fn(),
-            >(*function))
-        };
+    /// The alignment of the structure as a whole.
+    alignment: usize,
+}
 
-        let status = unsafe {
-            ffi_prep_cif(
-                &mut cif,
-                ffi_abi_FFI_DEFAULT_ABI,
-                arg_types.len() as u32,
-                self.return_type.borrow().ffi_type,
-                arg_types.as_mut_ptr(),
-            )
+impl StructLayout {
+    /// Get the fields of a structure value, making sure it's the right kind of structure.
+    fn field_values(
+        &self,
+        interpreter: &mut dyn Interpreter,
+        value: &Value,
+    ) -> error::Result<Vec<Value>> {
+        let Value::DataObject(data_ptr) = value else {
+            return script_error(
+                interpreter,
+                format!("Expected a {} structure, found {}.", self.name, value),
+            );
         };
 
-        if status != ffi_status_FFI_OK {
-            return script_error_str(interpreter, "Failed to create FFI cif.");
-        }
+        let data = data_ptr.borrow();
 
-        unsafe {
-            ffi_call(
-                &mut cif,
-                code_ptr,
-                return_buffer.borrow_mut().byte_ptr_mut(),
-                param_value_ptrs.as_mut_ptr(),
+        if *data.definition_ptr.borrow().name() != self.name
+            || data.fields.len() != self.fields.len()
+        {
+            return script_error(
+                interpreter,
+                format!("Expected a {} structure, found {}.", self.name, value),
             );
         }
 
-        // Convert the return value to an interpreter Value and push it onto the data stack.  But
-        // only if the return type is not void.
-        let value = (self.return_type.borrow().conversion_to)(
-            interpreter,
-            self.alignment,
-            &mut return_buffer,
-        )?;
+        Ok(data.fields.clone())
+    }
 
-        if !value.is_none() {
-            interpreter.push(value);
+    /// Calculate the extra space needed for data referenced by the structure's fields.
+    fn extra_size(&self, interpreter: &mut dyn Interpreter, value: &Value) -> error::Result<usize> {
+        let values = self.field_values(interpreter, value)?;
+        let mut extra_size = 0;
+
+        for ((type_name, _), field_value) in self.fields.iter().zip(values.iter()) {
+            let type_info = find_type(interpreter, type_name)?;
+            let (_, extra) = (type_info.borrow().conversion_size)(interpreter, 1, field_value)?;
+
+            extra_size += extra;
         }
 
-        // All done.
-        Ok(())
+        Ok(extra_size)
     }
 
-    /// Pop the parameters from the data stack, convert them to the native types in the supplied
-    /// byte buffers, and return a vector of pointers to the converted values.
-    fn get_param_value_ptrs(
+    /// Write a structure value into the buffer at it's current position, leaving the position just
+    /// past the structure.  Any referenced data is written to the extra buffer.
+    fn write(
         &self,
         interpreter: &mut dyn Interpreter,
+        value: &Value,
         buffer: &BufferPtr,
-        extra_buffer: &BufferPtr,
-    ) -> error::Result<Vec<*mut c_void>> {
-        let args_len = self.arg_types.len();
+        extra: &BufferPtr,
+    ) -> error::Result<()> {
+        let values = self.field_values(interpreter, value)?;
+        let start = buffer.borrow().position();
 
-        let mut arg_values: Vec<Value> = Vec::with_capacity(args_len);
-        let mut arg_value_ptrs = Vec::with_capacity(args_len);
+        // Make sure any padding between the fields is zeroed.
+        buffer.borrow_mut().set_bytes(start, &vec![0; self.size]);
 
-        let mut base_size = 0;
-        let mut extra_size = 0;
+        for ((type_name, offset), field_value) in self.fields.iter().zip(values.iter()) {
+            let type_info = find_type(interpreter, type_name)?;
 
-        arg_values.resize(args_len, Value::None);
+            buffer.borrow_mut().set_position(start + offset);
+            (type_info.borrow().conversion_from)(interpreter, field_value, 1, buffer, extra)?;
+        }
 
-        for index in 0..args_len {
-            let index = args_len - index - 1;
-            let value = interpreter.pop()?;
-            let (size, extra) = (self.arg_types[index].borrow().conversion_size)(
-                interpreter,
-                self.alignment,
-                &value,
-            )?;
+        buffer.borrow_mut().set_position(start + self.size);
 
-            base_size += size;
-            extra_size += extra;
+        Ok(())
+    }
+
+    /// Read a structure from the buffer at it's current position into a new structure value,
+    /// leaving the position just past the structure.
+    fn read(&self, interpreter: &mut dyn Interpreter, buffer: &BufferPtr) -> error::Result<Value> {
+        let definition = interpreter
+            .structure_definitions()
+            .iter()
+            .filter(|definition| *definition.borrow().name() == self.name)
+            .last()
+            .cloned();
+
+        let Some(definition) = definition else {
+            return script_error(
+                interpreter,
+                format!("Structure {} is not defined.", self.name),
+            );
+        };
+
+        let start = buffer.borrow().position();
+        let mut values = Vec::with_capacity(self.fields.len());
+
+        for (type_name, offset) in self.fields.iter() {
+            let type_info = find_type(interpreter, type_name)?;
+
+            buffer.borrow_mut().set_position(start + offset);
+            values.push((type_info.borrow().conversion_to)(interpreter, 1, buffer)?);
+        }
+
+        buffer.borrow_mut().set_position(start + self.size);
+
+        let data_ptr = DataObject::new(&definition);
+        data_ptr.borrow_mut().fields = values;
+
+        Ok(data_ptr.to_value())
+    }
+
+    /// Read a structure out of native memory at the given address.
+    fn read_native(
+        &self,
+        interpreter: &mut dyn Interpreter,
+        address: *const u8,
+    ) -> error::Result<Value> {
+        let bytes = unsafe { std::slice::from_raw_parts(address, self.size) };
+        let buffer = ByteBuffer::new_ptr(self.size);
+
+        buffer.borrow_mut().buffer_mut().copy_from_slice(bytes);
+
+        let buffer: BufferPtr = buffer;
+        self.read(interpreter, &buffer)
+    }
+
+    /// Align the extra buffer's position so that it's address is suitable for the structure, and
+    /// return that address.
+    fn align_extra(&self, extra: &BufferPtr) -> *mut c_void {
+        let address = extra.borrow_mut().position_ptr_mut() as usize;
+        let padding = FfiInterface::alignment(address, self.alignment);
+
+        extra.borrow_mut().increment_position(padding);
+        extra.borrow_mut().position_ptr_mut()
+    }
+
+    /// Create the type information for passing the structure by value.
+    fn by_value_type(layout: &Arc<StructLayout>, ffi_type: *mut ffi_type) -> TypeInfo {
+        let from_layout = layout.clone();
+        let to_layout = layout.clone();
+        let size_layout = layout.clone();
+        let size = layout.size;
+
+        TypeInfo {
+            name: layout.name.clone(),
+            ffi_type,
+            conversion_from: Rc::new(move |interpreter, value, align, buffer, extra| {
+                from_layout.write(interpreter, value, buffer, extra)?;
+
+                let padding = FfiInterface::alignment(from_layout.size, align);
+                buffer.borrow_mut().increment_position(padding);
+
+                Ok(())
+            }),
+            conversion_to: Rc::new(move |interpreter, align, buffer| {
+                let value = to_layout.read(interpreter, buffer)?;
+
+                let padding = FfiInterface::alignment(to_layout.size, align);
+                buffer.borrow_mut().increment_position(padding);
+
+                Ok(value)
+            }),
+            conversion_size: Rc::new(move |interpreter, align, value| {
+                let padding = FfiInterface::alignment(size_layout.size, align);
+                let extra = size_layout.extra_size(interpreter, value)?;
+
+                Ok((size_layout.size + padding, extra))
+            }),
+            base_size: Rc::new(move |align| size + FfiInterface::alignment(size, align)),
+            conversion_back: None,
+        }
+    }
+
+    /// Create the type information for passing the structure by pointer.  The structure's data is
+    /// placed in the extra buffer, and copied back into the structure value after the call so that
+    /// it can be used as an out parameter.  None is passed as a null pointer, and raw addresses are
+    /// passed as is.
+    fn by_pointer_type(layout: &Arc<StructLayout>) -> TypeInfo {
+        let from_layout = layout.clone();
+        let to_layout = layout.clone();
+        let size_layout = layout.clone();
+        let back_layout = layout.clone();
+
+        TypeInfo {
+            name: format!("{}-ptr", layout.name),
+            ffi_type: &raw mut types::pointer,
+            conversion_from: Rc::new(move |interpreter, value, align, buffer, extra| {
+                let ptr_size = size_of::<*const c_void>();
+                let ptr_padding = FfiInterface::alignment(ptr_size, align);
+
+                let address = if value.is_none() {
+                    0
+                } else if FfiInterface::is_address(value) {
+                    value.get_int_val()
+                } else {
+                    // Write the structure into a scratch buffer then copy it into place, so that
+                    // any data it references can follow it in the extra buffer.
+                    let address = from_layout.align_extra(extra);
+                    let start = extra.borrow().position();
+                    let scratch: BufferPtr = ByteBuffer::new_ptr(from_layout.size);
+
+                    extra.borrow_mut().increment_position(from_layout.size);
+                    from_layout.write(interpreter, value, &scratch, extra)?;
+
+                    let bytes = scratch.borrow().get_bytes(0, from_layout.size);
+                    extra.borrow_mut().set_bytes(start, &bytes);
+
+                    address as i64
+                };
+
+                buffer.borrow_mut().write_int(ptr_size, address);
+                buffer.borrow_mut().increment_position(ptr_padding);
+
+                Ok(())
+            }),
+            conversion_to: Rc::new(move |interpreter, align, buffer| {
+                let ptr_size = size_of::<*const c_void>();
+                let padding = FfiInterface::alignment(ptr_size, align);
+                let address = buffer.borrow_mut().read_int(ptr_size, false) as *const u8;
+
+                buffer.borrow_mut().increment_position(padding);
+
+                if address.is_null() {
+                    return Ok(Value::None);
+                }
+
+                to_layout.read_native(interpreter, address)
+            }),
+            conversion_size: Rc::new(move |interpreter, align, value| {
+                let ptr_size = size_of::<*const c_void>();
+                let padding = FfiInterface::alignment(ptr_size, align);
+
+                if value.is_none() || FfiInterface::is_address(value) {
+                    return Ok((ptr_size + padding, 0));
+                }
+
+                // Leave room to align the structure within the extra buffer.
+                let extra = size_layout.size
+                    + size_layout.alignment
+                    + size_layout.extra_size(interpreter, value)?;
+
+                Ok((ptr_size + padding, extra))
+            }),
+            base_size: Rc::new(|align| {
+                let ptr_size = size_of::<*const c_void>();
+                ptr_size + FfiInterface::alignment(ptr_size, align)
+            }),
+            conversion_back: Some(Rc::new(
+                move |interpreter, value, _align, _buffer, extra| {
+                    let Value::DataObject(data_ptr) = value else {
+                        return Ok(());
+                    };
+
+                    let address = back_layout.align_extra(extra) as *const u8;
+                    let new_value = back_layout.read_native(interpreter, address)?;
+
+                    if let Value::DataObject(new_data_ptr) = new_value {
+                        data_ptr.borrow_mut().fields = new_data_ptr.borrow().fields.clone();
+                    }
+
+                    Ok(())
+                },
+            )),
+        }
+    }
+}
+
+/// The native memory layout of an array type registered with ffi.[].  Elements are stored
+/// contiguously, each one padded out to the element type's alignment.
+struct ArrayLayout {
+    /// The name the array type was registered under.
+    name: String,
+
+    /// The name of the type of the array's elements.
+    element: String,
+
+    /// The number of elements for fixed size arrays, or None if the size comes from the array
+    /// value.
+    count: Option<usize>,
+
+    /// The distance in bytes between the start of each element.
+    stride: usize,
+
+    /// The alignment of the array's elements.
+    alignment: usize,
+}
+
+impl ArrayLayout {
+    /// Create the layout for an array of the given element type.
+    fn for_element(
+        name: String,
+        element: &str,
+        count: Option<usize>,
+        element_info: &TypeInfo,
+    ) -> ArrayLayout {
+        let element_size = (element_info.base_size)(1);
+        let alignment = unsafe { (*element_info.ffi_type).alignment as usize }.max(1);
+
+        ArrayLayout {
+            name,
+            element: element.to_string(),
+            count,
+            stride: element_size + FfiInterface::alignment(element_size, alignment),
+            alignment,
+        }
+    }
+
+    /// Get the elements of an array value.  Fixed size arrays accept fewer values than their size,
+    /// the rest of the array is zero filled.
+    fn element_values(
+        &self,
+        interpreter: &mut dyn Interpreter,
+        value: &Value,
+    ) -> error::Result<Vec<Value>> {
+        let values: Vec<Value> = match value {
+            Value::Vec(vec_ptr) => vec_ptr.borrow().iter().cloned().collect(),
+            Value::None if self.count.is_some() => Vec::new(),
+            _ => {
+                return script_error(
+                    interpreter,
+                    format!("Expected an array for {}, found {}.", self.name, value),
+                );
+            }
+        };
+
+        if let Some(count) = self.count
+            && values.len() > count
+        {
+            return script_error(
+                interpreter,
+                format!(
+                    "Array of {} items is too large for {}.",
+                    values.len(),
+                    self.name
+                ),
+            );
+        }
+
+        Ok(values)
+    }
+
+    /// The number of elements that will be passed for the given array value.
+    fn len(&self, values: &[Value]) -> usize {
+        self.count.unwrap_or(values.len())
+    }
+
+    /// Calculate the extra space needed for data referenced by the array's elements.
+    fn extra_size(
+        &self,
+        interpreter: &mut dyn Interpreter,
+        values: &[Value],
+    ) -> error::Result<usize> {
+        let type_info = find_type(interpreter, &self.element)?;
+        let mut extra_size = 0;
+
+        for value in values.iter().filter(|value| !value.is_none()) {
+            let (_, extra) = (type_info.borrow().conversion_size)(interpreter, 1, value)?;
+
+            extra_size += extra;
+        }
+
+        Ok(extra_size)
+    }
+
+    /// Write the array's elements into the buffer at it's current position, leaving the position
+    /// just past the array.  None elements, and any missing from a fixed size array, are left
+    /// zeroed.
+    fn write(
+        &self,
+        interpreter: &mut dyn Interpreter,
+        values: &[Value],
+        buffer: &BufferPtr,
+        extra: &BufferPtr,
+    ) -> error::Result<()> {
+        let type_info = find_type(interpreter, &self.element)?;
+        let start = buffer.borrow().position();
+        let size = self.len(values) * self.stride;
+
+        buffer.borrow_mut().set_bytes(start, &vec![0; size]);
+
+        for (index, value) in values.iter().enumerate() {
+            if value.is_none() {
+                continue;
+            }
+
+            buffer
+                .borrow_mut()
+                .set_position(start + index * self.stride);
+            (type_info.borrow().conversion_from)(interpreter, value, 1, buffer, extra)?;
+        }
+
+        buffer.borrow_mut().set_position(start + size);
+
+        Ok(())
+    }
+
+    /// Read count elements from the buffer at it's current position, leaving the position just past
+    /// the array.
+    fn read(
+        &self,
+        interpreter: &mut dyn Interpreter,
+        count: usize,
+        buffer: &BufferPtr,
+    ) -> error::Result<Vec<Value>> {
+        let type_info = find_type(interpreter, &self.element)?;
+        let start = buffer.borrow().position();
+        let mut values = Vec::with_capacity(count);
+
+        for index in 0..count {
+            buffer
+                .borrow_mut()
+                .set_position(start + index * self.stride);
+            values.push((type_info.borrow().conversion_to)(interpreter, 1, buffer)?);
+        }
+
+        buffer
+            .borrow_mut()
+            .set_position(start + count * self.stride);
+
+        Ok(values)
+    }
+
+    /// Read count elements out of native memory at the given address.
+    fn read_native(
+        &self,
+        interpreter: &mut dyn Interpreter,
+        count: usize,
+        address: *const u8,
+    ) -> error::Result<Vec<Value>> {
+        let size = count * self.stride;
+        let bytes = unsafe { std::slice::from_raw_parts(address, size) };
+        let buffer = ByteBuffer::new_ptr(size);
+
+        buffer.borrow_mut().buffer_mut().copy_from_slice(bytes);
+
+        let buffer: BufferPtr = buffer;
+        self.read(interpreter, count, &buffer)
+    }
+
+    /// Update an array value in place with the values read back from native memory.  Structures
+    /// are updated field by field so that any other references to them see the new values.
+    fn update(array: &Value, new_values: Vec<Value>) {
+        let Value::Vec(vec_ptr) = array else {
+            return;
+        };
+
+        let mut vec = vec_ptr.borrow_mut();
+
+        if vec.len() < new_values.len() {
+            vec.resize(new_values.len());
+        }
+
+        for (index, new_value) in new_values.into_iter().enumerate() {
+            match (&vec[index], &new_value) {
+                (Value::DataObject(data_ptr), Value::DataObject(new_data_ptr)) => {
+                    data_ptr.borrow_mut().fields = new_data_ptr.borrow().fields.clone();
+                }
+
+                _ => vec[index] = new_value,
+            }
+        }
+    }
+
+    /// Align the extra buffer's position so that it's address is suitable for the array, and
+    /// return that address.
+    fn align_extra(&self, extra: &BufferPtr) -> *mut c_void {
+        let address = extra.borrow_mut().position_ptr_mut() as usize;
+        let padding = FfiInterface::alignment(address, self.alignment);
+
+        extra.borrow_mut().increment_position(padding);
+        extra.borrow_mut().position_ptr_mut()
+    }
+
+    /// Create the type information for a fixed size array stored inline, as it would be when
+    /// embedded within a structure.
+    fn inline_type(layout: &Arc<ArrayLayout>, ffi_type: *mut ffi_type) -> TypeInfo {
+        let from_layout = layout.clone();
+        let to_layout = layout.clone();
+        let size_layout = layout.clone();
+        let size = layout.count.unwrap_or(0) * layout.stride;
+
+        TypeInfo {
+            name: layout.name.clone(),
+            ffi_type,
+            conversion_from: Rc::new(move |interpreter, value, align, buffer, extra| {
+                let values = from_layout.element_values(interpreter, value)?;
+
+                from_layout.write(interpreter, &values, buffer, extra)?;
+                buffer
+                    .borrow_mut()
+                    .increment_position(FfiInterface::alignment(size, align));
+
+                Ok(())
+            }),
+            conversion_to: Rc::new(move |interpreter, align, buffer| {
+                let count = to_layout.count.unwrap_or(0);
+                let values = to_layout.read(interpreter, count, buffer)?;
+
+                buffer
+                    .borrow_mut()
+                    .increment_position(FfiInterface::alignment(size, align));
+
+                Ok(ValueVec::from_vec(values).to_value())
+            }),
+            conversion_size: Rc::new(move |interpreter, align, value| {
+                let values = size_layout.element_values(interpreter, value)?;
+                let extra = size_layout.extra_size(interpreter, &values)?;
+
+                Ok((size + FfiInterface::alignment(size, align), extra))
+            }),
+            base_size: Rc::new(move |align| size + FfiInterface::alignment(size, align)),
+            conversion_back: None,
+        }
+    }
+
+    /// Create the type information for passing an array by pointer.  The elements are placed in
+    /// the extra buffer, and copied back into the array value after the call so that it can be used
+    /// as an out parameter.  None is passed as a null pointer, and raw addresses are passed as is.
+    ///
+    /// Only fixed size arrays can be returned, as the length of a variable sized array isn't known.
+    /// Variable sized arrays read back from native memory, such as in a structure, read as none.
+    fn by_pointer_type(layout: &Arc<ArrayLayout>, name: String) -> TypeInfo {
+        let from_layout = layout.clone();
+        let to_layout = layout.clone();
+        let size_layout = layout.clone();
+        let back_layout = layout.clone();
+
+        TypeInfo {
+            name,
+            ffi_type: &raw mut types::pointer,
+            conversion_from: Rc::new(move |interpreter, value, align, buffer, extra| {
+                let ptr_size = size_of::<*const c_void>();
+                let ptr_padding = FfiInterface::alignment(ptr_size, align);
+
+                let address = if value.is_none() {
+                    0
+                } else if FfiInterface::is_address(value) {
+                    value.get_int_val()
+                } else {
+                    // Write the elements into a scratch buffer then copy them into place, so that
+                    // any data they reference can follow them in the extra buffer.
+                    let values = from_layout.element_values(interpreter, value)?;
+                    let size = from_layout.len(&values) * from_layout.stride;
+
+                    let address = from_layout.align_extra(extra);
+                    let start = extra.borrow().position();
+                    let scratch: BufferPtr = ByteBuffer::new_ptr(size);
+
+                    extra.borrow_mut().increment_position(size);
+                    from_layout.write(interpreter, &values, &scratch, extra)?;
+
+                    let bytes = scratch.borrow().get_bytes(0, size);
+                    extra.borrow_mut().set_bytes(start, &bytes);
+
+                    address as i64
+                };
+
+                buffer.borrow_mut().write_int(ptr_size, address);
+                buffer.borrow_mut().increment_position(ptr_padding);
+
+                Ok(())
+            }),
+            conversion_to: Rc::new(move |interpreter, align, buffer| {
+                let ptr_size = size_of::<*const c_void>();
+                let padding = FfiInterface::alignment(ptr_size, align);
+                let address = buffer.borrow_mut().read_int(ptr_size, false) as *const u8;
+
+                buffer.borrow_mut().increment_position(padding);
+
+                match to_layout.count {
+                    Some(count) if !address.is_null() => {
+                        let values = to_layout.read_native(interpreter, count, address)?;
+                        Ok(ValueVec::from_vec(values).to_value())
+                    }
+
+                    _ => Ok(Value::None),
+                }
+            }),
+            conversion_size: Rc::new(move |interpreter, align, value| {
+                let ptr_size = size_of::<*const c_void>();
+                let padding = FfiInterface::alignment(ptr_size, align);
+
+                if value.is_none() || FfiInterface::is_address(value) {
+                    return Ok((ptr_size + padding, 0));
+                }
+
+                // Leave room to align the elements within the extra buffer.
+                let values = size_layout.element_values(interpreter, value)?;
+                let extra = size_layout.len(&values) * size_layout.stride
+                    + size_layout.alignment
+                    + size_layout.extra_size(interpreter, &values)?;
+
+                Ok((ptr_size + padding, extra))
+            }),
+            base_size: Rc::new(|align| {
+                let ptr_size = size_of::<*const c_void>();
+                ptr_size + FfiInterface::alignment(ptr_size, align)
+            }),
+            conversion_back: Some(Rc::new(
+                move |interpreter, value, _align, _buffer, extra| {
+                    if !matches!(value, Value::Vec(_)) {
+                        return Ok(());
+                    }
+
+                    let values = back_layout.element_values(interpreter, value)?;
+                    let count = back_layout.len(&values);
+
+                    let address = back_layout.align_extra(extra) as *const u8;
+                    let new_values = back_layout.read_native(interpreter, count, address)?;
+
+                    ArrayLayout::update(value, new_values);
+
+                    Ok(())
+                },
+            )),
+        }
+    }
+}
+
+thread_local! {
+    /// The interpreter currently making a foreign function call on this thread.  Callbacks run
+    /// their words on this interpreter, as they can only be safely invoked while it's waiting for
+    /// the foreign function to return.
+    static ACTIVE_INTERPRETER: Cell<Option<*mut dyn Interpreter>> = const { Cell::new(None) };
+}
+
+/// A sorth word wrapped in a libffi closure so that it can be called from native code as a function
+/// pointer.  Callbacks live until they're explicitly freed, or the ffi interface is reset.
+struct FfiCallback {
+    /// The name of the word the callback runs.
+    word_name: String,
+
+    /// The handler index of the word the callback runs.
+    word_index: usize,
+
+    /// The types of the arguments passed to the callback.
+    arg_types: Vec<Rc<RefCell<TypeInfo>>>,
+
+    /// The callback's return type.
+    return_type: Rc<RefCell<TypeInfo>>,
+
+    /// The call interface describing the callback to libffi.
+    cif: ffi_cif,
+
+    /// The libffi types of the arguments, referenced by the cif.
+    arg_ffi_types: Vec<*mut ffi_type>,
+
+    /// The closure allocated by libffi.
+    closure: *mut ffi_closure,
+
+    /// The executable address of the closure, the function pointer handed to native code.
+    code: *mut c_void,
+}
+
+impl Drop for FfiCallback {
+    fn drop(&mut self) {
+        if !self.closure.is_null() {
+            unsafe { ffi_closure_free(self.closure as *mut c_void) };
+        }
+    }
+}
+
+impl FfiCallback {
+    /// Convert the native arguments, run the word, and write it's result back for the caller.
+    fn invoke(
+        &self,
+        interpreter: &mut dyn Interpreter,
+        result: *mut c_void,
+        args: *mut *mut c_void,
+    ) -> error::Result<()> {
+        for (index, type_info) in self.arg_types.iter().enumerate() {
+            let size = (type_info.borrow().base_size)(1);
+            let bytes = unsafe { std::slice::from_raw_parts(*args.add(index) as *const u8, size) };
+            let buffer = ByteBuffer::new_ptr(size);
+
+            buffer.borrow_mut().buffer_mut().copy_from_slice(bytes);
+
+            let buffer: BufferPtr = buffer;
+            let value = (type_info.borrow().conversion_to)(interpreter, 1, &buffer)?;
+
+            interpreter.push(value);
+        }
+
+        interpreter.execute_word_index(&location_here!(), self.word_index)?;
+
+        let return_type = self.return_type.borrow();
+        let size = (return_type.base_size)(1);
+
+        if size == 0 {
+            return Ok(());
+        }
+
+        let value = interpreter.pop()?;
+        let (_, extra_size) = (return_type.conversion_size)(interpreter, 1, &value)?;
+
+        // Referenced data would be freed before the caller could use it.
+        if extra_size > 0 {
+            return script_error(
+                interpreter,
+                format!(
+                    "Callback {} can not return a {} that references other data.",
+                    self.word_name, return_type.name
+                ),
+            );
+        }
+
+        let buffer: BufferPtr = ByteBuffer::new_ptr(size);
+        let extra: BufferPtr = ByteBuffer::new_ptr(0);
+
+        (return_type.conversion_from)(interpreter, &value, 1, &buffer, &extra)?;
+
+        // libffi expects integer results smaller than a register to be widened to a full register.
+        let type_code = unsafe { (*return_type.ffi_type).type_ } as u32;
+        let is_signed = matches!(
+            type_code,
+            FFI_TYPE_SINT8 | FFI_TYPE_SINT16 | FFI_TYPE_SINT32
+        );
+        let is_unsigned = matches!(
+            type_code,
+            FFI_TYPE_UINT8 | FFI_TYPE_UINT16 | FFI_TYPE_UINT32
+        );
+
+        let bytes = if is_signed || is_unsigned {
+            buffer.borrow_mut().set_position(0);
+
+            let value = buffer.borrow_mut().read_int(size, is_signed);
+            value.to_ne_bytes().to_vec()
+        } else {
+            buffer.borrow().get_bytes(0, size)
+        };
+
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), result as *mut u8, bytes.len()) };
+
+        Ok(())
+    }
+
+    /// The size of the result buffer libffi hands to the callback.
+    fn result_size(&self) -> usize {
+        let size = unsafe { (*self.return_type.borrow().ffi_type).size };
+        size.max(size_of::<u64>())
+    }
+}
+
+/// The native entry point for all callbacks.  Errors, and panics, are recorded on the interpreter
+/// and raised once the foreign function returns, rather than unwinding into native code.  The
+/// result is left zeroed when the word fails.
+unsafe extern "C" fn ffi_callback_trampoline(
+    _cif: *mut ffi_cif,
+    result: *mut c_void,
+    args: *mut *mut c_void,
+    userdata: *mut c_void,
+) {
+    let callback = unsafe { &*(userdata as *const FfiCallback) };
+
+    unsafe { std::ptr::write_bytes(result as *mut u8, 0, callback.result_size()) };
+
+    let Some(interpreter) = ACTIVE_INTERPRETER.get() else {
+        eprintln!(
+            "Error: ffi callback {} was called outside of a foreign function call.",
+            callback.word_name
+        );
+        return;
+    };
+
+    let interpreter = unsafe { &mut *interpreter };
+
+    // Once a callback has failed, skip any further calls until the foreign function returns.
+    if interpreter.ffi().callback_error.is_some() {
+        return;
+    }
+
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        callback.invoke(interpreter, result, args)
+    }));
+
+    let error = match outcome {
+        Ok(Ok(())) => return,
+        Ok(Err(error)) => error,
+        Err(_) => match script_error::<()>(
+            interpreter,
+            format!("ffi callback {} panicked.", callback.word_name),
+        ) {
+            Err(error) => error,
+            Ok(()) => return,
+        },
+    };
+
+    interpreter.ffi_mut().callback_error = Some(error);
+}
+
+/// Structure that handles a word that calls a foreign function.
+struct FfiWord {
+    /// The library that contains the function.
+    library: Rc<RefCell<Library>>,
+
+    /// The name of the library.
+    library_name: String,
+
+    /// The name of the function to call in the library.
+    function_name: String,
+
+    /// The types of the arguments to the function.
+    arg_types: Vec<Rc<RefCell<TypeInfo>>>,
+
+    /// The function's return type.
+    return_type: Rc<RefCell<TypeInfo>>,
+
+    /// For each argument declared with ffi.len, the index of the argument whose length it's
+    /// automatically filled in with.  These arguments aren't taken from the data stack.
+    length_of: Vec<Option<usize>>,
+
+    /// Does the function take a variable number of arguments after the fixed ones?  If so the
+    /// extra arguments are passed as an array on top of the data stack.
+    is_variadic: bool,
+
+    /// The calling convention used to call the function.
+    abi: ffi_abi,
+
+    /// The alignment of the function's arguments and return value.
+    alignment: usize,
+}
+
+/// Implement the Fn trait for FfiWord to make the struct callable.
+impl Fn<(&mut dyn Interpreter,)> for FfiWord {
+    extern "rust-call" fn call(&self, args: (&mut dyn Interpreter,)) -> error::Result<()> {
+        self.handle_word(args.0)
+    }
+}
+
+/// Implement the FnMut trait for FfiWord to make the struct callable.
+impl FnMut<(&mut dyn Interpreter,)> for FfiWord {
+    extern "rust-call" fn call_mut(&mut self, args: (&mut dyn Interpreter,)) -> error::Result<()> {
+        self.handle_word(args.0)
+    }
+}
+
+/// Implement the FnOnce trait for the FfiWord to make the struct callable.
+impl FnOnce<(&mut dyn Interpreter,)> for FfiWord {
+    type Output = error::Result<()>;
+
+    extern "rust-call" fn call_once(self, args: (&mut dyn Interpreter,)) -> error::Result<()> {
+        self.handle_word(args.0)
+    }
+}
+
+impl FfiWord {
+    /// Create a new FfiWord handler.
+    pub fn new(
+        library: Rc<RefCell<Library>>,
+        library_name: String,
+        function_name: String,
+        arg_types: Vec<Rc<RefCell<TypeInfo>>>,
+        return_type: Rc<RefCell<TypeInfo>>,
+        length_of: Vec<Option<usize>>,
+    ) -> FfiWord {
+        FfiWord {
+            library,
+            library_name,
+            function_name,
+            arg_types,
+            return_type,
+            length_of,
+            is_variadic: false,
+            abi: ffi_abi_FFI_DEFAULT_ABI,
+            alignment: 8,
+        }
+    }
+
+    /// Handle the word by calling the foreign function.
+    fn handle_word(&self, interpreter: &mut dyn Interpreter) -> error::Result<()> {
+        let library = self.library.borrow();
+
+        // Get the function from the library.
+        let function: Symbol<*mut c_void> =
+            match unsafe { library.get(self.function_name.as_bytes()) } {
+                Ok(function) => function,
+                Err(error) => {
+                    return script_error(
+                        interpreter,
+                        format!(
+                            "Failed to get library {} symbol {}: {}.",
+                            self.library_name, self.function_name, error
+                        ),
+                    );
+                }
+            };
+
+        // Allocate the buffers for the parameters and populate them.  We also get the array of
+        // pointers to the parameters.
+        let buffer: BufferPtr = ByteBuffer::new_ptr(0);
+        let extra_buffer: BufferPtr = ByteBuffer::new_ptr(0);
+
+        // The variable arguments, if any, are on top of the stack.  Their types come from their
+        // values so they're only known now.
+        let (variadic_types, variadic_values) = match self.is_variadic {
+            true => FfiWord::pop_variadic_args(interpreter)?,
+            false => (Vec::new(), Vec::new()),
+        };
+
+        let mut all_arg_types = self.arg_types.clone();
+        all_arg_types.extend(variadic_types);
+
+        let (mut param_value_ptrs, arg_values, arg_positions) = self.get_param_value_ptrs(
+            interpreter,
+            &all_arg_types,
+            variadic_values,
+            &buffer,
+            &extra_buffer,
+        )?;
+
+        // Allocate the buffer for the return value.
+        let return_buffer =
+            ByteBuffer::new_ptr((self.return_type.borrow().base_size)(self.alignment));
+        let mut return_buffer: BufferPtr = return_buffer.clone();
+
+        // Create the array of raw ffi_type pointers for creating the ffi_cif.
+        let mut arg_types = all_arg_types
+            .iter()
+            .map(|type_info| type_info.borrow().ffi_type)
+            .collect::<Vec<_>>();
+
+        // Create teh ffi cif and if successful call the function.
+        let mut cif: ffi_cif = unsafe { std::mem::zeroed() };
+        let code_ptr = unsafe {
+            Some(std::mem::transmute::<
+                *mut std::ffi::c_void,
+                unsafe extern "C" fn(),
+            >(*function))
+        };
+
+        let status = unsafe {
+            match self.is_variadic {
+                true => ffi_prep_cif_var(
+                    &mut cif,
+                    self.abi,
+                    self.arg_types.len() as u32,
+                    arg_types.len() as u32,
+                    self.return_type.borrow().ffi_type,
+                    arg_types.as_mut_ptr(),
+                ),
+                false => ffi_prep_cif(
+                    &mut cif,
+                    self.abi,
+                    arg_types.len() as u32,
+                    self.return_type.borrow().ffi_type,
+                    arg_types.as_mut_ptr(),
+                ),
+            }
+        };
+
+        if status != ffi_status_FFI_OK {
+            return script_error_str(interpreter, "Failed to create FFI cif.");
+        }
+
+        // Make this interpreter available to any callbacks the function invokes.
+        let interpreter_ptr = unsafe {
+            std::mem::transmute::<*mut (dyn Interpreter + '_), *mut (dyn Interpreter + 'static)>(
+                interpreter,
+            )
+        };
+        let previous_interpreter = ACTIVE_INTERPRETER.replace(Some(interpreter_ptr));
+
+        unsafe {
+            ffi_call(
+                &mut cif,
+                code_ptr,
+                return_buffer.borrow_mut().byte_ptr_mut(),
+                param_value_ptrs.as_mut_ptr(),
+            );
+        }
+
+        // Capture errno before anything else has a chance to change it.
+        let errno = std::io::Error::last_os_error().raw_os_error().unwrap_or(0);
+
+        ACTIVE_INTERPRETER.set(previous_interpreter);
+        interpreter.ffi_mut().errno = errno;
+
+        // Now that no native code is running, callbacks freed during the call can be released.
+        if previous_interpreter.is_none() {
+            interpreter.ffi_mut().freed_callbacks.clear();
+        }
+
+        if let Some(error) = interpreter.ffi_mut().callback_error.take() {
+            return Err(error);
+        }
+
+        // Give any parameters passed by pointer a chance to copy back what the function wrote.
+        for (index, value) in arg_values.iter().enumerate() {
+            let conversion_back = all_arg_types[index].borrow().conversion_back.clone();
+
+            if let Some(conversion_back) = conversion_back {
+                let (position, extra_position) = arg_positions[index];
+
+                buffer.borrow_mut().set_position(position);
+                extra_buffer.borrow_mut().set_position(extra_position);
+
+                conversion_back(interpreter, value, self.alignment, &buffer, &extra_buffer)?;
+            }
+        }
+
+        // Convert the return value to an interpreter Value and push it onto the data stack.  But
+        // only if the return type is not void.
+        let value = (self.return_type.borrow().conversion_to)(
+            interpreter,
+            self.alignment,
+            &mut return_buffer,
+        )?;
+
+        if !value.is_none() {
+            interpreter.push(value);
+        }
+
+        // All done.
+        Ok(())
+    }
+
+    /// Pop the fixed parameters from the data stack, convert them and any variadic values to the
+    /// native types in the supplied byte buffers, and return a vector of pointers to the converted
+    /// values.  The original values are returned along with the buffer positions they were
+    /// converted at.
+    fn get_param_value_ptrs(
+        &self,
+        interpreter: &mut dyn Interpreter,
+        arg_types: &[Rc<RefCell<TypeInfo>>],
+        variadic_values: Vec<Value>,
+        buffer: &BufferPtr,
+        extra_buffer: &BufferPtr,
+    ) -> error::Result<ParamValues> {
+        let fixed_len = self.arg_types.len();
+        let args_len = arg_types.len();
+
+        let mut arg_values: Vec<Value> = Vec::with_capacity(args_len);
+        let mut arg_value_ptrs = Vec::with_capacity(args_len);
+
+        let mut base_size = 0;
+        let mut extra_size = 0;
+
+        arg_values.resize(fixed_len, Value::None);
+
+        for index in (0..fixed_len).rev() {
+            if self.length_of[index].is_none() {
+                arg_values[index] = interpreter.pop()?;
+            }
+        }
+
+        // Fill in the lengths of any pointer plus length argument pairs.
+        for index in 0..fixed_len {
+            if let Some(target) = self.length_of[index] {
+                arg_values[index] = FfiWord::value_length(interpreter, &arg_values[target])?;
+            }
+        }
+
+        arg_values.extend(variadic_values);
+
+        for (index, value) in arg_values.iter().enumerate() {
+            let (size, extra) =
+                (arg_types[index].borrow().conversion_size)(interpreter, self.alignment, value)?;
+
+            base_size += size;
+            extra_size += extra;
+        }
+
+        buffer.borrow_mut().resize(base_size);
+        extra_buffer.borrow_mut().resize(extra_size);
+
+        let mut arg_positions = Vec::with_capacity(args_len);
+
+        for (index, value) in arg_values.iter().enumerate() {
+            arg_value_ptrs.push(buffer.borrow_mut().position_ptr_mut());
+            arg_positions.push((buffer.borrow().position(), extra_buffer.borrow().position()));
+
+            (arg_types[index].borrow().conversion_from)(
+                interpreter,
+                value,
+                self.alignment,
+                buffer,
+                extra_buffer,
+            )?;
+        }
+
+        Ok((arg_value_ptrs, arg_values, arg_positions))
+    }
+
+    /// Pop the array of variable arguments from the data stack and work out the native type of
+    /// each one.  Integers are passed as ffi.i64, floats as ffi.f64, strings as ffi.string, byte
+    /// buffers as ffi.buffer and none as a null ffi.ptr.  A two item array of a type name and a
+    /// value passes the value as that type instead, [ "ffi.i32" , 5 ].
+    fn pop_variadic_args(interpreter: &mut dyn Interpreter) -> error::Result<VariadicArgs> {
+        let args = interpreter.pop_as_array()?;
+        let args = args.borrow();
+
+        let mut types = Vec::with_capacity(args.len());
+        let mut values = Vec::with_capacity(args.len());
+
+        for arg in args.iter() {
+            let (type_name, value) = match arg {
+                Value::Vec(pair)
+                    if pair.borrow().len() == 2 && matches!(pair.borrow()[0], Value::String(_)) =>
+                {
+                    let pair = pair.borrow();
+                    (pair[0].get_string_val(), pair[1].clone())
+                }
+                Value::Int(_) | Value::Bool(_) => ("ffi.i64".to_string(), arg.clone()),
+                Value::Float(_) => ("ffi.f64".to_string(), arg.clone()),
+                Value::String(_) => ("ffi.string".to_string(), arg.clone()),
+                Value::ByteBuffer(_) => ("ffi.buffer".to_string(), arg.clone()),
+                Value::None => ("ffi.ptr".to_string(), arg.clone()),
+                _ => {
+                    return script_error(
+                        interpreter,
+                        format!(
+                            "Can not pass {} as a variadic argument, use a [ type , value ] pair.",
+                            arg
+                        ),
+                    );
+                }
+            };
+
+            if type_name == "ffi.void" || is_inline_array(&type_name) {
+                return script_error(
+                    interpreter,
+                    format!("Type {} can not be a variadic argument.", type_name),
+                );
+            }
+
+            check_by_value(interpreter, &type_name)?;
+            types.push(find_type(interpreter, &type_name)?);
+            values.push(value);
+        }
+
+        Ok((types, values))
+    }
+
+    /// Get the length of an argument for an ffi.len argument.  Arrays give their item count, while
+    /// byte buffers and strings give their size in bytes.
+    fn value_length(interpreter: &mut dyn Interpreter, value: &Value) -> error::Result<Value> {
+        let length = match value {
+            Value::Vec(vec_ptr) => vec_ptr.borrow().len(),
+            Value::ByteBuffer(buffer) => buffer.borrow().len(),
+            Value::String(string) => string.len(),
+            Value::None => 0,
+            _ => {
+                return script_error(
+                    interpreter,
+                    format!("Can not take the length of {} for ffi.len.", value),
+                );
+            }
+        };
+
+        Ok((length as i64).to_value())
+    }
+}
+
+/// Load a native library and register it with the ffi interface under the library's alias name.
+fn word_ffi_open(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let register_name = interpreter.pop_as_string()?;
+    let lib_name = interpreter.pop_as_string()?;
+
+    if interpreter.ffi().libs.contains_key(&register_name) {
+        error::script_error(
+            interpreter,
+            format!("Library {} is already loaded.", register_name),
+        )?;
+    }
+
+    let lib = unsafe { Library::new(lib_name.clone()) };
+
+    match lib {
+        Ok(lib) => {
+            let _ = interpreter
+                .ffi_mut()
+                .libs
+                .insert(register_name, Rc::new(RefCell::new(lib)));
+        }
+
+        Err(error) => {
+            return script_error(
+                interpreter,
+                format!("Failed to load library {}: {}.", lib_name, error),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a new word that calls a foreign function.
+fn word_ffi_fn(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let ret_type_name = interpreter.pop_as_string()?;
+    let param_type_names = interpreter.pop_as_array()?;
+    let abi_name = interpreter.pop_as_string()?;
+    let mut fn_alias = interpreter.pop_as_string()?;
+    let fn_name = interpreter.pop()?;
+    let lib_name = interpreter.pop_as_string()?;
+
+    // Get the location of the name of the function from the script.
+    let location = fn_name.as_token(interpreter)?.location();
+
+    let fn_name = fn_name.get_string_val();
+
+    // If the alias is empty, use the function name as the alias.
+    if fn_alias.is_empty() {
+        fn_alias = fn_name.clone();
+    }
+
+    // Get the library from the ffi interface.  Then check to see if the function is in the library.
+
+    let lib: Rc<RefCell<Library>> = match interpreter.ffi().libs.get(&lib_name) {
+        Some(lib) => lib.clone(),
+        None => return script_error(interpreter, format!("Library {} is not loaded.", lib_name)),
+    };
+
+    {
+        let lib_borrow = lib.borrow();
+        if let Err(error) = unsafe { lib_borrow.get::<Symbol<*mut c_void>>(fn_name.as_bytes()) } {
+            return script_error(
+                interpreter,
+                format!(
+                    "Failed to get symbol {} from library {}: {}.",
+                    fn_name, lib_name, error
+                ),
+            );
+        }
+    }
+
+    let abi = find_abi(interpreter, &abi_name)?;
+
+    // A trailing ... marks the function as variadic, the extra arguments are given as an array.
+    let is_variadic = match param_type_names
+        .borrow()
+        .iter()
+        .position(|name| name.to_string() == "...")
+    {
+        Some(index) if index + 1 == param_type_names.borrow().len() => true,
+        Some(_) => {
+            return script_error_str(interpreter, "The ... must be the last parameter type.");
+        }
+        None => false,
+    };
+
+    // Get the type information for the parameter types.  A type preceded by ffi.len is filled in
+    // with the length of the argument before it, or the one after it if it's the first argument.
+    let (arg_type_infos, length_of) = {
+        let mut arg_type_infos = Vec::with_capacity(param_type_names.borrow().len());
+        let mut length_of = Vec::with_capacity(param_type_names.borrow().len());
+        let mut is_length = false;
+
+        for param_type_name in param_type_names.borrow().iter() {
+            let param_type_name = match param_type_name.is_token() {
+                true => param_type_name.to_string(),
+                false => {
+                    return script_error_str(
+                        interpreter,
+                        "Parameter type name, {}, is not a string.",
+                    );
+                }
+            };
+
+            if param_type_name == "..." {
+                break;
+            }
+
+            if param_type_name == "ffi.len" {
+                if is_length {
+                    return script_error_str(interpreter, "Found ffi.len ffi.len in parameters.");
+                }
+
+                is_length = true;
+                continue;
+            }
+
+            if is_inline_array(&param_type_name) {
+                return script_error(
+                    interpreter,
+                    format!(
+                        "Array type {} can only be used in structures, use {}-ptr.",
+                        param_type_name, param_type_name
+                    ),
+                );
+            }
+
+            check_by_value(interpreter, &param_type_name)?;
+
+            let type_info = match interpreter.ffi().types.get(&param_type_name) {
+                Some(type_info) => type_info,
+                None => {
+                    return script_error(
+                        interpreter,
+                        format!("Unknown ffi type name {}.", param_type_name),
+                    );
+                }
+            };
+
+            arg_type_infos.push(type_info.clone());
+            length_of.push(is_length.then_some(arg_type_infos.len() - 1));
+            is_length = false;
+        }
+
+        if is_length {
+            return script_error_str(interpreter, "Expected a parameter type after ffi.len.");
+        }
+
+        // Point each length argument at the argument it measures.
+        for index in 0..length_of.len() {
+            if length_of[index].is_none() {
+                continue;
+            }
+
+            let target = if index > 0 { index - 1 } else { 1 };
+
+            if target >= length_of.len() || length_of[target].is_some() {
+                return script_error(
+                    interpreter,
+                    format!(
+                        "The ffi.len parameter of {} has no argument to measure.",
+                        fn_name
+                    ),
+                );
+            }
+
+            length_of[index] = Some(target);
+        }
+
+        (arg_type_infos, length_of)
+    };
+
+    if ret_type_name.ends_with("[]") || is_inline_array(&ret_type_name) {
+        return script_error(
+            interpreter,
+            format!(
+                "Array type {} can not be returned, use a fixed size array's -ptr type.",
+                ret_type_name
+            ),
+        );
+    }
+
+    check_by_value(interpreter, &ret_type_name)?;
+
+    // Get the type information for the return value.
+    let ret_type_info = match interpreter.ffi().types.get(&ret_type_name) {
+        Some(ret_type_info) => ret_type_info.clone(),
+        None => {
+            return script_error(
+                interpreter,
+                format!("Unknown ffi type name {}.", ret_type_name),
+            );
+        }
+    };
+
+    // Create the signature for the word's description.
+    let arg_signature = {
+        let mut signature = String::new();
+
+        if is_variadic || length_of.iter().any(|length| length.is_none()) {
+            for (arg_type, length) in arg_type_infos.iter().zip(length_of.iter()) {
+                if length.is_none() {
+                    signature.push_str(&arg_type.borrow().name);
+                    signature.push(' ');
+                }
+            }
+
+            if is_variadic {
+                signature.push_str("[ ... ] ");
+            }
+
+            signature.push_str("-- ");
+        } else {
+            signature = " -- ".to_string();
+        }
+
+        signature.push_str(&ret_type_name);
+
+        signature
+    };
+
+    // Create the word handler for the foreign function, then add the new word to the interpreter.
+    let word = FfiWord {
+        is_variadic,
+        abi,
+        ..FfiWord::new(
+            lib,
+            lib_name.clone(),
+            fn_name.clone(),
+            arg_type_infos,
+            ret_type_info,
+            length_of,
+        )
+    };
+
+    interpreter.add_word(
+        location.path().clone(),
+        location.line(),
+        location.column(),
+        fn_alias,
+        Rc::new(word),
+        format!("Call native function {} in library {}.", fn_name, lib_name),
+        arg_signature,
+        WordRuntime::Normal,
+        WordVisibility::Visible,
+        WordType::Native,
+    );
+
+    Ok(())
+}
+
+/// The calling conventions libffi supports on this platform besides the default, by the names
+/// accepted in ffi.fn and ffi.callback's abi clause.
+#[cfg(all(target_arch = "x86_64", unix))]
+const PLATFORM_ABIS: &[(&str, ffi_abi)] = &[
+    ("unix64", raw::ffi_abi_FFI_UNIX64),
+    ("win64", raw::ffi_abi_FFI_WIN64),
+    ("efi64", raw::ffi_abi_FFI_EFI64),
+    ("gnuw64", raw::ffi_abi_FFI_GNUW64),
+];
+
+#[cfg(all(target_arch = "x86_64", windows))]
+const PLATFORM_ABIS: &[(&str, ffi_abi)] = &[
+    ("win64", raw::ffi_abi_FFI_WIN64),
+    ("gnuw64", raw::ffi_abi_FFI_GNUW64),
+];
+
+#[cfg(target_arch = "x86")]
+const PLATFORM_ABIS: &[(&str, ffi_abi)] = &[
+    ("sysv", raw::ffi_abi_FFI_SYSV),
+    ("stdcall", raw::ffi_abi_FFI_STDCALL),
+    ("thiscall", raw::ffi_abi_FFI_THISCALL),
+    ("fastcall", raw::ffi_abi_FFI_FASTCALL),
+    ("ms-cdecl", raw::ffi_abi_FFI_MS_CDECL),
+    ("pascal", raw::ffi_abi_FFI_PASCAL),
+    ("register", raw::ffi_abi_FFI_REGISTER),
+];
+
+#[cfg(target_arch = "arm")]
+const PLATFORM_ABIS: &[(&str, ffi_abi)] = &[
+    ("sysv", raw::ffi_abi_FFI_SYSV),
+    ("vfp", raw::ffi_abi_FFI_VFP),
+];
+
+#[cfg(target_arch = "aarch64")]
+const PLATFORM_ABIS: &[(&str, ffi_abi)] = &[("sysv", raw::ffi_abi_FFI_SYSV)];
+
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "x86",
+    target_arch = "arm",
+    target_arch = "aarch64"
+)))]
+const PLATFORM_ABIS: &[(&str, ffi_abi)] = &[];
+
+/// Look up a calling convention by name.  An empty name or "default" is the platform's default
+/// calling convention.
+fn find_abi(interpreter: &mut dyn Interpreter, name: &str) -> error::Result<ffi_abi> {
+    if name.is_empty() || name == "default" {
+        return Ok(ffi_abi_FFI_DEFAULT_ABI);
+    }
+
+    match PLATFORM_ABIS.iter().find(|(abi_name, _)| *abi_name == name) {
+        Some((_, abi)) => Ok(*abi),
+        None => {
+            let known = PLATFORM_ABIS
+                .iter()
+                .map(|(abi_name, _)| *abi_name)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            script_error(
+                interpreter,
+                format!(
+                    "Unknown calling convention {}, this platform supports: default{}{}.",
+                    name,
+                    if known.is_empty() { "" } else { ", " },
+                    known
+                ),
+            )
+        }
+    }
+}
+
+/// Is the type name a fixed size array stored inline, such as ffi.i32[4]?  These can only be used
+/// within structures, C passes arrays to functions by pointer.
+fn is_inline_array(type_name: &str) -> bool {
+    type_name.ends_with(']') && !type_name.ends_with("[]")
+}
+
+/// Convert a packing or alignment value to a number.  Zero means that the fields use their natural
+/// alignment.
+fn alignment_value(interpreter: &mut dyn Interpreter, value: &Value) -> error::Result<usize> {
+    let alignment = if value.is_numeric() {
+        Some(value.get_int_val())
+    } else if value.is_stringable() {
+        value.get_string_val().parse::<i64>().ok()
+    } else {
+        None
+    };
+
+    match alignment {
+        Some(alignment)
+            if alignment == 0 || (alignment > 0 && (alignment as u64).is_power_of_two()) =>
+        {
+            Ok(alignment as usize)
+        }
+        _ => script_error(
+            interpreter,
+            format!("Structure alignment {} must be 0 or a power of two.", value),
+        ),
+    }
+}
+
+// Create a new structure compatible with the ffi interface.  The native layout follows the C rules
+// for the platform, with fields aligned to the smaller of their natural alignment and the packing
+// value if one is given.  Two ffi types are registered: the structure's name for passing by value,
+// and name-ptr for passing by pointer.  A matching sorth structure is also created.
+//
+// Packed structures that change the natural layout can only be passed by pointer, libffi always uses
+// the natural layout for values.
+fn word_ffi_struct(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let found_initializers = interpreter.pop_as_bool()?;
+    let is_hidden = interpreter.pop_as_bool()?;
+    let type_names = interpreter.pop_as_array()?;
+    let raw_field_names = interpreter.pop_as_array()?;
+    let packing = interpreter.pop()?;
+    let name = interpreter.pop_as_token()?;
+
+    // Get the location of the struct definition from the name token's location.  Then convert the
+    // name token to a string.
+    let location = name.location().clone();
+    let name = name.text(interpreter)?.clone();
+
+    // Get an array of default values if they were found.  Otherwise use the default value of none.
+    let defaults = if found_initializers {
+        interpreter.pop_as_array()?
+    } else {
+        ValueVec::new(raw_field_names.borrow().len())
+    };
+
+    let packing = alignment_value(interpreter, &packing)?;
+
+    let field_names: Vec<Value> = raw_field_names.borrow().iter().cloned().collect();
+    let type_names: Vec<Value> = type_names.borrow().iter().cloned().collect();
+
+    if field_names.len() != type_names.len() {
+        return script_error(
+            interpreter,
+            format!("Structure {} has mismatched field and type lists.", name),
+        );
+    }
+
+    // Compute the layout of the structure, and gather the libffi types of it's fields.
+    let mut fields = Vec::with_capacity(field_names.len());
+    let mut elements = Vec::with_capacity(field_names.len() + 1);
+    let mut offset = 0;
+    let mut struct_alignment = 1;
+
+    // The layout without packing, to find out if the structure can be passed by value.
+    let mut natural_offset = 0;
+    let mut natural_struct_alignment = 1;
+    let mut is_natural = true;
+
+    for type_name in type_names.iter() {
+        if !type_name.is_stringable() {
+            return script_error(
+                interpreter,
+                format!("Field type name, {}, is not a string.", type_name),
+            );
+        }
+
+        let type_name = type_name.get_string_val();
+        let type_info = match interpreter.ffi().types.get(&type_name) {
+            Some(type_info) if type_name != "ffi.void" => type_info.clone(),
+            _ => {
+                return script_error(
+                    interpreter,
+                    format!("Unknown ffi type name {} in structure {}.", type_name, name),
+                );
+            }
+        };
+
+        let type_info = type_info.borrow();
+        let size = (type_info.base_size)(1);
+        let natural_alignment = unsafe { (*type_info.ffi_type).alignment as usize }.max(1);
+
+        let alignment = if packing > 0 {
+            natural_alignment.min(packing)
+        } else {
+            natural_alignment
+        };
+
+        offset += FfiInterface::alignment(offset, alignment);
+        natural_offset += FfiInterface::alignment(natural_offset, natural_alignment);
+
+        is_natural &=
+            offset == natural_offset && !interpreter.ffi().pointer_only.contains(&type_name);
+
+        fields.push((type_name.clone(), offset));
+        elements.push(type_info.ffi_type);
+
+        offset += size;
+        struct_alignment = struct_alignment.max(alignment);
+
+        natural_offset += size;
+        natural_struct_alignment = natural_struct_alignment.max(natural_alignment);
+    }
+
+    let size = offset + FfiInterface::alignment(offset, struct_alignment);
+    let natural_size =
+        natural_offset + FfiInterface::alignment(natural_offset, natural_struct_alignment);
+
+    is_natural &= size == natural_size && struct_alignment == natural_struct_alignment;
+
+    elements.push(std::ptr::null_mut());
+
+    let mut native_type = NativeStructType::new(size, struct_alignment, elements);
+
+    let layout = Arc::new(StructLayout {
+        name: name.clone(),
+        fields,
+        size,
+        alignment: struct_alignment,
+    });
+
+    // Register the by value and by pointer types.
+    let ffi_type_ptr: *mut ffi_type = &mut *native_type.ffi_type;
+    let value_type = StructLayout::by_value_type(&layout, ffi_type_ptr);
+    let pointer_type = StructLayout::by_pointer_type(&layout);
+
+    let ffi = interpreter.ffi_mut();
+
+    match is_natural {
+        true => ffi.pointer_only.remove(&value_type.name),
+        false => ffi.pointer_only.insert(value_type.name.clone()),
+    };
+
+    ffi.struct_types.push(native_type);
+    ffi.types
+        .insert(value_type.name.clone(), Rc::new(RefCell::new(value_type)));
+    ffi.types.insert(
+        pointer_type.name.clone(),
+        Rc::new(RefCell::new(pointer_type)),
+    );
+
+    // Finally create the matching sorth structure.
+    let mut names = Vec::with_capacity(field_names.len());
+
+    for field_name in field_names.iter() {
+        if !field_name.is_stringable() {
+            return script_error_str(interpreter, "Field names must be strings.");
+        }
+
+        names.push(field_name.get_string_val());
+    }
+
+    let defaults = defaults.borrow().iter().cloned().collect();
+    let definition_ptr = DataObjectDefinition::new(interpreter, name, names, defaults, is_hidden);
+
+    DataObjectDefinition::create_data_definition_words(
+        interpreter,
+        Some(location),
+        definition_ptr,
+        is_hidden,
+    );
+
+    Ok(())
+}
+
+// Register a new ffi array type for an existing ffi type.  The name is the element type followed
+// by the array size, as in ffi.i32[4], or by empty brackets for arrays sized by the value passed.
+// A plain type name is treated as a variable sized array of that type.
+//
+// Variable sized arrays are passed by pointer.  Fixed size arrays register two types, name for
+// embedding the array within a structure, and name-ptr for passing it by pointer.
+fn word_ffi_array(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let name = interpreter.pop()?;
+
+    if !name.is_stringable() {
+        return script_error(
+            interpreter,
+            format!("Array type name, {}, is not a string.", name),
+        );
+    }
+
+    let name = name.get_string_val();
+
+    // Split the name into the element type name and the array size.
+    let (element, count) = match name
+        .strip_suffix(']')
+        .and_then(|name| name.rsplit_once('['))
+    {
+        Some((element, "")) => (element.to_string(), None),
+        Some((element, count)) => match count.parse::<usize>() {
+            Ok(count) if count > 0 => (element.to_string(), Some(count)),
+            _ => {
+                return script_error(
+                    interpreter,
+                    format!("Invalid array size in type name {}.", name),
+                );
+            }
+        },
+        None => (name.clone(), None),
+    };
+
+    let type_info = match interpreter.ffi().types.get(&element) {
+        Some(type_info) if element != "ffi.void" => type_info.clone(),
+        _ => {
+            return script_error(
+                interpreter,
+                format!("Unknown ffi type name {} for array {}.", element, name),
+            );
+        }
+    };
+
+    let array_name = match count {
+        Some(count) => format!("{}[{}]", element, count),
+        None => format!("{}[]", element),
+    };
+
+    let element_type = type_info.borrow().ffi_type;
+    let layout = Arc::new(ArrayLayout::for_element(
+        array_name,
+        &element,
+        count,
+        &type_info.borrow(),
+    ));
+    let (stride, alignment) = (layout.stride, layout.alignment);
+
+    match count {
+        Some(count) => {
+            // libffi describes fixed size arrays as a structure with an element for each item.
+            let mut elements = vec![element_type; count];
+            elements.push(std::ptr::null_mut());
+
+            let mut native_type = NativeStructType::new(count * stride, alignment, elements);
+
+            let ffi_type_ptr: *mut ffi_type = &mut *native_type.ffi_type;
+            let inline_type = ArrayLayout::inline_type(&layout, ffi_type_ptr);
+            let pointer_type =
+                ArrayLayout::by_pointer_type(&layout, format!("{}-ptr", layout.name));
+
+            let ffi = interpreter.ffi_mut();
+
+            // An array of packed structures embedded in a structure changes it's layout too.
+            match ffi.pointer_only.contains(&element) {
+                true => ffi.pointer_only.insert(inline_type.name.clone()),
+                false => ffi.pointer_only.remove(&inline_type.name),
+            };
+
+            ffi.struct_types.push(native_type);
+            ffi.types
+                .insert(inline_type.name.clone(), Rc::new(RefCell::new(inline_type)));
+            ffi.types.insert(
+                pointer_type.name.clone(),
+                Rc::new(RefCell::new(pointer_type)),
+            );
+        }
+
+        None => {
+            let pointer_type = ArrayLayout::by_pointer_type(&layout, layout.name.clone());
+
+            interpreter.ffi_mut().types.insert(
+                pointer_type.name.clone(),
+                Rc::new(RefCell::new(pointer_type)),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// Create a native function pointer that runs a sorth word.  The callback's arguments are pushed in
+// order before the word runs, and unless the return type is void, it's result is popped after.  The
+// callback lives until it's freed with ffi.callback.free.
+fn word_ffi_callback(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let ret_type_name = interpreter.pop_as_string()?;
+    let param_type_names = interpreter.pop_as_array()?;
+    let abi_name = interpreter.pop_as_string()?;
+    let word_name = interpreter.pop()?.get_string_val();
+
+    let abi = find_abi(interpreter, &abi_name)?;
+
+    let word_index = match interpreter.find_word(&word_name) {
+        Some(word_info) => word_info.handler_index,
+        None => {
+            return script_error(
+                interpreter,
+                format!("Word {} not found for ffi callback.", word_name),
+            );
+        }
+    };
+
+    let mut arg_types = Vec::with_capacity(param_type_names.borrow().len());
+
+    for param_type_name in param_type_names.borrow().iter() {
+        let param_type_name = param_type_name.get_string_val();
+
+        if param_type_name == "ffi.void" || is_inline_array(&param_type_name) {
+            return script_error(
+                interpreter,
+                format!("Type {} can not be a callback parameter.", param_type_name),
+            );
+        }
+
+        check_by_value(interpreter, &param_type_name)?;
+        arg_types.push(find_type(interpreter, &param_type_name)?);
+    }
+
+    check_by_value(interpreter, &ret_type_name)?;
+    let return_type = find_type(interpreter, &ret_type_name)?;
+
+    let mut callback = Box::new(FfiCallback {
+        word_name,
+        word_index,
+        arg_ffi_types: arg_types
+            .iter()
+            .map(|type_info| type_info.borrow().ffi_type)
+            .collect(),
+        arg_types,
+        return_type,
+        cif: unsafe { std::mem::zeroed() },
+        closure: std::ptr::null_mut(),
+        code: std::ptr::null_mut(),
+    });
+
+    let status = unsafe {
+        ffi_prep_cif(
+            &mut callback.cif,
+            abi,
+            callback.arg_ffi_types.len() as u32,
+            callback.return_type.borrow().ffi_type,
+            callback.arg_ffi_types.as_mut_ptr(),
+        )
+    };
+
+    if status != ffi_status_FFI_OK {
+        return script_error_str(interpreter, "Failed to create FFI cif for callback.");
+    }
+
+    callback.closure =
+        unsafe { ffi_closure_alloc(size_of::<ffi_closure>(), &mut callback.code) } as *mut _;
+
+    if callback.closure.is_null() {
+        return script_error_str(interpreter, "Failed to allocate FFI callback.");
+    }
+
+    let userdata = &*callback as *const FfiCallback as *mut c_void;
+    let status = unsafe {
+        ffi_prep_closure_loc(
+            callback.closure,
+            &mut callback.cif,
+            Some(ffi_callback_trampoline),
+            userdata,
+            callback.code,
+        )
+    };
+
+    if status != ffi_status_FFI_OK {
+        return script_error_str(interpreter, "Failed to prepare FFI callback.");
+    }
+
+    let address = callback.code as usize;
+
+    interpreter.ffi_mut().callbacks.insert(address, callback);
+    interpreter.push((address as i64).to_value());
 
-            arg_values[index] = value;
-        }
+    Ok(())
+}
 
-        buffer.borrow_mut().resize(base_size);
-        extra_buffer.borrow_mut().resize(extra_size);
+// Free a callback created by ffi.callback.  Native code must not call it afterwards.  Freeing a
+// callback from inside a foreign function call, including from the callback itself, is deferred
+// until that call returns.
+fn word_ffi_callback_free(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let address = interpreter.pop_as_int()? as usize;
 
-        for (index, value) in arg_values.iter().enumerate() {
-            arg_value_ptrs.push(buffer.borrow_mut().position_ptr_mut());
-            (self.arg_types[index].borrow().conversion_from)(
-                interpreter,
-                value,
-                self.alignment,
-                buffer,
-                extra_buffer,
-            )?;
+    match interpreter.ffi_mut().callbacks.remove(&address) {
+        Some(callback) => {
+            interpreter.ffi_mut().release_callback(callback);
+            Ok(())
         }
 
-        Ok(arg_value_ptrs)
+        None => script_error(
+            interpreter,
+            format!("Address {:#x} is not a live ffi callback.", address),
+        ),
     }
 }
 
-/// Load a native library and register it with the ffi interface under the library's alias name.
-fn word_ffi_open(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    let register_name = interpreter.pop_as_string()?;
-    let lib_name = interpreter.pop_as_string()?;
+/// Pop an address from the stack.  None is treated as a null address.
+fn pop_address(interpreter: &mut dyn Interpreter) -> error::Result<*mut c_void> {
+    let value = interpreter.pop()?;
 
-    if interpreter.ffi().libs.contains_key(&register_name) {
-        error::script_error(
+    if !value.is_numeric() {
+        return script_error(
             interpreter,
-            format!("Library {} is already loaded.", register_name),
-        )?;
+            format!("Expected an address, found {}.", value),
+        );
     }
 
-    let lib = unsafe { Library::new(lib_name.clone()) };
+    Ok(value.get_int_val() as *mut c_void)
+}
 
-    match lib {
-        Ok(lib) => {
-            let _ = interpreter
-                .ffi_mut()
-                .libs
-                .insert(register_name, Rc::new(RefCell::new(lib)));
-        }
+/// Pop a type name from the stack and look up it's type information.  The void type has no value
+/// to read or write so it isn't allowed.
+fn pop_value_type(interpreter: &mut dyn Interpreter) -> error::Result<Rc<RefCell<TypeInfo>>> {
+    let type_name = interpreter.pop_as_string()?;
 
-        Err(error) => {
-            return script_error(
-                interpreter,
-                format!("Failed to load library {}: {}.", lib_name, error),
-            );
-        }
+    if type_name == "ffi.void" {
+        return script_error_str(interpreter, "The type ffi.void has no value.");
     }
 
+    find_type(interpreter, &type_name)
+}
+
+/// Push the null address.
+///
+/// Signature: ` -- address`
+fn word_ffi_null(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    interpreter.push(0_i64.to_value());
     Ok(())
 }
 
-/// Create a new word that calls a foreign function.
-fn word_ffi_fn(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    let ret_type_name = interpreter.pop_as_string()?;
-    let param_type_names = interpreter.pop_as_array()?;
-    let mut fn_alias = interpreter.pop_as_string()?;
-    let fn_name = interpreter.pop()?;
-    let lib_name = interpreter.pop_as_string()?;
+/// Allocate native memory with the C library's malloc.  The memory is uninitialized.
+///
+/// Signature: `size -- address`
+fn word_ffi_malloc(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let size = interpreter.pop_as_usize()?;
+    let address = unsafe { libc::malloc(size.max(1)) };
 
-    // Get the location of the name of the function from the script.
-    let location = fn_name.as_token(interpreter)?.location();
+    if address.is_null() {
+        return script_error(
+            interpreter,
+            format!("Failed to allocate {} bytes of native memory.", size),
+        );
+    }
 
-    let fn_name = fn_name.get_string_val();
+    interpreter.push((address as i64).to_value());
 
-    // If the alias is empty, use the function name as the alias.
-    if fn_alias.is_empty() {
-        fn_alias = fn_name.clone();
+    Ok(())
+}
+
+/// Free native memory with the C library's free, such as memory from ffi.malloc, or memory a
+/// foreign function returns for the caller to free.  Freeing null does nothing.
+///
+/// Signature: `address -- `
+fn word_ffi_free(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let address = pop_address(interpreter)?;
+
+    unsafe { libc::free(address) };
+
+    Ok(())
+}
+
+/// Read a value of the given ffi type from native memory.
+///
+/// Signature: `address type-name -- value`
+fn word_ffi_peek(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let type_info = pop_value_type(interpreter)?;
+    let address = pop_address(interpreter)?;
+
+    if address.is_null() {
+        return script_error_str(interpreter, "Attempted to read from a null address.");
     }
 
-    // Get the library from the ffi interface.  Then check to see if the function is in the library.
+    let size = (type_info.borrow().base_size)(1);
+    let bytes = unsafe { std::slice::from_raw_parts(address as *const u8, size) };
+    let buffer = ByteBuffer::new_ptr(size);
 
-    let lib: Rc<RefCell<Library>> = match interpreter.ffi().libs.get(&lib_name) {
-        Some(lib) => lib.clone(),
-        None => return script_error(interpreter, format!("Library {} is not loaded.", lib_name)),
-    };
+    buffer.borrow_mut().buffer_mut().copy_from_slice(bytes);
 
-    {
-        let lib_borrow = lib.borrow();
-        if let Err(error) = unsafe { lib_borrow.get::<Symbol<*mut c_void>>(fn_name.as_bytes()) } {
-            return script_error(
-                interpreter,
-                format!(
-                    "Failed to get symbol {} from library {}: {}.",
-                    fn_name, lib_name, error
-                ),
-            );
-        }
+    let buffer: BufferPtr = buffer;
+    let value = (type_info.borrow().conversion_to)(interpreter, 1, &buffer)?;
+
+    interpreter.push(value);
+
+    Ok(())
+}
+
+/// Write a value as the given ffi type into native memory.  Values that reference other data, such
+/// as strings, can't be written as that data wouldn't outlive the write.
+///
+/// Signature: `value address type-name -- `
+fn word_ffi_poke(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let type_info = pop_value_type(interpreter)?;
+    let address = pop_address(interpreter)?;
+    let value = interpreter.pop()?;
+
+    if address.is_null() {
+        return script_error_str(interpreter, "Attempted to write to a null address.");
     }
 
-    // Get the type information for the parameter types.
-    let arg_type_infos = {
-        let mut arg_type_infos = Vec::with_capacity(param_type_names.borrow().len());
+    let type_info = type_info.borrow();
+    let (_, extra_size) = (type_info.conversion_size)(interpreter, 1, &value)?;
 
-        for param_type_name in param_type_names.borrow().iter() {
-            let param_type_name = match param_type_name.is_token() {
-                true => param_type_name.to_string(),
-                false => {
-                    return script_error_str(
-                        interpreter,
-                        "Parameter type name, {}, is not a string.",
-                    );
-                }
-            };
+    if extra_size > 0 {
+        return script_error(
+            interpreter,
+            format!(
+                "Can not write a {} that references other data to native memory.",
+                type_info.name
+            ),
+        );
+    }
 
-            let type_info = match interpreter.ffi().types.get(&param_type_name) {
-                Some(type_info) => type_info,
-                None => {
-                    return script_error(
-                        interpreter,
-                        format!("Unknown ffi type name {}.", param_type_name),
-                    );
-                }
-            };
+    let size = (type_info.base_size)(1);
+    let buffer: BufferPtr = ByteBuffer::new_ptr(size);
+    let extra: BufferPtr = ByteBuffer::new_ptr(0);
 
-            arg_type_infos.push(type_info.clone());
-        }
+    (type_info.conversion_from)(interpreter, &value, 1, &buffer, &extra)?;
+
+    let bytes = buffer.borrow().get_bytes(0, size);
+
+    unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), address as *mut u8, size) };
+
+    Ok(())
+}
+
+/// Wrap native memory in a byte buffer, with an optional function to free it with.
+fn wrap_native_memory(
+    interpreter: &mut dyn Interpreter,
+    free: Option<NativeFree>,
+) -> error::Result<()> {
+    let size = interpreter.pop_as_usize()?;
+    let address = pop_address(interpreter)?;
+
+    if address.is_null() {
+        return script_error_str(interpreter, "Can not wrap a null address in a buffer.");
+    }
+
+    interpreter.push(NativeBuffer::new_ptr(address, size, free).to_value());
+
+    Ok(())
+}
+
+/// Wrap native memory in a byte buffer without taking ownership.  The memory must stay valid for as
+/// long as the buffer is used.
+///
+/// Signature: `address size -- buffer`
+fn word_ffi_buffer_borrow(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    wrap_native_memory(interpreter, None)
+}
+
+/// Wrap native memory in a byte buffer that owns it.  The memory is freed with the C library's free
+/// once the buffer is no longer referenced.
+///
+/// Signature: `address size -- buffer`
+fn word_ffi_buffer_own(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    unsafe extern "C" fn free_memory(address: *mut c_void) {
+        unsafe { libc::free(address) };
+    }
+
+    wrap_native_memory(interpreter, Some(free_memory))
+}
+
+/// Wrap native memory in a byte buffer that owns it.  The memory is released by calling the given
+/// native function, which takes the address as it's only parameter, once the buffer is no longer
+/// referenced.
+///
+/// Signature: `address size free-fn -- buffer`
+fn word_ffi_buffer_own_with(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let free = pop_address(interpreter)?;
 
-        arg_type_infos
+    if free.is_null() {
+        return script_error_str(interpreter, "The free function can not be null.");
+    }
+
+    let free = unsafe { std::mem::transmute::<*mut c_void, NativeFree>(free) };
+
+    wrap_native_memory(interpreter, Some(free))
+}
+
+/// Get the address of a buffer's data, for use with ffi.peek, ffi.poke, or raw pointer parameters.
+///
+/// Signature: `buffer -- address`
+fn word_ffi_buffer_address(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let address = buffer.borrow_mut().byte_ptr_mut() as i64;
+
+    interpreter.push(address.to_value());
+
+    Ok(())
+}
+
+/// Look up the address of a symbol in a loaded library, such as a function to pass as a function
+/// pointer.
+///
+/// Signature: `symbol-name lib-name -- address`
+fn word_ffi_sym(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let lib_name = interpreter.pop_as_string()?;
+    let symbol_name = interpreter.pop_as_string()?;
+
+    let lib = match interpreter.ffi().libs.get(&lib_name) {
+        Some(lib) => lib.clone(),
+        None => return script_error(interpreter, format!("Library {} is not loaded.", lib_name)),
     };
 
-    // Get the type information for the return value.
-    let ret_type_info = match interpreter.ffi().types.get(&ret_type_name) {
-        Some(ret_type_info) => ret_type_info.clone(),
-        None => {
+    let lib = lib.borrow();
+    let address = match unsafe { lib.get::<*mut c_void>(symbol_name.as_bytes()) } {
+        Ok(symbol) => *symbol as i64,
+        Err(error) => {
             return script_error(
                 interpreter,
-                format!("Unknown ffi type name {}.", ret_type_name),
+                format!(
+                    "Failed to get symbol {} from library {}: {}.",
+                    symbol_name, lib_name, error
+                ),
             );
         }
     };
 
-    // Create the signature for the word's description.
-    let arg_signature = {
-        let mut signature = String::new();
+    interpreter.push(address.to_value());
 
-        if !arg_type_infos.is_empty() {
-            for arg_type in arg_type_infos.iter() {
-                signature.push_str(&arg_type.borrow().name);
-                signature.push(' ');
-            }
+    Ok(())
+}
 
-            signature.push_str("-- ");
-        } else {
-            signature = " -- ".to_string();
-        }
+/// Get the value of errno, (or GetLastError on Windows,) as it was immediately after the last
+/// foreign function call.
+///
+/// Signature: ` -- errno`
+fn word_ffi_errno(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let errno = interpreter.ffi().errno as i64;
 
-        signature.push_str(&ret_type_name);
+    interpreter.push(errno.to_value());
 
-        signature
-    };
+    Ok(())
+}
 
-    // Create the word handler for the foreign function, then add the new word to the interpreter.
-    let word = FfiWord::new(
-        lib,
-        lib_name.clone(),
-        fn_name.clone(),
-        arg_type_infos,
-        ret_type_info,
-    );
+// Generate the source that binds a library using the declarations in a C header.  The source is
+// returned along with a list of the declarations that couldn't be mapped.
+fn word_ffi_header_generate(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let header_path = interpreter.pop_as_string()?;
+    let lib_path = interpreter.pop_as_string()?;
 
-    interpreter.add_word(
-        location.path().clone(),
-        location.line(),
-        location.column(),
-        fn_alias,
-        Rc::new(word),
-        format!("Call native function {} in library {}.", fn_name, lib_name),
-        arg_signature,
-        WordRuntime::Normal,
-        WordVisibility::Visible,
-        WordType::Native,
-    );
+    let (_, bindings) = read_header(interpreter, &lib_path, &header_path)?;
+
+    interpreter.push(bindings.source.to_value());
+    interpreter.push(Value::from(bindings.unmapped.iter().collect::<Vec<_>>()));
 
     Ok(())
 }
 
-// Create a new structure compatible with the ffi interface.
-fn word_ffi_struct(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    let found_initializers = interpreter.pop_as_bool()?;
-    let _is_hidden = interpreter.pop_as_bool()?;
-    let _type_names = interpreter.pop_as_array()?;
-    let raw_field_names = interpreter.pop_as_array()?;
-    let name = interpreter.pop_as_token()?;
+// Bind a library at compile time using the declarations in a C header.  Declarations that couldn't
+// be mapped are reported as warnings.
+fn word_ffi_bind_header(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let lib_path = interpreter.next_token_text()?;
+    let header_path = interpreter.next_token_text()?;
 
-    // Get the location of the struct definition from the name token's location.  Then convert the
-    // name token to a string.
-    let _location = name.location();
-    let _name = name.text(interpreter)?;
+    let (full_path, bindings) = read_header(interpreter, &lib_path, &header_path)?;
 
-    // Get an array of default values if they were found.  Otherwise use the default value of none.
-    let _defaults = if found_initializers {
-        interpreter.pop_as_array()?
-    } else {
-        ValueVec::new(raw_field_names.borrow().len())
-    };
+    for message in &bindings.unmapped {
+        eprintln!("Warning: {}: could not map {}.", header_path, message);
+    }
 
-    Ok(())
+    interpreter.process_source(&full_path, &bindings.source)
 }
 
-// Register a new ffi array type for an existing ffi type.
-fn word_ffi_array(_interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    Ok(())
+/// Find a C header in the interpreter's search paths and generate it's bindings.
+fn read_header(
+    interpreter: &mut dyn Interpreter,
+    lib_path: &str,
+    header_path: &str,
+) -> error::Result<(String, HeaderBindings)> {
+    let full_path = interpreter.find_file(header_path)?;
+
+    match bind_header_file(lib_path, &full_path) {
+        Ok(bindings) => Ok((full_path, bindings)),
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not read header {}: {}.", header_path, error),
+        ),
+    }
 }
 
 // Register the ffi words with the interpreter.
@@ -969,7 +3130,7 @@ pub fn register_ffi_words(interpreter: &mut dyn Interpreter) {
         "ffi.fn",
         word_ffi_fn,
         "Bind to an external function.",
-        "lib-name fn-name fn-alias fn-params ret-name -- "
+        "lib-name fn-name fn-alias abi-name fn-params ret-name -- "
     );
 
     add_native_word!(
@@ -977,7 +3138,127 @@ pub fn register_ffi_words(interpreter: &mut dyn Interpreter) {
         "ffi.#",
         word_ffi_struct,
         "Create a structure compatible with the ffi interface.",
-        "found_initializers is_hidden types fields packing name [defaults] -- "
+        "[defaults] name packing fields types is_hidden found_initializers -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.callback",
+        word_ffi_callback,
+        "Wrap a word as a native function pointer.",
+        "word-name abi-name param-types ret-name -- callback"
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.callback.free",
+        word_ffi_callback_free,
+        "Free a callback created with ffi.callback.",
+        "callback -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.null",
+        word_ffi_null,
+        "The null address.",
+        " -- address"
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.malloc",
+        word_ffi_malloc,
+        "Allocate native memory with the C library's malloc.",
+        "size -- address"
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.free",
+        word_ffi_free,
+        "Free native memory with the C library's free.",
+        "address -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.peek",
+        word_ffi_peek,
+        "Read a value of the given ffi type from native memory.",
+        "address type-name -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.poke",
+        word_ffi_poke,
+        "Write a value as the given ffi type into native memory.",
+        "value address type-name -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.buffer.borrow",
+        word_ffi_buffer_borrow,
+        "Wrap native memory in a buffer, the memory must outlive the buffer.",
+        "address size -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.buffer.own",
+        word_ffi_buffer_own,
+        "Wrap native memory in a buffer that frees it with the C library's free.",
+        "address size -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.buffer.own-with",
+        word_ffi_buffer_own_with,
+        "Wrap native memory in a buffer that frees it with the given native function.",
+        "address size free-fn -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.buffer.address",
+        word_ffi_buffer_address,
+        "Get the native address of a buffer's data.",
+        "buffer -- address"
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.sym",
+        word_ffi_sym,
+        "Get the address of a symbol in a loaded library.",
+        "symbol-name lib-name -- address"
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.errno@",
+        word_ffi_errno,
+        "Get errno as it was just after the last foreign function call.",
+        " -- errno"
+    );
+
+    add_native_word!(
+        interpreter,
+        "ffi.header.generate",
+        word_ffi_header_generate,
+        "Generate the source binding a library from a C header, and the declarations not mapped.",
+        "lib-path header-path -- source unmapped"
+    );
+
+    add_native_immediate_word!(
+        interpreter,
+        "ffi.bind-header",
+        word_ffi_bind_header,
+        "Bind a library's functions, structures and constants from a C header.",
+        "ffi.bind-header lib-path header-path"
     );
 
     add_native_word!(
@@ -985,6 +3266,6 @@ pub fn register_ffi_words(interpreter: &mut dyn Interpreter) {
         "ffi.[]",
         word_ffi_array,
         "Register a new ffi array type for the existing ffi type.",
-        "struct-name -- "
+        "type-name -- "
     );
 }
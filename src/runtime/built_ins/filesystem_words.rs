@@ -0,0 +1,678 @@
+use crate::{
+    add_native_word, location_here,
+    runtime::{
+        data_structures::{
+            value::{ToValue, Value},
+            value_hash::ValueHash,
+            value_vec::ValueVec,
+        },
+        error::{self, script_error},
+        interpreter::Interpreter,
+    },
+};
+use std::{
+    env::{current_dir, set_current_dir},
+    fs::{self, Metadata, canonicalize},
+    io,
+    path::{Component, Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+/// Describe a file system entry as a hash table with it's name, path, type, size, modification
+/// time in seconds since the epoch, and whether it is read only.  Symbolic links are described
+/// as links, not as what they point to.
+fn entry_to_value(path: &Path, metadata: &Metadata) -> Value {
+    let file_type = metadata.file_type();
+    let type_name = if file_type.is_symlink() {
+        "symlink"
+    } else if file_type.is_dir() {
+        "dir"
+    } else if file_type.is_file() {
+        "file"
+    } else {
+        "other"
+    };
+
+    let mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0);
+
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let hash = ValueHash::new();
+
+    {
+        let mut hash = hash.borrow_mut();
+
+        hash.insert("name".to_string().to_value(), name.to_value());
+        hash.insert(
+            "path".to_string().to_value(),
+            path_to_string(path).to_value(),
+        );
+        hash.insert(
+            "type".to_string().to_value(),
+            type_name.to_string().to_value(),
+        );
+        hash.insert("size".to_string().to_value(), metadata.len().to_value());
+        hash.insert("mtime".to_string().to_value(), mtime.to_value());
+        hash.insert(
+            "readonly".to_string().to_value(),
+            metadata.permissions().readonly().to_value(),
+        );
+    }
+
+    hash.to_value()
+}
+
+fn path_to_string(path: &Path) -> String {
+    path.display().to_string()
+}
+
+/// Get the entries of a directory, sorted by name so that listings are the same on every
+/// platform.
+fn sorted_entries(path: &Path) -> io::Result<Vec<(PathBuf, Metadata)>> {
+    // An empty path is the current directory, but read_dir doesn't accept it.
+    let read_path = match path.as_os_str().is_empty() {
+        true => Path::new("."),
+        false => path,
+    };
+
+    let mut entries = Vec::new();
+
+    for entry in fs::read_dir(read_path)? {
+        let entry = entry?;
+
+        entries.push((path.join(entry.file_name()), entry.metadata()?));
+    }
+
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(entries)
+}
+
+/// Run a file system operation, reporting it's failure as a script error.
+fn check<T>(
+    interpreter: &mut dyn Interpreter,
+    result: io::Result<T>,
+    action: &str,
+    path: &str,
+) -> error::Result<T> {
+    match result {
+        Ok(value) => Ok(value),
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not {} {}: {}.", action, path, error),
+        ),
+    }
+}
+
+/// List the entries of a directory as an array of hash tables, sorted by name.  Each entry has
+/// the keys name, path, type, size, mtime and readonly.
+///
+/// Signature: `path -- entries`
+fn word_dir_list(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let path = interpreter.pop_as_string()?;
+    let entries = check(
+        interpreter,
+        sorted_entries(Path::new(&path)),
+        "list directory",
+        &path,
+    )?;
+
+    let values = entries
+        .iter()
+        .map(|(path, metadata)| entry_to_value(path, metadata))
+        .collect();
+
+    interpreter.push(ValueVec::from_vec(values).to_value());
+
+    Ok(())
+}
+
+/// Create a directory along with any of it's parents that don't exist yet.
+///
+/// Signature: `path -- `
+fn word_dir_create(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let path = interpreter.pop_as_string()?;
+    let result = fs::create_dir_all(&path);
+
+    check(interpreter, result, "create directory", &path)
+}
+
+/// Remove a directory and everything in it.  Symbolic links inside the directory are removed,
+/// not followed.
+///
+/// Signature: `path -- `
+fn word_dir_remove(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let path = interpreter.pop_as_string()?;
+    let result = fs::remove_dir_all(&path);
+
+    check(interpreter, result, "remove directory", &path)
+}
+
+/// Visit every entry under a directory, depth first and in name order, calling a word with each
+/// entry's hash table.  Directories are visited before their contents and symbolic links to
+/// directories are not followed.
+///
+/// Signature: `path word -- `
+fn word_dir_walk(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let word = interpreter.pop()?;
+    let path = interpreter.pop_as_string()?;
+
+    if !word.is_numeric() && !word.is_stringable() {
+        return script_error(
+            interpreter,
+            format!("Value {} is not a valid word name or index.", word),
+        );
+    }
+
+    walk_directory(interpreter, Path::new(&path), &word)
+}
+
+fn walk_directory(
+    interpreter: &mut dyn Interpreter,
+    path: &Path,
+    word: &Value,
+) -> error::Result<()> {
+    let entries = check(
+        interpreter,
+        sorted_entries(path),
+        "walk directory",
+        &path_to_string(path),
+    )?;
+
+    for (entry_path, metadata) in entries {
+        interpreter.push(entry_to_value(&entry_path, &metadata));
+
+        if word.is_numeric() {
+            interpreter.execute_word_index(&location_here!(), word.get_int_val() as usize)?;
+        } else {
+            interpreter.execute_word_named(&location_here!(), &word.get_string_val())?;
+        }
+
+        if metadata.is_dir() {
+            walk_directory(interpreter, &entry_path, word)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Rename or move a file or directory.
+///
+/// Signature: `from to -- `
+fn word_file_rename(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let to = interpreter.pop_as_string()?;
+    let from = interpreter.pop_as_string()?;
+    let result = fs::rename(&from, &to);
+
+    check(interpreter, result, "rename", &from)
+}
+
+/// Copy a file's contents and permissions to a new file, replacing it if it exists.
+///
+/// Signature: `from to -- `
+fn word_file_copy(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let to = interpreter.pop_as_string()?;
+    let from = interpreter.pop_as_string()?;
+    let result = fs::copy(&from, &to);
+
+    check(interpreter, result, "copy", &from)?;
+
+    Ok(())
+}
+
+/// Get the same hash table that dir.list gives for an entry, for a single path.
+///
+/// Signature: `path -- entry`
+fn word_file_stat(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let path = interpreter.pop_as_string()?;
+    let metadata = check(interpreter, fs::symlink_metadata(&path), "stat", &path)?;
+
+    interpreter.push(entry_to_value(Path::new(&path), &metadata));
+
+    Ok(())
+}
+
+/// Match a single path component against a pattern component.  A * matches any run of
+/// characters, a ? matches one character and [abc] or [a-z] match one character from a set.
+/// Hidden names that start with a . are only matched by patterns that also start with one.
+fn glob_match_name(pattern: &[char], name: &[char]) -> bool {
+    if name.first() == Some(&'.') && pattern.first() != Some(&'.') {
+        return false;
+    }
+
+    glob_match_chars(pattern, name)
+}
+
+fn glob_match_chars(pattern: &[char], name: &[char]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+
+        Some('*') => (0..=name.len()).any(|skip| glob_match_chars(&pattern[1..], &name[skip..])),
+
+        Some('?') => !name.is_empty() && glob_match_chars(&pattern[1..], &name[1..]),
+
+        Some('[') => {
+            let Some(close) = pattern.iter().skip(2).position(|c| *c == ']') else {
+                return name.first() == Some(&'[') && glob_match_chars(&pattern[1..], &name[1..]);
+            };
+            let set = &pattern[1..close + 2];
+
+            match name.first() {
+                Some(c) if glob_set_contains(set, *c) => {
+                    glob_match_chars(&pattern[close + 3..], &name[1..])
+                }
+                _ => false,
+            }
+        }
+
+        Some(c) => name.first() == Some(c) && glob_match_chars(&pattern[1..], &name[1..]),
+    }
+}
+
+/// Check a [] character set, which can be negated with a leading ! and can hold ranges.
+fn glob_set_contains(set: &[char], c: char) -> bool {
+    let (negated, set) = match set.first() {
+        Some('!') => (true, &set[1..]),
+        _ => (false, set),
+    };
+
+    let mut found = false;
+    let mut index = 0;
+
+    while index < set.len() {
+        if index + 2 < set.len() && set[index + 1] == '-' {
+            found |= set[index] <= c && c <= set[index + 2];
+            index += 3;
+        } else {
+            found |= set[index] == c;
+            index += 1;
+        }
+    }
+
+    found != negated
+}
+
+/// Collect the paths that match the remaining pattern components, starting from a directory.  A
+/// ** component matches any number of directories, including none.
+fn glob_walk(path: &Path, components: &[Vec<char>], results: &mut Vec<PathBuf>) {
+    let Some(component) = components.first() else {
+        results.push(path.to_path_buf());
+        return;
+    };
+
+    let rest = &components[1..];
+
+    if component.iter().collect::<String>() == "**" {
+        glob_walk(path, rest, results);
+
+        for (entry_path, metadata) in sorted_entries(path).unwrap_or_default() {
+            let name: Vec<char> = entry_path
+                .file_name()
+                .map(|name| name.to_string_lossy().chars().collect())
+                .unwrap_or_default();
+
+            if metadata.is_dir() && name.first() != Some(&'.') {
+                glob_walk(&entry_path, components, results);
+            }
+        }
+
+        return;
+    }
+
+    if !component.iter().any(|c| matches!(c, '*' | '?' | '[')) {
+        let next = path.join(component.iter().collect::<String>());
+
+        if fs::symlink_metadata(&next).is_ok() {
+            glob_walk(&next, rest, results);
+        }
+
+        return;
+    }
+
+    for (entry_path, metadata) in sorted_entries(path).unwrap_or_default() {
+        let name: Vec<char> = entry_path
+            .file_name()
+            .map(|name| name.to_string_lossy().chars().collect())
+            .unwrap_or_default();
+
+        if glob_match_name(component, &name) && (rest.is_empty() || metadata.is_dir()) {
+            glob_walk(&entry_path, rest, results);
+        }
+    }
+}
+
+/// Find the paths that match a glob pattern such as `src/**/*.rs`.  The results are sorted.
+///
+/// Signature: `pattern -- paths`
+fn word_file_glob(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let pattern = interpreter.pop_as_string()?;
+    let pattern_path = Path::new(&pattern);
+
+    // Split off any root so that absolute patterns are searched from the root.
+    let mut start = PathBuf::new();
+    let mut components = Vec::new();
+
+    for component in pattern_path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => start.push(component.as_os_str()),
+            Component::CurDir if components.is_empty() => start.push("."),
+            _ => components.push(component.as_os_str().to_string_lossy().chars().collect()),
+        }
+    }
+
+    let mut results = Vec::new();
+
+    glob_walk(&start, &components, &mut results);
+
+    results.sort();
+    results.dedup();
+
+    let values = results
+        .iter()
+        .map(|path| path_to_string(path).to_value())
+        .collect();
+
+    interpreter.push(ValueVec::from_vec(values).to_value());
+
+    Ok(())
+}
+
+/// Join two paths with the platform's separator.  If the second path is absolute it replaces the
+/// first.
+///
+/// Signature: `path other -- joined`
+fn word_path_join(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let other = interpreter.pop_as_string()?;
+    let path = interpreter.pop_as_string()?;
+
+    interpreter.push(path_to_string(&Path::new(&path).join(other)).to_value());
+
+    Ok(())
+}
+
+/// Get the path's parent directory, or an empty string if it doesn't have one.
+///
+/// Signature: `path -- parent`
+fn word_path_parent(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let path = interpreter.pop_as_string()?;
+    let parent = Path::new(&path)
+        .parent()
+        .map(path_to_string)
+        .unwrap_or_default();
+
+    interpreter.push(parent.to_value());
+
+    Ok(())
+}
+
+/// Get the last component of the path, or an empty string if it ends with `..`.
+///
+/// Signature: `path -- name`
+fn word_path_file_name(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let path = interpreter.pop_as_string()?;
+    let name = Path::new(&path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    interpreter.push(name.to_value());
+
+    Ok(())
+}
+
+/// Get the path's extension without the dot, or an empty string if it doesn't have one.
+///
+/// Signature: `path -- extension`
+fn word_path_extension(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let path = interpreter.pop_as_string()?;
+    let extension = Path::new(&path)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    interpreter.push(extension.to_value());
+
+    Ok(())
+}
+
+/// Get the absolute path with all links, `.` and `..` components resolved.  The path must exist.
+///
+/// Signature: `path -- canonical-path`
+fn word_path_canonical(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let path = interpreter.pop_as_string()?;
+    let canonical = check(interpreter, canonicalize(&path), "resolve", &path)?;
+
+    interpreter.push(path_to_string(&canonical).to_value());
+
+    Ok(())
+}
+
+/// Make a path absolute and drop it's `.` and `..` components without touching the file system,
+/// so paths that don't exist yet can still be compared.
+fn absolute_path(path: &Path) -> io::Result<PathBuf> {
+    let joined = current_dir()?.join(path);
+    let mut absolute = PathBuf::new();
+
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                absolute.pop();
+            }
+            _ => absolute.push(component.as_os_str()),
+        }
+    }
+
+    Ok(absolute)
+}
+
+/// Get the path to reach the first path from the second, base, path.
+fn relative_path(path: &Path, base: &Path) -> io::Result<PathBuf> {
+    let path = absolute_path(path)?;
+    let base = absolute_path(base)?;
+
+    let path_components: Vec<Component> = path.components().collect();
+    let base_components: Vec<Component> = base.components().collect();
+
+    if path_components.first() != base_components.first() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the paths do not share a root",
+        ));
+    }
+
+    let common = path_components
+        .iter()
+        .zip(base_components.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+
+    for _ in common..base_components.len() {
+        relative.push("..");
+    }
+
+    for component in &path_components[common..] {
+        relative.push(component.as_os_str());
+    }
+
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+
+    Ok(relative)
+}
+
+/// Get the path relative to a base directory.  Neither path has to exist.
+///
+/// Signature: `path base -- relative-path`
+fn word_path_relative(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let base = interpreter.pop_as_string()?;
+    let path = interpreter.pop_as_string()?;
+    let relative = relative_path(Path::new(&path), Path::new(&base));
+    let relative = check(interpreter, relative, "make relative path for", &path)?;
+
+    interpreter.push(path_to_string(&relative).to_value());
+
+    Ok(())
+}
+
+/// Get the current working directory.
+///
+/// Signature: ` -- path`
+fn word_cwd_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let cwd = check(interpreter, current_dir(), "get", "the working directory")?;
+
+    interpreter.push(path_to_string(&cwd).to_value());
+
+    Ok(())
+}
+
+/// Change the current working directory.  This is shared by the whole process.
+///
+/// Signature: `path -- `
+fn word_cwd_write(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let path = interpreter.pop_as_string()?;
+    let result = set_current_dir(&path);
+
+    check(interpreter, result, "change directory to", &path)
+}
+
+pub fn register_filesystem_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "dir.list",
+        word_dir_list,
+        "List a directory's entries as hash tables, sorted by name.",
+        "path -- entries"
+    );
+
+    add_native_word!(
+        interpreter,
+        "dir.create",
+        word_dir_create,
+        "Create a directory and any missing parents.",
+        "path -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "dir.remove",
+        word_dir_remove,
+        "Remove a directory and everything in it.",
+        "path -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "dir.walk",
+        word_dir_walk,
+        "Call a word with the hash table of every entry under a directory.",
+        "path word -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.rename",
+        word_file_rename,
+        "Rename or move a file or directory.",
+        "from to -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.copy",
+        word_file_copy,
+        "Copy a file, replacing the destination if it exists.",
+        "from to -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.stat",
+        word_file_stat,
+        "Get a hash table describing a file or directory.",
+        "path -- entry"
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.glob",
+        word_file_glob,
+        "Find the paths that match a pattern, ** matches any number of directories.",
+        "pattern -- paths"
+    );
+
+    add_native_word!(
+        interpreter,
+        "path.join",
+        word_path_join,
+        "Join two paths with the platform's separator.",
+        "path other -- joined"
+    );
+
+    add_native_word!(
+        interpreter,
+        "path.parent",
+        word_path_parent,
+        "Get a path's parent directory.",
+        "path -- parent"
+    );
+
+    add_native_word!(
+        interpreter,
+        "path.file-name",
+        word_path_file_name,
+        "Get the last component of a path.",
+        "path -- name"
+    );
+
+    add_native_word!(
+        interpreter,
+        "path.extension",
+        word_path_extension,
+        "Get a path's extension without the dot.",
+        "path -- extension"
+    );
+
+    add_native_word!(
+        interpreter,
+        "path.canonical",
+        word_path_canonical,
+        "Resolve an existing path to an absolute path without links.",
+        "path -- canonical-path"
+    );
+
+    add_native_word!(
+        interpreter,
+        "path.relative",
+        word_path_relative,
+        "Get a path relative to a base directory.",
+        "path base -- relative-path"
+    );
+
+    add_native_word!(
+        interpreter,
+        "cwd@",
+        word_cwd_read,
+        "Get the current working directory.",
+        " -- path"
+    );
+
+    add_native_word!(
+        interpreter,
+        "cwd!",
+        word_cwd_write,
+        "Change the current working directory.",
+        "path -- "
+    );
+}
@@ -0,0 +1,818 @@
+use crate::{
+    add_native_word, location_here,
+    runtime::{
+        built_ins::io_words::{RawIpcListener, RawIpcStream, clone_listener},
+        data_structures::{
+            byte_buffer::ByteBuffer,
+            value::{ToValue, Value},
+            value_hash::ValueHash,
+        },
+        error::{self, script_error},
+        interpreter::Interpreter,
+    },
+};
+use std::{
+    io::{self, BufRead, BufReader, ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+/// How long to wait to connect, or for the other end to send or accept more data, before giving
+/// up on the connection.
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The longest a single start, header or chunk size line may be.
+const MAX_LINE_SIZE: usize = 8 * 1024;
+
+/// The most bytes allowed in the start line and headers of a message, together.
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+
+/// The start line and headers of a request or response, everything before it's body.  Header
+/// names are lower-cased so that they can be looked up no matter how the other end spelled them.
+struct MessageHead {
+    start_line: String,
+    headers: Vec<(String, String)>,
+}
+
+impl MessageHead {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn is_chunked(&self) -> bool {
+        self.header("transfer-encoding").is_some_and(|encoding| {
+            encoding
+                .split(',')
+                .any(|coding| coding.trim().eq_ignore_ascii_case("chunked"))
+        })
+    }
+
+    fn content_length(&self) -> io::Result<Option<u64>> {
+        self.header("content-length")
+            .map(|length| {
+                length
+                    .parse()
+                    .map_err(|_| invalid(format!("bad content-length '{}'", length)))
+            })
+            .transpose()
+    }
+
+    /// Convert the headers to a hash table.  Repeated headers are joined with commas, as HTTP
+    /// allows.
+    fn headers_to_value(&self) -> Value {
+        let hash = ValueHash::new();
+
+        for (name, value) in &self.headers {
+            let key = name.clone().to_value();
+            let joined = match hash.borrow().get(&key) {
+                Some(existing) => format!("{}, {}", existing.get_string_val(), value),
+                None => value.clone(),
+            };
+
+            hash.borrow_mut().insert(key, joined.to_value());
+        }
+
+        hash.to_value()
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+/// Read a line without it's line ending, either CRLF or a bare LF.  None is returned if the other
+/// end closed the connection.
+/// Lines longer than MAX_LINE_SIZE are an error.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = Vec::new();
+    let read = reader
+        .take(MAX_LINE_SIZE as u64 + 1)
+        .read_until(b'\n', &mut line)?;
+
+    if read == 0 {
+        return Ok(None);
+    }
+
+    if line.len() > MAX_LINE_SIZE {
+        return Err(invalid(format!(
+            "line is longer than {} bytes",
+            MAX_LINE_SIZE
+        )));
+    }
+
+    while line
+        .last()
+        .is_some_and(|byte| *byte == b'\n' || *byte == b'\r')
+    {
+        line.pop();
+    }
+
+    Ok(Some(String::from_utf8_lossy(&line).to_string()))
+}
+
+/// Read the start line and headers of a message.  None is returned if the connection was closed
+/// before anything was sent.
+fn read_head(reader: &mut impl BufRead) -> io::Result<Option<MessageHead>> {
+    let Some(start_line) = read_line(reader)? else {
+        return Ok(None);
+    };
+
+    let mut headers = Vec::new();
+    let mut head_size = start_line.len();
+
+    loop {
+        let Some(line) = read_line(reader)? else {
+            return Err(invalid("the connection closed in the headers".to_string()));
+        };
+
+        head_size += line.len();
+
+        if head_size > MAX_HEAD_SIZE {
+            return Err(invalid(format!(
+                "headers are longer than {} bytes",
+                MAX_HEAD_SIZE
+            )));
+        }
+
+        if line.is_empty() {
+            break;
+        }
+
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(invalid(format!("malformed header line '{}'", line)));
+        };
+
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+    }
+
+    Ok(Some(MessageHead {
+        start_line,
+        headers,
+    }))
+}
+
+/// Read exactly length bytes, failing if the connection closes first.
+fn read_exact_body(reader: &mut impl BufRead, length: u64) -> io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+
+    reader.take(length).read_to_end(&mut body)?;
+
+    match body.len() as u64 == length {
+        true => Ok(body),
+        false => Err(invalid(
+            "the connection closed before the end of the body".to_string(),
+        )),
+    }
+}
+
+/// Read a body sent with chunked transfer encoding.  Chunk extensions and trailers are skipped.
+fn read_chunked_body(reader: &mut impl BufRead) -> io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+
+    loop {
+        let Some(line) = read_line(reader)? else {
+            return Err(invalid("the connection closed in a chunk".to_string()));
+        };
+
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let Ok(size) = u64::from_str_radix(size, 16) else {
+            return Err(invalid(format!("bad chunk size '{}'", size)));
+        };
+
+        if size == 0 {
+            while let Some(trailer) = read_line(reader)?
+                && !trailer.is_empty()
+            {}
+
+            return Ok(body);
+        }
+
+        body.extend(read_exact_body(reader, size)?);
+        read_line(reader)?;
+    }
+}
+
+/// Read the body of a message by it's transfer encoding or length.  A response with neither runs
+/// until the connection closes, a request with neither has no body.
+fn read_body(
+    reader: &mut impl BufRead,
+    head: &MessageHead,
+    until_close: bool,
+) -> io::Result<Vec<u8>> {
+    if head.is_chunked() {
+        return read_chunked_body(reader);
+    }
+
+    match head.content_length()? {
+        Some(length) => read_exact_body(reader, length),
+
+        None if until_close => {
+            let mut body = Vec::new();
+
+            reader.read_to_end(&mut body)?;
+            Ok(body)
+        }
+
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Write the start line and headers of a message, along with the blank line that ends them.
+fn write_head(
+    stream: &mut impl Write,
+    start_line: &str,
+    headers: &[(String, String)],
+) -> io::Result<()> {
+    let mut head = format!("{}\r\n", start_line);
+
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())
+}
+
+/// Bodies that are valid UTF-8 become strings, anything else is kept as a buffer.
+fn body_to_value(body: Vec<u8>) -> Value {
+    match String::from_utf8(body) {
+        Ok(text) => text.to_value(),
+
+        Err(error) => {
+            let bytes = error.into_bytes();
+            let buffer = ByteBuffer::new_ptr(bytes.len());
+
+            buffer.borrow_mut().buffer_mut().copy_from_slice(&bytes);
+            buffer.to_value()
+        }
+    }
+}
+
+/// Get the bytes of a body from a string, the whole of a buffer, or none for no body.
+fn value_to_body(interpreter: &mut dyn Interpreter, value: &Value) -> error::Result<Vec<u8>> {
+    match value {
+        Value::None => Ok(Vec::new()),
+        Value::ByteBuffer(buffer) => Ok(buffer.borrow().get_bytes(0, buffer.borrow().len())),
+        value if value.is_stringable() => Ok(value.get_string_val().into_bytes()),
+        _ => script_error(
+            interpreter,
+            format!(
+                "An HTTP body must be a string, a buffer or none, found {}.",
+                value
+            ),
+        ),
+    }
+}
+
+/// Get headers from a hash table of names to values, or none for no headers.  Line breaks are
+/// refused so that a value can't sneak in headers of it's own.
+fn value_to_headers(
+    interpreter: &mut dyn Interpreter,
+    value: &Value,
+) -> error::Result<Vec<(String, String)>> {
+    let mut headers: Vec<(String, String)> = match value {
+        Value::None => Vec::new(),
+        Value::HashMap(hash) => hash
+            .borrow()
+            .iter()
+            .map(|(name, value)| (name.get_string_val(), value.get_string_val()))
+            .collect(),
+        _ => {
+            return script_error(
+                interpreter,
+                format!(
+                    "HTTP headers must be a hash table or none, found {}.",
+                    value
+                ),
+            );
+        }
+    };
+
+    if let Some((name, _)) = headers.iter().find(|(name, value)| {
+        name.is_empty() || name.contains([':', '\r', '\n']) || value.contains(['\r', '\n'])
+    }) {
+        return script_error(interpreter, format!("Invalid HTTP header '{}'.", name));
+    }
+
+    headers.sort();
+
+    Ok(headers)
+}
+
+fn has_header(headers: &[(String, String)], name: &str) -> bool {
+    headers
+        .iter()
+        .any(|(header, _)| header.eq_ignore_ascii_case(name))
+}
+
+/// Add a header unless the script already gave one with that name.
+fn add_default_header(headers: &mut Vec<(String, String)>, name: &str, value: &str) {
+    if !has_header(headers, name) {
+        headers.push((name.to_string(), value.to_string()));
+    }
+}
+
+/// A plain http:// URL split into the address to connect to, the value of the Host header, and
+/// the path and query to request.
+struct HttpUrl {
+    address: String,
+    host: String,
+    target: String,
+}
+
+/// Split a URL into it's parts.  Whitespace and control characters are refused, as they would let
+/// the URL add lines of it's own to the request.
+fn parse_url(url: &str) -> Result<HttpUrl, String> {
+    let Some(rest) = url.strip_prefix("http://") else {
+        return Err("only plain http:// URLs are supported".to_string());
+    };
+
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err("the URL can not contain spaces or control characters".to_string());
+    }
+
+    let rest = rest.split('#').next().unwrap_or_default();
+    let (host, target) = match rest.find(['/', '?']) {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, "/"),
+    };
+
+    if host.is_empty() || host.contains('@') {
+        return Err("the URL must have a host and no user name".to_string());
+    }
+
+    let has_port = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.contains("]:"),
+        None => host.contains(':'),
+    };
+
+    Ok(HttpUrl {
+        address: match has_port {
+            true => host.to_string(),
+            false => format!("{}:80", host),
+        },
+        host: host.to_string(),
+        target: match target.starts_with('?') {
+            true => format!("/{}", target),
+            false => target.to_string(),
+        },
+    })
+}
+
+/// Is the method a valid HTTP token, such as GET or POST?
+fn is_valid_method(method: &str) -> bool {
+    !method.is_empty()
+        && method
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+/// Split a status line into the status code and reason.
+fn parse_status_line(line: &str) -> io::Result<(i64, String)> {
+    let mut parts = line.splitn(3, ' ');
+    let version = parts.next().unwrap_or_default();
+    let status = parts.next().and_then(|status| status.parse().ok());
+
+    match status {
+        Some(status) if version.starts_with("HTTP/") => {
+            Ok((status, parts.next().unwrap_or_default().to_string()))
+        }
+        _ => Err(invalid(format!("malformed status line '{}'", line))),
+    }
+}
+
+/// Connect to the first of the address's resolved addresses that answers within the timeout.
+fn connect(address: &str) -> io::Result<TcpStream> {
+    let mut last_error = None;
+
+    for socket_address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket_address, IO_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(error) => last_error = Some(error),
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| {
+        io::Error::new(
+            ErrorKind::NotFound,
+            format!("no addresses found for {}", address),
+        )
+    }))
+}
+
+/// Connect, send the request and read the whole response into a hash table.
+fn send_request(
+    url: &HttpUrl,
+    method: &str,
+    headers: &[(String, String)],
+    body: &[u8],
+) -> io::Result<Value> {
+    let mut stream = connect(&url.address)?;
+
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+
+    write_head(
+        &mut stream,
+        &format!("{} {} HTTP/1.1", method, url.target),
+        headers,
+    )?;
+    stream.write_all(body)?;
+
+    let mut reader = BufReader::new(stream);
+
+    // Informational responses, such as 100 Continue, come before the real one.
+    let (head, status, reason) = loop {
+        let Some(head) = read_head(&mut reader)? else {
+            return Err(invalid(
+                "the server closed the connection without responding".to_string(),
+            ));
+        };
+
+        let (status, reason) = parse_status_line(&head.start_line)?;
+
+        if !(100..200).contains(&status) {
+            break (head, status, reason);
+        }
+    };
+
+    let body = match method.eq_ignore_ascii_case("HEAD") || status == 204 || status == 304 {
+        true => Vec::new(),
+        false => read_body(&mut reader, &head, true)?,
+    };
+
+    let response = ValueHash::new();
+
+    response
+        .borrow_mut()
+        .insert("status".to_string().to_value(), status.to_value());
+    response
+        .borrow_mut()
+        .insert("reason".to_string().to_value(), reason.to_value());
+    response
+        .borrow_mut()
+        .insert("headers".to_string().to_value(), head.headers_to_value());
+    response
+        .borrow_mut()
+        .insert("body".to_string().to_value(), body_to_value(body));
+
+    Ok(response.to_value())
+}
+
+/// Send a request to a plain http:// URL and wait for the whole response.  The headers are a hash
+/// table or none, and the body is a string, a buffer, or none.  The response is a hash table of
+/// the status, reason, headers and body.  Response header names are lower-cased and chunked
+/// bodies are decoded.  Bodies that aren't valid UTF-8 are returned as a buffer.
+///
+/// Signature: `method url headers body -- response`
+fn word_http_request(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let body = interpreter.pop()?;
+    let headers = interpreter.pop()?;
+    let url = interpreter.pop_as_string()?;
+    let method = interpreter.pop_as_string()?.to_ascii_uppercase();
+
+    if !is_valid_method(&method) {
+        return script_error(interpreter, format!("Invalid HTTP method '{}'.", method));
+    }
+
+    let body = value_to_body(interpreter, &body)?;
+    let mut headers = value_to_headers(interpreter, &headers)?;
+
+    let target = match parse_url(&url) {
+        Ok(target) => target,
+        Err(message) => {
+            return script_error(
+                interpreter,
+                format!("Could not request {}: {}.", url, message),
+            );
+        }
+    };
+
+    add_default_header(&mut headers, "Host", &target.host);
+    add_default_header(&mut headers, "Connection", "close");
+
+    if (!body.is_empty() || matches!(method.as_str(), "POST" | "PUT" | "PATCH"))
+        && !has_header(&headers, "Transfer-Encoding")
+    {
+        add_default_header(&mut headers, "Content-Length", &body.len().to_string());
+    }
+
+    match send_request(&target, &method, &headers, &body) {
+        Ok(response) => {
+            interpreter.push(response);
+            Ok(())
+        }
+
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not request {}: {}.", url, error),
+        ),
+    }
+}
+
+/// The reason phrases for the statuses that handlers are likely to use.
+fn reason_phrase(status: i64) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+/// What a handler asked to be sent back for a request.
+struct HttpResponse {
+    status: i64,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+    stop: bool,
+}
+
+impl HttpResponse {
+    fn text(status: i64, body: String) -> HttpResponse {
+        HttpResponse {
+            status,
+            headers: vec![(
+                "Content-Type".to_string(),
+                "text/plain; charset=utf-8".to_string(),
+            )],
+            body: body.into_bytes(),
+            stop: false,
+        }
+    }
+
+    /// Write the response and it's body, a response to a HEAD request only gets the headers.  The
+    /// length and connection headers are always set here, as every connection is closed after
+    /// it's response.
+    fn write(&self, stream: &mut impl Write, include_body: bool) -> io::Result<()> {
+        let mut headers: Vec<(String, String)> = self
+            .headers
+            .iter()
+            .filter(|(name, _)| {
+                !["content-length", "transfer-encoding", "connection"]
+                    .contains(&name.to_ascii_lowercase().as_str())
+            })
+            .cloned()
+            .collect();
+
+        headers.push(("Content-Length".to_string(), self.body.len().to_string()));
+        headers.push(("Connection".to_string(), "close".to_string()));
+
+        write_head(
+            stream,
+            &format!("HTTP/1.1 {} {}", self.status, reason_phrase(self.status)),
+            &headers,
+        )?;
+
+        if include_body {
+            stream.write_all(&self.body)?;
+        }
+
+        stream.flush()
+    }
+}
+
+/// Convert what a handler returned into a response.  A hash table can have a status, headers, body
+/// and stop, all optional.  A string is sent as a plain text body.
+fn value_to_response(
+    interpreter: &mut dyn Interpreter,
+    value: &Value,
+) -> error::Result<HttpResponse> {
+    let hash = match value {
+        Value::HashMap(hash) => hash,
+        Value::String(text) => return Ok(HttpResponse::text(200, text.clone())),
+        _ => {
+            return script_error(
+                interpreter,
+                format!(
+                    "An HTTP handler must return a response hash table or a string, found {}.",
+                    value
+                ),
+            );
+        }
+    };
+
+    let get = |key: &str| {
+        hash.borrow()
+            .get(&key.to_string().to_value())
+            .cloned()
+            .unwrap_or(Value::None)
+    };
+
+    let status = match get("status") {
+        Value::None => 200,
+        Value::Int(status) if (100..1000).contains(&status) => status,
+        status => {
+            return script_error(
+                interpreter,
+                format!("Invalid HTTP response status {}.", status),
+            );
+        }
+    };
+
+    Ok(HttpResponse {
+        status,
+        headers: value_to_headers(interpreter, &get("headers"))?,
+        body: value_to_body(interpreter, &get("body"))?,
+        stop: matches!(get("stop"), Value::Bool(true)),
+    })
+}
+
+/// Read a request and convert it into the hash table given to the handler.  Returns None if the
+/// client closed the connection without sending anything.
+fn read_request(
+    reader: &mut BufReader<RawIpcStream>,
+    peer: &str,
+) -> io::Result<Option<(String, Value)>> {
+    let Some(head) = read_head(reader)? else {
+        return Ok(None);
+    };
+
+    let mut parts = head.start_line.split(' ');
+    let (Some(method), Some(target), Some(version), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid(format!(
+            "malformed request line '{}'",
+            head.start_line
+        )));
+    };
+
+    if !version.starts_with("HTTP/1.") {
+        return Err(invalid(format!("unsupported version '{}'", version)));
+    }
+
+    if head
+        .header("expect")
+        .is_some_and(|expect| expect.eq_ignore_ascii_case("100-continue"))
+    {
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+    }
+
+    let body = read_body(reader, &head, false)?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let request = ValueHash::new();
+
+    for (key, value) in [
+        ("method", method.to_string().to_value()),
+        ("path", path.to_string().to_value()),
+        ("query", query.to_string().to_value()),
+        ("version", version.to_string().to_value()),
+        ("headers", head.headers_to_value()),
+        ("body", body_to_value(body)),
+        ("peer", peer.to_string().to_value()),
+    ] {
+        request
+            .borrow_mut()
+            .insert(key.to_string().to_value(), value);
+    }
+
+    Ok(Some((method.to_string(), request.to_value())))
+}
+
+fn call_handler(interpreter: &mut dyn Interpreter, handler: &Value) -> error::Result<()> {
+    if handler.is_numeric() {
+        interpreter.execute_word_index(&location_here!(), handler.get_int_val() as usize)
+    } else {
+        interpreter.execute_word_named(&location_here!(), &handler.get_string_val())
+    }
+}
+
+/// Answer one request on a new connection, then close it.  Returns true if the handler asked for
+/// the server to stop.  Errors writing the response only affect that client, so they're ignored.
+/// A client that stops sending or reading times out so that it can't hold up the server.
+fn serve_connection(
+    interpreter: &mut dyn Interpreter,
+    stream: RawIpcStream,
+    peer: &str,
+    handler: &Value,
+) -> error::Result<bool> {
+    // Named pipes don't support timeouts, so they're served without them.
+    let _ = stream.set_read_timeout(Some(IO_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(IO_TIMEOUT));
+
+    let mut reader = BufReader::new(stream);
+
+    let (method, request) = match read_request(&mut reader, peer) {
+        Ok(Some(request)) => request,
+        Ok(None) => return Ok(false),
+
+        // Clients that went quiet are dropped without an answer.
+        Err(error) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+            return Ok(false);
+        }
+
+        // Malformed requests are answered here, without bothering the handler.
+        Err(error) => {
+            let _ = HttpResponse::text(400, format!("{}\n", error)).write(reader.get_mut(), true);
+            return Ok(false);
+        }
+    };
+
+    interpreter.push(request);
+
+    // A handler that fails, or leaves nothing to answer with, still gets the client an answer.
+    let response = call_handler(interpreter, handler)
+        .and_then(|_| interpreter.pop())
+        .and_then(|value| value_to_response(interpreter, &value));
+
+    match response {
+        Ok(response) => {
+            let _ = response.write(reader.get_mut(), method != "HEAD");
+            Ok(response.stop)
+        }
+
+        // Let the client know something went wrong before the error is passed on to the script.
+        Err(error) => {
+            let _ = HttpResponse::text(500, "Internal Server Error\n".to_string())
+                .write(reader.get_mut(), method != "HEAD");
+            Err(error)
+        }
+    }
+}
+
+/// Serve HTTP requests, one connection at a time, calling the handler word for each request.  The
+/// address is a host:port to listen on, or the fd of a socket from socket.listen.  The handler is
+/// given a hash table of the request's method, path, query, version, headers, body and peer.  It
+/// returns a hash table with an optional status, headers and body, or a string to send as plain
+/// text.  Serving stops after sending a response that has stop set to true.
+///
+/// Signature: `address handler -- `
+fn word_http_serve(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let handler = interpreter.pop()?;
+    let address = interpreter.pop()?;
+
+    if !handler.is_numeric() && !handler.is_stringable() {
+        return script_error(
+            interpreter,
+            format!("Value {} is not a valid word name or index.", handler),
+        );
+    }
+
+    let listener = match address {
+        Value::Int(fd) => clone_listener(interpreter, fd)?.0,
+
+        _ => match TcpListener::bind(address.get_string_val()) {
+            Ok(listener) => RawIpcListener::Tcp(listener),
+            Err(error) => {
+                return script_error(
+                    interpreter,
+                    format!("Could not listen on {}: {}.", address, error),
+                );
+            }
+        },
+    };
+
+    loop {
+        let (stream, peer) = match listener.accept() {
+            Ok(connection) => connection,
+            Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+            Err(error) => {
+                return script_error(
+                    interpreter,
+                    format!("Could not accept connection: {}.", error),
+                );
+            }
+        };
+
+        if serve_connection(interpreter, stream, &peer, &handler)? {
+            return Ok(());
+        }
+    }
+}
+
+pub fn register_http_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "http.request",
+        word_http_request,
+        "Send an HTTP request to an http:// URL and return the response as a hash table.",
+        "method url headers body -- response"
+    );
+
+    add_native_word!(
+        interpreter,
+        "http.serve",
+        word_http_serve,
+        "Serve HTTP requests on an address or listening fd, calling a handler word for each.",
+        "address handler -- "
+    );
+}
@@ -33,24 +33,39 @@ impl Write for RawIpcStream {
 }
 
 use std::{
-    collections::HashMap,
-    fs::{File, OpenOptions, remove_file},
-    io::{BufRead, BufReader, BufWriter, Seek},
-    net::TcpStream,
-    path::Path,
+    collections::{HashMap, hash_map::RandomState},
+    env,
+    fs::{DirBuilder, File, OpenOptions, remove_dir_all, remove_file},
+    hash::{BuildHasher, Hasher},
+    io::{ErrorKind, Seek, SeekFrom},
+    mem::take,
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket},
+    path::{Path, PathBuf},
+    process::{self, ChildStderr, ChildStdin, ChildStdout, Stdio},
     sync::{
-        Mutex,
+        Arc, Mutex,
         atomic::{AtomicI64, Ordering},
     },
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 #[cfg(unix)]
-use std::os::unix::net::UnixStream;
+use std::os::unix::{
+    io::{AsFd, AsRawFd, RawFd},
+    net::{SocketAddr as UnixSocketAddr, UnixListener, UnixStream},
+};
+
+#[cfg(windows)]
+use std::os::windows::io::AsHandle;
 
 use crate::{
     add_native_word,
     runtime::{
-        data_structures::value::ToValue,
+        data_structures::{
+            byte_buffer::{BufferPtr, ByteBuffer},
+            value::{ToValue, Value},
+            value_vec::ValueVec,
+        },
         error::{self, script_error, script_error_str},
         interpreter::Interpreter,
     },
@@ -58,8 +73,7 @@ use crate::{
 use lazy_static::lazy_static;
 #[cfg(windows)]
 use named_pipe::PipeClient;
-#[cfg(unix)]
-use std::os::unix::net::UnixStream;
+use socket2::{Domain, Protocol, Socket, Type};
 
 pub enum RawIpcStream {
     #[cfg(unix)]
@@ -69,9 +83,564 @@ pub enum RawIpcStream {
     Tcp(TcpStream),
 }
 
+impl RawIpcStream {
+    /// Shut down the read half, write half, or both halves of the connection.
+    pub fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            RawIpcStream::Unix(s) => s.shutdown(how),
+            #[cfg(windows)]
+            RawIpcStream::NamedPipe(_) => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "named pipes can not be shut down",
+            )),
+            RawIpcStream::Tcp(s) => s.shutdown(how),
+        }
+    }
+
+    /// Set how long a read can block before failing, None blocks forever.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            RawIpcStream::Unix(s) => s.set_read_timeout(timeout),
+            #[cfg(windows)]
+            RawIpcStream::NamedPipe(_) => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "named pipes do not support timeouts",
+            )),
+            RawIpcStream::Tcp(s) => s.set_read_timeout(timeout),
+        }
+    }
+
+    /// Set how long a write can block before failing, None blocks forever.
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            RawIpcStream::Unix(s) => s.set_write_timeout(timeout),
+            #[cfg(windows)]
+            RawIpcStream::NamedPipe(_) => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "named pipes do not support timeouts",
+            )),
+            RawIpcStream::Tcp(s) => s.set_write_timeout(timeout),
+        }
+    }
+
+    /// Switch the stream between blocking and non-blocking reads and writes.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            RawIpcStream::Unix(s) => s.set_nonblocking(nonblocking),
+            #[cfg(windows)]
+            RawIpcStream::NamedPipe(_) => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "named pipes do not support non-blocking mode",
+            )),
+            RawIpcStream::Tcp(s) => s.set_nonblocking(nonblocking),
+        }
+    }
+
+    /// Get the address this end of the connection is bound to.
+    pub fn local_address(&self) -> io::Result<String> {
+        match self {
+            #[cfg(unix)]
+            RawIpcStream::Unix(s) => Ok(unix_address_string(&s.local_addr()?)),
+            #[cfg(windows)]
+            RawIpcStream::NamedPipe(_) => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "named pipes do not have addresses",
+            )),
+            RawIpcStream::Tcp(s) => Ok(s.local_addr()?.to_string()),
+        }
+    }
+}
+
+/// A socket listening for new connections, either on a TCP port or a Unix domain socket path.
+pub enum RawIpcListener {
+    #[cfg(unix)]
+    Unix(UnixListener),
+    Tcp(TcpListener),
+}
+
+impl RawIpcListener {
+    /// Wait for the next connection, returning the new stream and the address of the peer.
+    pub fn accept(&self) -> io::Result<(RawIpcStream, String)> {
+        match self {
+            #[cfg(unix)]
+            RawIpcListener::Unix(l) => {
+                let (stream, address) = l.accept()?;
+                Ok((RawIpcStream::Unix(stream), unix_address_string(&address)))
+            }
+            RawIpcListener::Tcp(l) => {
+                let (stream, address) = l.accept()?;
+                Ok((RawIpcStream::Tcp(stream), address.to_string()))
+            }
+        }
+    }
+
+    /// Create a new handle to the same listening socket.
+    pub fn try_clone(&self) -> io::Result<RawIpcListener> {
+        match self {
+            #[cfg(unix)]
+            RawIpcListener::Unix(l) => Ok(RawIpcListener::Unix(l.try_clone()?)),
+            RawIpcListener::Tcp(l) => Ok(RawIpcListener::Tcp(l.try_clone()?)),
+        }
+    }
+
+    /// Get the address the listener is bound to.
+    pub fn local_address(&self) -> io::Result<String> {
+        match self {
+            #[cfg(unix)]
+            RawIpcListener::Unix(l) => Ok(unix_address_string(&l.local_addr()?)),
+            RawIpcListener::Tcp(l) => Ok(l.local_addr()?.to_string()),
+        }
+    }
+
+    /// Switch accepting connections between blocking and non-blocking.
+    pub fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            RawIpcListener::Unix(l) => l.set_nonblocking(nonblocking),
+            RawIpcListener::Tcp(l) => l.set_nonblocking(nonblocking),
+        }
+    }
+}
+
+/// Unix domain socket addresses are their path, unnamed sockets are reported as an empty string.
+#[cfg(unix)]
+fn unix_address_string(address: &UnixSocketAddr) -> String {
+    address
+        .as_pathname()
+        .map(|path| path.display().to_string())
+        .unwrap_or_default()
+}
+
+/// Everything that can be referred to by an fd.  Listeners, UDP sockets and directories don't
+/// have a stream of bytes, so the plain reads and writes fail for them.
 enum FileObject {
     File(File),
+    Directory,
     Stream(RawIpcStream),
+    Listener {
+        listener: RawIpcListener,
+        nodelay: bool,
+    },
+    Udp(UdpSocket),
+    Pipe(ProcessPipe),
+
+    /// The fd was closed while another thread was still using it.
+    Closed,
+}
+
+/// One end of a pipe connected to a child process's standard input, output or error.
+pub enum ProcessPipe {
+    Input(ChildStdin),
+    Output(ChildStdout),
+    Error(ChildStderr),
+}
+
+impl ProcessPipe {
+    /// Duplicate the pipe's handle so that it can be given to another process, leaving this end
+    /// open until the other process has started.
+    fn try_clone_stdio(&self) -> io::Result<Stdio> {
+        #[cfg(unix)]
+        let handle = match self {
+            ProcessPipe::Input(input) => input.as_fd().try_clone_to_owned(),
+            ProcessPipe::Output(output) => output.as_fd().try_clone_to_owned(),
+            ProcessPipe::Error(error) => error.as_fd().try_clone_to_owned(),
+        };
+
+        #[cfg(windows)]
+        let handle = match self {
+            ProcessPipe::Input(input) => input.as_handle().try_clone_to_owned(),
+            ProcessPipe::Output(output) => output.as_handle().try_clone_to_owned(),
+            ProcessPipe::Error(error) => error.as_handle().try_clone_to_owned(),
+        };
+
+        handle.map(Stdio::from)
+    }
+}
+
+impl FileObject {
+    fn unsupported(&self, operation: &str) -> io::Error {
+        let kind = match self {
+            FileObject::Listener { .. } => "a listening socket, use socket.accept",
+            FileObject::Directory => "a directory",
+            FileObject::Closed => "a closed fd",
+            FileObject::Pipe(ProcessPipe::Input(_)) => "a process's input",
+            FileObject::Pipe(_) => "a process's output",
+            _ => "a UDP socket, use udp.send-to and udp.recv-from",
+        };
+
+        io::Error::new(
+            ErrorKind::Unsupported,
+            format!("can not {} {}", operation, kind),
+        )
+    }
+
+    /// Switch reads, writes and accepts between blocking and non-blocking.  Regular files never
+    /// block, so for them there is nothing to change.
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        match self {
+            FileObject::File(_) => Ok(()),
+            FileObject::Directory | FileObject::Closed => {
+                Err(self.unsupported("set non-blocking mode on"))
+            }
+            FileObject::Stream(stream) => stream.set_nonblocking(nonblocking),
+            FileObject::Listener { listener, .. } => listener.set_nonblocking(nonblocking),
+            FileObject::Udp(socket) => socket.set_nonblocking(nonblocking),
+
+            #[cfg(unix)]
+            FileObject::Pipe(_) => set_fd_nonblocking(self.raw_fd()?, nonblocking),
+
+            #[cfg(not(unix))]
+            FileObject::Pipe(_) => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "process pipes do not support non-blocking mode",
+            )),
+        }
+    }
+
+    /// Get the operating system's descriptor behind the object, for polling.
+    #[cfg(unix)]
+    fn raw_fd(&self) -> io::Result<RawFd> {
+        match self {
+            FileObject::File(file) => Ok(file.as_raw_fd()),
+            FileObject::Directory | FileObject::Closed => Err(self.unsupported("poll")),
+            FileObject::Stream(RawIpcStream::Unix(stream)) => Ok(stream.as_raw_fd()),
+            FileObject::Stream(RawIpcStream::Tcp(stream)) => Ok(stream.as_raw_fd()),
+            FileObject::Listener {
+                listener: RawIpcListener::Unix(listener),
+                ..
+            } => Ok(listener.as_raw_fd()),
+            FileObject::Listener {
+                listener: RawIpcListener::Tcp(listener),
+                ..
+            } => Ok(listener.as_raw_fd()),
+            FileObject::Udp(socket) => Ok(socket.as_raw_fd()),
+            FileObject::Pipe(ProcessPipe::Input(input)) => Ok(input.as_raw_fd()),
+            FileObject::Pipe(ProcessPipe::Output(output)) => Ok(output.as_raw_fd()),
+            FileObject::Pipe(ProcessPipe::Error(error)) => Ok(error.as_raw_fd()),
+        }
+    }
+}
+
+/// Set or clear O_NONBLOCK on a raw descriptor.
+#[cfg(unix)]
+fn set_fd_nonblocking(fd: RawFd, nonblocking: bool) -> io::Result<()> {
+    let flags = unsafe { libc::fcntl(fd, libc::F_GETFL) };
+
+    if flags < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let flags = match nonblocking {
+        true => flags | libc::O_NONBLOCK,
+        false => flags & !libc::O_NONBLOCK,
+    };
+
+    match unsafe { libc::fcntl(fd, libc::F_SETFL, flags) } {
+        0.. => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+impl Read for FileObject {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            FileObject::File(file) => file.read(buf),
+            FileObject::Stream(stream) => stream.read(buf),
+            FileObject::Pipe(ProcessPipe::Output(output)) => output.read(buf),
+            FileObject::Pipe(ProcessPipe::Error(error)) => error.read(buf),
+            _ => Err(self.unsupported("read from")),
+        }
+    }
+}
+
+impl Write for FileObject {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            FileObject::File(file) => file.write(buf),
+            FileObject::Stream(stream) => stream.write(buf),
+            FileObject::Pipe(ProcessPipe::Input(input)) => input.write(buf),
+            _ => Err(self.unsupported("write to")),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            FileObject::File(file) => file.flush(),
+            FileObject::Stream(stream) => stream.flush(),
+            FileObject::Pipe(ProcessPipe::Input(input)) => input.flush(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A temporary file or directory to delete when it's fd is closed, along with the id of the
+/// interpreter that created it.  Resetting that interpreter deletes it as well.
+struct AutoDelete {
+    path: PathBuf,
+    owner: u64,
+}
+
+/// An open file or stream in the file table.  Buffered reads, such as reading a line, can read
+/// ahead of what they return.  Those bytes are kept with the fd so that the next read picks up
+/// where the last one left off.
+struct FileEntry {
+    object: FileObject,
+    pending: Vec<u8>,
+
+    /// A temporary file or directory to delete when the fd is closed.
+    auto_delete: Option<AutoDelete>,
+
+    /// Reads that would block return none instead of waiting.
+    nonblocking: bool,
+}
+
+impl FileEntry {
+    fn new(object: FileObject) -> FileEntry {
+        FileEntry {
+            object,
+            pending: Vec::new(),
+            auto_delete: None,
+            nonblocking: false,
+        }
+    }
+
+    /// Close the fd, deleting it's file or directory if it was created to be deleted.  The file
+    /// is closed before it is deleted, as not all platforms can delete open files.  Any thread
+    /// that still holds the entry sees it as closed.
+    fn close(&mut self) -> io::Result<()> {
+        self.object = FileObject::Closed;
+        self.pending.clear();
+
+        match self.auto_delete.take() {
+            Some(AutoDelete { path, .. }) if path.is_dir() => remove_dir_all(path),
+            Some(AutoDelete { path, .. }) => remove_file(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Read the next chunk of data into the read ahead buffer, returning the number of bytes
+    /// read.  Zero is returned at the end of the file.
+    fn fill(&mut self) -> io::Result<usize> {
+        let mut chunk = [0; 4096];
+        let count = self.object.read(&mut chunk)?;
+
+        self.pending.extend_from_slice(&chunk[..count]);
+
+        Ok(count)
+    }
+
+    /// Read a line without it's line ending.  None is returned at the end of the file.
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        loop {
+            if let Some(index) = self.pending.iter().position(|byte| *byte == b'\n') {
+                let line: Vec<u8> = self.pending.drain(..=index).collect();
+                return Ok(Some(FileEntry::trim_line(&line)));
+            }
+
+            if self.fill()? == 0 {
+                return match self.pending.is_empty() {
+                    true => Ok(None),
+                    false => Ok(Some(FileEntry::trim_line(&take(&mut self.pending)))),
+                };
+            }
+        }
+    }
+
+    fn trim_line(line: &[u8]) -> String {
+        String::from_utf8_lossy(line)
+            .trim_end_matches(&['\n', '\r'][..])
+            .to_string()
+    }
+
+    /// Read everything up to the end of the file, or until the other end closes the stream.  A
+    /// non-blocking fd stops at whatever has arrived so far, and only reports that it would block
+    /// if nothing has.
+    fn read_to_end(&mut self) -> io::Result<Vec<u8>> {
+        let mut bytes = take(&mut self.pending);
+
+        match self.object.read_to_end(&mut bytes) {
+            Ok(_) => Ok(bytes),
+            Err(error)
+                if self.nonblocking
+                    && error.kind() == ErrorKind::WouldBlock
+                    && !bytes.is_empty() =>
+            {
+                Ok(bytes)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Read up to count bytes, stopping early at the end of the file.  The bytes are collected as
+    /// they arrive, so asking for far more than the file holds doesn't allocate all of it up
+    /// front.  A non-blocking fd stops early once there is nothing more to read.
+    fn read_up_to(&mut self, count: usize) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+
+        match self.take(count as u64).read_to_end(&mut bytes) {
+            Ok(_) => Ok(bytes),
+            Err(error)
+                if self.nonblocking
+                    && error.kind() == ErrorKind::WouldBlock
+                    && !bytes.is_empty() =>
+            {
+                Ok(bytes)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Read until the buffer is full or the end of the file is reached, returning the number of
+    /// bytes read.  A non-blocking fd stops early once there is nothing more to read.
+    fn read_full(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let mut count = 0;
+
+        while count < buffer.len() {
+            match self.read(&mut buffer[count..]) {
+                Ok(0) => break,
+                Ok(read) => count += read,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error)
+                    if self.nonblocking && error.kind() == ErrorKind::WouldBlock && count > 0 =>
+                {
+                    break;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Write all of the bytes.  A file's read ahead is dropped first so that the write lands
+    /// where the reads left off.  A stream's reads and writes are independent, so anything read
+    /// ahead is kept.
+    fn write_all(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.drop_read_ahead()?;
+        self.object.write_all(bytes)
+    }
+
+    /// Move a file's cursor back over the bytes that were read ahead and forget them, so that the
+    /// file's cursor is where the script thinks it is.
+    fn drop_read_ahead(&mut self) -> io::Result<()> {
+        if let FileObject::File(file) = &mut self.object
+            && !self.pending.is_empty()
+        {
+            file.seek(SeekFrom::Current(-(self.pending.len() as i64)))?;
+            self.pending.clear();
+        }
+
+        Ok(())
+    }
+
+    /// Get the file of a regular file fd, with it's cursor where the script left it.
+    fn file(&mut self) -> io::Result<&mut File> {
+        self.drop_read_ahead()?;
+
+        match &mut self.object {
+            FileObject::File(file) => Ok(file),
+            _ => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "the fd is not a regular file",
+            )),
+        }
+    }
+
+    /// Get the stream of a connected socket fd, or an error if the fd is anything else.
+    fn stream(&mut self) -> io::Result<&mut RawIpcStream> {
+        match &mut self.object {
+            FileObject::Stream(stream) => Ok(stream),
+            _ => Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "the fd is not a connected socket",
+            )),
+        }
+    }
+
+    /// Get the TCP stream of a connected socket fd.
+    fn tcp_stream(&mut self) -> io::Result<&mut TcpStream> {
+        match self.stream()? {
+            RawIpcStream::Tcp(stream) => Ok(stream),
+            _ => Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "the fd is not a TCP socket",
+            )),
+        }
+    }
+
+    /// Get the UDP socket of an fd opened with udp.bind.
+    fn udp(&mut self) -> io::Result<&mut UdpSocket> {
+        match &mut self.object {
+            FileObject::Udp(socket) => Ok(socket),
+            _ => Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "the fd is not a UDP socket",
+            )),
+        }
+    }
+
+    /// Get the address a socket fd is bound to.
+    fn local_address(&mut self) -> io::Result<String> {
+        match &mut self.object {
+            FileObject::Stream(stream) => stream.local_address(),
+            FileObject::Listener { listener, .. } => listener.local_address(),
+            FileObject::Udp(socket) => Ok(socket.local_addr()?.to_string()),
+            _ => Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "the fd is not a socket",
+            )),
+        }
+    }
+
+    /// Switch the fd between blocking and non-blocking mode.
+    fn set_nonblocking(&mut self, nonblocking: bool) -> io::Result<()> {
+        self.object.set_nonblocking(nonblocking)?;
+        self.nonblocking = nonblocking;
+
+        Ok(())
+    }
+
+    /// Get the number of bytes that can be read from a stream without blocking.  Whatever has
+    /// arrived is moved into the read ahead buffer.
+    fn available(&mut self) -> io::Result<usize> {
+        self.stream()?.set_nonblocking(true)?;
+
+        let result = loop {
+            match self.fill() {
+                Ok(0) => break Ok(()),
+                Ok(_) => continue,
+                Err(error) if error.kind() == ErrorKind::WouldBlock => break Ok(()),
+                Err(error) => break Err(error),
+            }
+        };
+
+        let nonblocking = self.nonblocking;
+
+        self.stream()?.set_nonblocking(nonblocking)?;
+        result?;
+
+        Ok(self.pending.len())
+    }
+}
+
+impl Read for FileEntry {
+    /// Read up to the size of the buffer, starting with any bytes that were read ahead.
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        if self.pending.is_empty() {
+            return self.object.read(buffer);
+        }
+
+        let count = buffer.len().min(self.pending.len());
+
+        buffer[..count].copy_from_slice(&self.pending[..count]);
+        self.pending.drain(..count);
+
+        Ok(count)
+    }
 }
 
 lazy_static! {
@@ -79,7 +648,26 @@ lazy_static! {
     static ref FD_COUNTER: AtomicI64 = AtomicI64::new(4);
 
     // Keep a table to map generated FDs to file structs.
-    static ref FILE_TABLE: Mutex<HashMap<i64, FileObject>> = Mutex::new(HashMap::new());
+    static ref FILE_TABLE: Mutex<HashMap<i64, SharedEntry>> = Mutex::new(HashMap::new());
+}
+
+/// Entries are locked individually, so that a read that blocks on one fd doesn't hold up the
+/// other fds.  The table itself is only locked long enough to find or change an entry.
+type SharedEntry = Arc<Mutex<FileEntry>>;
+
+fn insert_entry(fd: i64, entry: FileEntry) {
+    FILE_TABLE
+        .lock()
+        .unwrap()
+        .insert(fd, Arc::new(Mutex::new(entry)));
+}
+
+fn find_entry(fd: i64) -> Option<SharedEntry> {
+    FILE_TABLE.lock().unwrap().get(&fd).cloned()
+}
+
+fn remove_entry(fd: i64) -> Option<SharedEntry> {
+    FILE_TABLE.lock().unwrap().remove(&fd)
 }
 
 fn generate_fd() -> i64 {
@@ -87,48 +675,156 @@ fn generate_fd() -> i64 {
 }
 
 fn add_file(fd: i64, file: File) {
-    FILE_TABLE
-        .lock()
-        .unwrap()
-        .insert(fd, FileObject::File(file));
+    add_object(fd, FileObject::File(file));
 }
 
 fn add_stream(fd: i64, stream: RawIpcStream) {
-    FILE_TABLE
-        .lock()
-        .unwrap()
-        .insert(fd, FileObject::Stream(stream));
+    add_object(fd, FileObject::Stream(stream));
 }
 
-fn get_file(interpreter: &mut dyn Interpreter, fd: i64) -> error::Result<FileObject> {
-    let table = FILE_TABLE.lock().unwrap();
-    let file = table.get(&fd);
-
-    match file {
-        Some(file) => {
-            match file {
-                FileObject::File(file) => Ok(FileObject::File(file.try_clone()?)),
-                // Cloning streams is not supported for all types; return an error for now
-                FileObject::Stream(_) => {
-                    Err(std::io::Error::other("Cloning streams is not supported").into())
-                }
-            }
-        }
+fn add_object(fd: i64, object: FileObject) {
+    insert_entry(fd, FileEntry::new(object));
+}
 
+/// Run an operation on an open fd's entry in the file table.  I/O errors are reported as script
+/// errors, described by the action that was being attempted.
+fn with_entry<T>(
+    interpreter: &mut dyn Interpreter,
+    fd: i64,
+    action: &str,
+    operation: impl FnOnce(&mut FileEntry) -> io::Result<T>,
+) -> error::Result<T> {
+    let result = find_entry(fd).map(|entry| operation(&mut entry.lock().unwrap()));
+
+    match result {
+        Some(Ok(value)) => Ok(value),
+        Some(Err(error)) => script_error(interpreter, format!("Could not {}: {}.", action, error)),
         None => script_error(interpreter, format!("File struct for fd {} not found.", fd)),
     }
 }
 
-fn unregister_file(interpreter: &mut dyn Interpreter, fd: i64) -> error::Result<()> {
-    let mut table = FILE_TABLE.lock().unwrap();
+/// Turn an operation that would block on a non-blocking fd into None, so that it's word can push
+/// none instead of raising an error.  A read timing out also reports that it would block, that is
+/// still an error for fds that weren't made non-blocking.
+fn unless_would_block<T>(nonblocking: bool, result: io::Result<T>) -> io::Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if nonblocking && error.kind() == ErrorKind::WouldBlock => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Read a single byte from an open fd.  None is returned at the end of the file.
+pub fn read_fd_byte(interpreter: &mut dyn Interpreter, fd: i64) -> error::Result<Option<u8>> {
+    with_entry(interpreter, fd, "read from file", |entry| {
+        let mut buffer = [0; 1];
+
+        match entry.read(&mut buffer)? {
+            0 => Ok(None),
+            _ => Ok(Some(buffer[0])),
+        }
+    })
+}
+
+/// Add a pipe to a child process to the file table, returning it's new fd.
+pub fn add_process_pipe(pipe: ProcessPipe) -> i64 {
+    let fd = generate_fd();
+
+    add_object(fd, FileObject::Pipe(pipe));
+
+    fd
+}
+
+/// Get an fd ready to be used as a child process's standard input, output or error.  Regular
+/// files are shared with the child.  Process pipes are duplicated, once the child has started the
+/// pipe's fd should be closed with close_process_pipe so that it connects one process to the
+/// next.  A pipe with data that was already read ahead can't be handed on without losing it, so
+/// it's refused.
+pub fn fd_to_stdio(interpreter: &mut dyn Interpreter, fd: i64) -> error::Result<Stdio> {
+    let action = format!("connect fd {} to a process", fd);
+
+    with_entry(interpreter, fd, &action, |entry| match &entry.object {
+        FileObject::Pipe(_) if !entry.pending.is_empty() => Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "it has data that was already read from the pipe",
+        )),
+        FileObject::Pipe(pipe) => pipe.try_clone_stdio(),
+        _ => entry
+            .file()
+            .and_then(|file| file.try_clone())
+            .map(Stdio::from),
+    })
+}
+
+/// Close the fd of a process pipe that was given to a child process by fd_to_stdio.  Returns
+/// true if the fd was a pipe and has been closed, other fds are left open.
+pub fn close_process_pipe(fd: i64) -> bool {
+    let Some(shared) = find_entry(fd) else {
+        return false;
+    };
 
-    if !table.contains_key(&fd) {
-        script_error(interpreter, format!("File struct not found for fd {}.", fd))?;
+    let mut entry = shared.lock().unwrap();
+
+    if !matches!(entry.object, FileObject::Pipe(_)) {
+        return false;
     }
 
-    table.remove(&fd);
+    remove_entry(fd);
+    let _ = entry.close();
 
-    Ok(())
+    true
+}
+
+/// Write all of the given bytes directly to an open fd.
+pub fn write_fd_bytes(
+    interpreter: &mut dyn Interpreter,
+    fd: i64,
+    bytes: &[u8],
+) -> error::Result<()> {
+    with_entry(interpreter, fd, "write to file", |entry| {
+        entry.write_all(bytes)
+    })
+}
+
+fn unregister_file(interpreter: &mut dyn Interpreter, fd: i64) -> error::Result<()> {
+    let entry = remove_entry(fd);
+
+    match entry.map(|entry| entry.lock().unwrap().close()) {
+        Some(Ok(())) => Ok(()),
+        Some(Err(error)) => script_error(
+            interpreter,
+            format!("Could not delete temporary file for fd {}: {}.", fd, error),
+        ),
+        None => script_error(interpreter, format!("File struct not found for fd {}.", fd)),
+    }
+}
+
+/// Close every fd that the interpreter created with file.auto-delete, deleting their files and
+/// directories.  Called when the interpreter is reset, fds created by other interpreters in the
+/// process are left alone.
+pub fn close_auto_delete_files(owner: u64) {
+    let entries: Vec<(i64, SharedEntry)> = FILE_TABLE
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(fd, entry)| (*fd, entry.clone()))
+        .collect();
+
+    for (fd, entry) in entries {
+        let mut entry = entry.lock().unwrap();
+
+        if entry
+            .auto_delete
+            .as_ref()
+            .is_some_and(|auto_delete| auto_delete.owner == owner)
+        {
+            remove_entry(fd);
+
+            // There is no one to report an error to during a reset, so whatever can't be deleted
+            // is left behind.
+            let _ = entry.close();
+        }
+    }
 }
 
 fn flags_to_options(flags: i64) -> OpenOptions {
@@ -198,27 +894,147 @@ fn word_file_create(interpreter: &mut dyn Interpreter) -> error::Result<()> {
     Ok(())
 }
 
-fn word_file_create_temp_file(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    script_error_str(interpreter, "Create temp file unimplemented.")
-}
+/// Flag for file.create.tempfile and dir.create.temp, delete the file or directory when it's fd
+/// is closed or the interpreter is reset.
+const FILE_AUTO_DELETE: i64 = 0b0100;
 
-fn word_file_close(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    let fd = interpreter.pop_as_int()?;
+/// Try new random names in the system's temp directory until create succeeds on one that
+/// doesn't exist yet.  The create must fail if the path already exists so that a file or link
+/// planted by someone else is never opened.
+fn create_temp_path<T>(
+    prefix: &str,
+    create: impl Fn(&Path) -> io::Result<T>,
+) -> io::Result<(PathBuf, T)> {
+    static TEMP_COUNTER: AtomicI64 = AtomicI64::new(0);
 
-    unregister_file(interpreter, fd)?;
+    let temp_dir = env::temp_dir();
 
-    Ok(())
-}
+    for _ in 0..100 {
+        let mut hasher = RandomState::new().build_hasher();
 
-fn word_file_delete(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    let path = interpreter.pop_as_string()?;
+        hasher.write_u32(process::id());
+        hasher.write_i64(TEMP_COUNTER.fetch_add(1, Ordering::SeqCst));
 
-    remove_file(&path)?;
+        if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+            hasher.write_u128(elapsed.as_nanos());
+        }
 
-    Ok(())
-}
+        let path = temp_dir.join(format!("{}-{:016x}", prefix, hasher.finish()));
 
-fn word_socket_connect(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+        match create(&path) {
+            Ok(created) => return Ok((path, created)),
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => continue,
+            Err(error) => return Err(error),
+        }
+    }
+
+    Err(io::Error::new(
+        ErrorKind::AlreadyExists,
+        "could not find an unused temporary name",
+    ))
+}
+
+fn add_temp_entry(owner: u64, fd: i64, object: FileObject, path: &Path, flags: i64) {
+    let mut entry = FileEntry::new(object);
+
+    if flags & FILE_AUTO_DELETE != 0 {
+        entry.auto_delete = Some(AutoDelete {
+            path: path.to_path_buf(),
+            owner,
+        });
+    }
+
+    insert_entry(fd, entry);
+}
+
+/// Create a new file in the system's temp directory with a unique name, readable and writable
+/// only by the current user.  Temp files are always opened for writing.
+///
+/// Signature: `flags -- path fd`
+fn word_file_create_temp_file(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let flags = interpreter.pop_as_int()?;
+
+    let mut options = flags_to_options(flags);
+
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    match create_temp_path("sorth", |path| options.open(path)) {
+        Ok((path, file)) => {
+            let fd = generate_fd();
+
+            add_temp_entry(interpreter.id(), fd, FileObject::File(file), &path, flags);
+
+            interpreter.push(path.display().to_string().to_value());
+            interpreter.push(fd.to_value());
+
+            Ok(())
+        }
+
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not create temporary file: {}.", error),
+        ),
+    }
+}
+
+/// Create a new directory in the system's temp directory with a unique name, accessible only by
+/// the current user.  The fd can't be read or written, closing it deletes the directory and
+/// everything in it if the file.auto-delete flag was given.
+///
+/// Signature: `flags -- path fd`
+fn word_dir_create_temp(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let flags = interpreter.pop_as_int()?;
+
+    let mut builder = DirBuilder::new();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+
+    match create_temp_path("sorth-dir", |path| builder.create(path)) {
+        Ok((path, ())) => {
+            let fd = generate_fd();
+
+            add_temp_entry(interpreter.id(), fd, FileObject::Directory, &path, flags);
+
+            interpreter.push(path.display().to_string().to_value());
+            interpreter.push(fd.to_value());
+
+            Ok(())
+        }
+
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not create temporary directory: {}.", error),
+        ),
+    }
+}
+
+fn word_file_close(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+
+    unregister_file(interpreter, fd)?;
+
+    Ok(())
+}
+
+fn word_file_delete(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let path = interpreter.pop_as_string()?;
+
+    remove_file(&path)?;
+
+    Ok(())
+}
+
+fn word_socket_connect(interpreter: &mut dyn Interpreter) -> error::Result<()> {
     let path = interpreter.pop_as_string()?;
 
     #[cfg(unix)]
@@ -264,20 +1080,17 @@ fn word_socket_connect(interpreter: &mut dyn Interpreter) -> error::Result<()> {
 
 fn word_file_size_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
     let fd = interpreter.pop_as_int()?;
-    let file = get_file(interpreter, fd)?;
-
-    match file {
-        FileObject::File(file) => {
-            let metadata = file.metadata()?;
-            let size = metadata.len();
-
-            interpreter.push(size.to_value());
+    let size = with_entry(interpreter, fd, "read size of file", |entry| {
+        match &entry.object {
+            FileObject::File(file) => Ok(file.metadata()?.len()),
+            _ => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "can not read size of a socket",
+            )),
         }
+    })?;
 
-        FileObject::Stream(_) => {
-            script_error_str(interpreter, "Can not read size of a socket.")?;
-        }
-    }
+    interpreter.push(size.to_value());
 
     Ok(())
 }
@@ -291,199 +1104,892 @@ fn word_file_exists(interpreter: &mut dyn Interpreter) -> error::Result<()> {
 
 fn word_file_is_open(interpreter: &mut dyn Interpreter) -> error::Result<()> {
     let fd = interpreter.pop_as_int()?;
-    let file = get_file(interpreter, fd);
+    let is_open = find_entry(fd).is_some();
 
-    interpreter.push(file.is_ok().to_value());
+    interpreter.push(is_open.to_value());
 
     Ok(())
 }
 
 fn word_file_is_eof(interpreter: &mut dyn Interpreter) -> error::Result<()> {
     let fd = interpreter.pop_as_int()?;
-    let file = get_file(interpreter, fd)?;
+    let is_eof = with_entry(interpreter, fd, "check eof status of file", |entry| {
+        let pending = entry.pending.len() as u64;
 
-    match file {
-        FileObject::File(mut file) => {
-            let current_pos = file.stream_position()?;
-            let total_size = file.metadata()?.len();
+        match &mut entry.object {
+            FileObject::File(file) => {
+                let current_pos = file.stream_position()?;
+                let total_size = file.metadata()?.len();
 
-            interpreter.push((current_pos == total_size).to_value());
-        }
+                Ok(current_pos - pending == total_size)
+            }
 
-        FileObject::Stream(_) => {
-            script_error_str(interpreter, "Can not eof status of a socket.")?;
+            _ => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "can not eof status of a socket",
+            )),
         }
-    }
+    })?;
+
+    interpreter.push(is_eof.to_value());
 
     Ok(())
 }
 
 fn word_file_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    script_error_str(interpreter, "Unimplemented.")
+    let fd = interpreter.pop_as_int()?;
+    let count = interpreter.pop_as_usize()?;
+
+    let bytes = with_entry(interpreter, fd, "read from file", |entry| {
+        unless_would_block(entry.nonblocking, entry.read_up_to(count))
+    })?;
+
+    match bytes {
+        Some(bytes) => interpreter.push(bytes_to_buffer(&bytes).to_value()),
+        None => interpreter.push(Value::None),
+    }
+
+    Ok(())
 }
 
 fn word_file_read_character(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    fn read<T>(interpreter: &mut dyn Interpreter, reader: &mut BufReader<T>) -> error::Result<()>
-    where
-        T: Read,
-    {
+    let fd = interpreter.pop_as_int()?;
+    let character = with_entry(interpreter, fd, "read from file", |entry| {
         let mut buffer = [0; 1];
+        let count = unless_would_block(entry.nonblocking, entry.read(&mut buffer))?;
+
+        Ok(count.map(|count| (count > 0).then_some(buffer[0])))
+    })?;
+
+    match character {
+        Some(Some(character)) => interpreter.push(character.to_string().to_value()),
+        Some(None) => interpreter.push("".to_string().to_value()),
+        None => interpreter.push(Value::None),
+    }
+
+    Ok(())
+}
+
+fn word_file_read_string(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let bytes = with_entry(interpreter, fd, "read from file", |entry| {
+        unless_would_block(entry.nonblocking, entry.read_to_end())
+    })?;
+
+    match bytes {
+        Some(bytes) => interpreter.push(String::from_utf8_lossy(&bytes).to_string().to_value()),
+        None => interpreter.push(Value::None),
+    }
+
+    Ok(())
+}
+
+fn word_file_write(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    // TODO: Implement better string conversion.
+    let fd = interpreter.pop_as_int()?;
+    let value = interpreter.pop()?;
+
+    match value {
+        Value::ByteBuffer(buffer) => {
+            let bytes = buffer.borrow().get_bytes(0, buffer.borrow().len());
+            write_fd_bytes(interpreter, fd, &bytes)
+        }
+
+        value if value.is_stringable() => {
+            write_fd_bytes(interpreter, fd, value.get_string_val().as_bytes())
+        }
+
+        _ => script_error_str(interpreter, "Expected a string value or a ByteBuffer."),
+    }
+}
+
+fn word_file_line_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let line = with_entry(interpreter, fd, "read from file", |entry| {
+        unless_would_block(entry.nonblocking, entry.read_line())
+    })?;
+
+    // A partial line is kept until the rest of it arrives.
+    match line {
+        Some(line) => interpreter.push(line.unwrap_or_default().to_value()),
+        None => interpreter.push(Value::None),
+    }
+
+    Ok(())
+}
+
+fn word_file_line_write(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    // TODO: Implement better string conversion.
+    let fd = interpreter.pop_as_int()?;
+    let string = interpreter.pop_as_string()?;
+
+    write_fd_bytes(interpreter, fd, (string + "\n").as_bytes())
+}
+
+/// Create a new buffer holding a copy of the given bytes.
+fn bytes_to_buffer(bytes: &[u8]) -> BufferPtr {
+    let buffer = ByteBuffer::new_ptr(bytes.len());
+
+    buffer.borrow_mut().buffer_mut().copy_from_slice(bytes);
+
+    buffer
+}
+
+/// Fill a buffer from it's current position to it's end, stopping early at the end of the file.
+/// The buffer's position is moved past the bytes read.  A non-blocking fd with nothing to read
+/// gives none.
+///
+/// Signature: `buffer fd -- count`
+fn word_file_read_into(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let buffer = interpreter.pop_as_byte_buffer()?;
+
+    let (position, len) = {
+        let buffer = buffer.borrow();
+        (buffer.position(), buffer.len())
+    };
+
+    let mut bytes = vec![0; len - position];
+    let count = with_entry(interpreter, fd, "read from file", |entry| {
+        unless_would_block(entry.nonblocking, entry.read_full(&mut bytes))
+    })?;
+
+    let Some(count) = count else {
+        interpreter.push(Value::None);
+        return Ok(());
+    };
+
+    buffer.borrow_mut().set_bytes(position, &bytes[..count]);
+    buffer.borrow_mut().increment_position(count);
 
-        match reader.read(&mut buffer) {
-            Ok(0) => {
-                interpreter.push("".to_string().to_value());
+    interpreter.push(count.to_value());
+
+    Ok(())
+}
+
+/// Write count bytes starting at the buffer's current position.  The buffer's position is moved
+/// past the bytes written.
+///
+/// Signature: `buffer count fd -- `
+fn word_file_write_buffer(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let count = interpreter.pop_as_usize()?;
+    let buffer = interpreter.pop_as_byte_buffer()?;
+
+    let bytes = take_buffer_bytes(interpreter, &buffer, count)?;
+
+    write_fd_bytes(interpreter, fd, &bytes)?;
+    buffer.borrow_mut().increment_position(count);
+
+    Ok(())
+}
+
+/// Move a file's cursor relative to the start, the current position, or the end of the file.
+///
+/// Signature: `offset whence fd -- `
+fn word_file_seek(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let whence = interpreter.pop_as_int()?;
+    let offset = interpreter.pop_as_int()?;
+
+    let seek_from = match whence {
+        0 => {
+            if offset < 0 {
+                return script_error(
+                    interpreter,
+                    format!("Can not seek to negative position {}.", offset),
+                );
             }
 
-            Ok(_) => {
-                interpreter.push(buffer[0].to_string().to_value());
+            SeekFrom::Start(offset as u64)
+        }
+        1 => SeekFrom::Current(offset),
+        2 => SeekFrom::End(offset),
+        _ => return script_error(interpreter, format!("Unknown seek origin {}.", whence)),
+    };
+
+    with_entry(interpreter, fd, "seek in file", |entry| {
+        entry.file()?.seek(seek_from)
+    })?;
+
+    Ok(())
+}
+
+/// Get the position of a file's cursor.
+///
+/// Signature: `fd -- position`
+fn word_file_tell(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let position = with_entry(interpreter, fd, "get file position", |entry| {
+        entry.file()?.stream_position()
+    })?;
+
+    interpreter.push(position.to_value());
+
+    Ok(())
+}
+
+/// Cut a file down, or pad it with zeros, to the given size.  The cursor is not moved.
+///
+/// Signature: `size fd -- `
+fn word_file_truncate(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let size = interpreter.pop_as_usize()?;
+
+    with_entry(interpreter, fd, "truncate file", |entry| {
+        entry.file()?.set_len(size as u64)
+    })
+}
+
+/// Make sure everything written to a file has reached the disk.  Streams are flushed.
+///
+/// Signature: `fd -- `
+fn word_file_sync(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+
+    with_entry(interpreter, fd, "sync file", |entry| {
+        match &mut entry.object {
+            FileObject::File(file) => file.sync_all(),
+            object => object.flush(),
+        }
+    })
+}
+
+/// Read everything up to the end of the file, or until the other end closes the stream, into a
+/// new buffer.  A non-blocking fd reads whatever has arrived, or gives none if nothing has.
+///
+/// Signature: `fd -- buffer`
+fn word_file_read_all(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let bytes = with_entry(interpreter, fd, "read from file", |entry| {
+        unless_would_block(entry.nonblocking, entry.read_to_end())
+    })?;
+
+    match bytes {
+        Some(bytes) => interpreter.push(bytes_to_buffer(&bytes).to_value()),
+        None => interpreter.push(Value::None),
+    }
+
+    Ok(())
+}
+
+/// Write the whole of a buffer, no matter where it's position is.
+///
+/// Signature: `buffer fd -- `
+fn word_file_write_all(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let buffer = interpreter.pop_as_byte_buffer()?;
+    let bytes = buffer.borrow().get_bytes(0, buffer.borrow().len());
+
+    write_fd_bytes(interpreter, fd, &bytes)
+}
+
+/// Shut down the reading half, writing half, or both halves of a socket's connection.  Shutting
+/// down writing lets the other end read to the end of the stream while replies can still be read.
+///
+/// Signature: `how fd -- `
+fn word_socket_shutdown(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let how = match interpreter.pop_as_int()? {
+        0b0001 => Shutdown::Read,
+        0b0010 => Shutdown::Write,
+        0b0011 => Shutdown::Both,
+        how => {
+            return script_error(
+                interpreter,
+                format!("Unknown socket shutdown mode {}.", how),
+            );
+        }
+    };
+
+    with_entry(interpreter, fd, "shut down socket", |entry| {
+        entry.stream()?.shutdown(how)
+    })
+}
+
+/// Pop a timeout in milliseconds.  Zero or none means no timeout.
+fn pop_timeout(interpreter: &mut dyn Interpreter) -> error::Result<Option<Duration>> {
+    let timeout = interpreter.pop()?;
+
+    if timeout.is_none() {
+        return Ok(None);
+    }
+
+    match timeout.get_int_val() {
+        0 => Ok(None),
+        milliseconds if milliseconds > 0 => Ok(Some(Duration::from_millis(milliseconds as u64))),
+        _ => script_error_str(interpreter, "Socket timeouts can not be negative."),
+    }
+}
+
+/// Set how long reads from a socket can block before failing with an error.
+///
+/// Signature: `milliseconds fd -- `
+fn word_socket_read_timeout_write(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let timeout = pop_timeout(interpreter)?;
+
+    with_entry(interpreter, fd, "set socket read timeout", |entry| {
+        entry.stream()?.set_read_timeout(timeout)
+    })
+}
+
+/// Set how long writes to a socket can block before failing with an error.
+///
+/// Signature: `milliseconds fd -- `
+fn word_socket_write_timeout_write(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let timeout = pop_timeout(interpreter)?;
+
+    with_entry(interpreter, fd, "set socket write timeout", |entry| {
+        entry.stream()?.set_write_timeout(timeout)
+    })
+}
+
+/// Get the number of bytes that can be read from a socket without blocking.
+///
+/// Signature: `fd -- count`
+fn word_socket_available_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let available = with_entry(interpreter, fd, "check socket", |entry| entry.available())?;
+
+    interpreter.push(available.to_value());
+
+    Ok(())
+}
+
+/// Option flag for socket.listen and udp.bind, allow binding to an address that is still in use
+/// by a closed connection.  For Unix domain sockets a stale socket file is removed first.
+const SOCKET_REUSE_ADDR: i64 = 0b0001;
+
+/// Option flag for socket.listen, turn off Nagle's algorithm on accepted TCP connections.
+const SOCKET_NODELAY: i64 = 0b0010;
+
+/// Pop the socket.opt/ flags for a new socket, none is the same as no options.
+fn pop_socket_options(interpreter: &mut dyn Interpreter) -> error::Result<i64> {
+    let options = interpreter.pop()?;
+
+    match options.is_none() {
+        true => Ok(0),
+        false => Ok(options.get_int_val()),
+    }
+}
+
+/// Resolve a host:port string to a socket address, None if it isn't one.
+fn resolve_address(address: &str) -> Option<SocketAddr> {
+    address.to_socket_addrs().ok()?.next()
+}
+
+/// Create a socket bound to the given address with SO_REUSEADDR set as requested.
+fn bind_socket(address: SocketAddr, socket_type: Type, reuse_addr: bool) -> io::Result<Socket> {
+    let protocol = match socket_type {
+        Type::STREAM => Protocol::TCP,
+        _ => Protocol::UDP,
+    };
+    let socket = Socket::new(Domain::for_address(address), socket_type, Some(protocol))?;
+
+    socket.set_reuse_address(reuse_addr)?;
+    socket.bind(&address.into())?;
+
+    Ok(socket)
+}
+
+fn listen_tcp(address: SocketAddr, options: i64) -> io::Result<RawIpcListener> {
+    let socket = bind_socket(address, Type::STREAM, options & SOCKET_REUSE_ADDR != 0)?;
+
+    socket.listen(128)?;
+
+    Ok(RawIpcListener::Tcp(socket.into()))
+}
+
+#[cfg(unix)]
+fn listen_unix(path: &str, options: i64) -> io::Result<RawIpcListener> {
+    use std::os::unix::fs::FileTypeExt;
+
+    if options & SOCKET_REUSE_ADDR != 0
+        && let Ok(metadata) = std::fs::symlink_metadata(path)
+        && metadata.file_type().is_socket()
+    {
+        remove_file(path)?;
+    }
+
+    Ok(RawIpcListener::Unix(UnixListener::bind(path)?))
+}
+
+#[cfg(not(unix))]
+fn listen_unix(path: &str, _options: i64) -> io::Result<RawIpcListener> {
+    Err(io::Error::new(
+        ErrorKind::InvalidInput,
+        format!("{} is not a host:port address", path),
+    ))
+}
+
+/// Start listening for connections on a TCP host:port, or on a Unix domain socket path.  The
+/// options are the socket.opt/ flags or'ed together, none or 0 for no options.
+///
+/// Signature: `address options -- fd`
+fn word_socket_listen(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let options = pop_socket_options(interpreter)?;
+    let address = interpreter.pop_as_string()?;
+
+    let listener = match resolve_address(&address) {
+        Some(socket_address) => listen_tcp(socket_address, options),
+        None => listen_unix(&address, options),
+    };
+
+    match listener {
+        Ok(listener) => {
+            let fd = generate_fd();
+            let nodelay = options & SOCKET_NODELAY != 0;
+
+            add_object(fd, FileObject::Listener { listener, nodelay });
+            interpreter.push(fd.to_value());
+
+            Ok(())
+        }
+
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not listen on {}: {}.", address, error),
+        ),
+    }
+}
+
+/// Get a new handle to the listening socket behind an fd, along with whether accepted connections
+/// should have nodelay set.
+pub fn clone_listener(
+    interpreter: &mut dyn Interpreter,
+    fd: i64,
+) -> error::Result<(RawIpcListener, bool)> {
+    with_entry(interpreter, fd, "accept connection", |entry| {
+        match &entry.object {
+            FileObject::Listener { listener, nodelay } => Ok((listener.try_clone()?, *nodelay)),
+            _ => Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "the fd is not a listening socket",
+            )),
+        }
+    })
+}
+
+/// Wait for a connection on a listening socket.  The new connection gets it's own fd.  A
+/// non-blocking listener with no connection waiting gives none for both.
+///
+/// Signature: `fd -- connection-fd peer-address`
+fn word_socket_accept(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+
+    // Clone the listener so that the table isn't locked while waiting for a connection.
+    let (listener, nodelay) = clone_listener(interpreter, fd)?;
+
+    match listener.accept() {
+        Ok((stream, peer_address)) => {
+            if nodelay && let RawIpcStream::Tcp(tcp_stream) = &stream {
+                tcp_stream.set_nodelay(true)?;
             }
 
-            Err(error) => {
-                return script_error(interpreter, format!("Could not read from file: {}.", error));
-            }
+            let connection_fd = generate_fd();
+
+            add_stream(connection_fd, stream);
+            interpreter.push(connection_fd.to_value());
+            interpreter.push(peer_address.to_value());
+
+            Ok(())
+        }
+
+        Err(error) if error.kind() == ErrorKind::WouldBlock => {
+            interpreter.push(Value::None);
+            interpreter.push(Value::None);
+
+            Ok(())
+        }
+
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not accept connection: {}.", error),
+        ),
+    }
+}
+
+/// Get the address a socket is bound to.  Listening on port 0 picks a free port, this is how to
+/// find out which one.
+///
+/// Signature: `fd -- address`
+fn word_socket_local_address_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let address = with_entry(interpreter, fd, "get socket address", |entry| {
+        entry.local_address()
+    })?;
+
+    interpreter.push(address.to_value());
+
+    Ok(())
+}
+
+/// Turn Nagle's algorithm off, true, or back on, false, for a connected TCP socket.
+///
+/// Signature: `flag fd -- `
+fn word_socket_nodelay_write(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let nodelay = interpreter.pop_as_bool()?;
+
+    with_entry(interpreter, fd, "set socket nodelay", |entry| {
+        entry.tcp_stream()?.set_nodelay(nodelay)
+    })
+}
+
+/// Check if Nagle's algorithm is turned off for a connected TCP socket.
+///
+/// Signature: `fd -- flag`
+fn word_socket_nodelay_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let nodelay = with_entry(interpreter, fd, "get socket nodelay", |entry| {
+        entry.tcp_stream()?.nodelay()
+    })?;
+
+    interpreter.push(nodelay.to_value());
+
+    Ok(())
+}
+
+/// Create a UDP socket bound to a host:port.  Only socket.opt/reuse-addr applies to UDP sockets.
+///
+/// Signature: `address options -- fd`
+fn word_udp_bind(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let options = pop_socket_options(interpreter)?;
+    let address = interpreter.pop_as_string()?;
+
+    if options & SOCKET_NODELAY != 0 {
+        script_error_str(
+            interpreter,
+            "The nodelay option only applies to TCP sockets.",
+        )?;
+    }
+
+    let Some(socket_address) = resolve_address(&address) else {
+        return script_error(
+            interpreter,
+            format!("Could not bind to {}: not a host:port address.", address),
+        );
+    };
+
+    match bind_socket(
+        socket_address,
+        Type::DGRAM,
+        options & SOCKET_REUSE_ADDR != 0,
+    ) {
+        Ok(socket) => {
+            let fd = generate_fd();
+
+            add_object(fd, FileObject::Udp(socket.into()));
+            interpreter.push(fd.to_value());
+
+            Ok(())
+        }
+
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not bind to {}: {}.", address, error),
+        ),
+    }
+}
+
+/// Send a datagram of count bytes, read from the buffer's current position.  The buffer's
+/// position is moved past the bytes sent.
+///
+/// Signature: `buffer count address fd -- sent`
+fn word_udp_send_to(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let address = interpreter.pop_as_string()?;
+    let count = interpreter.pop_as_usize()?;
+    let buffer = interpreter.pop_as_byte_buffer()?;
+
+    let bytes = take_buffer_bytes(interpreter, &buffer, count)?;
+
+    let sent = with_entry(interpreter, fd, "send datagram", |entry| {
+        entry.udp()?.send_to(&bytes, address.as_str())
+    })?;
+
+    buffer.borrow_mut().increment_position(sent);
+    interpreter.push(sent.to_value());
+
+    Ok(())
+}
+
+/// Wait for a datagram and write it into the buffer at it's current position.  Anything that
+/// doesn't fit in the rest of the buffer is lost.  The buffer's position is moved past the bytes
+/// received.  A non-blocking socket with no datagram waiting gives none for both.
+///
+/// Signature: `buffer fd -- count sender-address`
+fn word_udp_recv_from(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let buffer = interpreter.pop_as_byte_buffer()?;
+
+    let remaining = {
+        let buffer = buffer.borrow();
+        buffer.len() - buffer.position()
+    };
+
+    // Clone the socket so that the table isn't locked while waiting for the datagram.
+    let socket = with_entry(interpreter, fd, "receive datagram", |entry| {
+        entry.udp()?.try_clone()
+    })?;
+
+    let mut bytes = vec![0; remaining];
+
+    match socket.recv_from(&mut bytes) {
+        Ok((count, sender)) => {
+            let position = buffer.borrow().position();
+
+            buffer.borrow_mut().set_bytes(position, &bytes[..count]);
+            buffer.borrow_mut().increment_position(count);
+
+            interpreter.push(count.to_value());
+            interpreter.push(sender.to_string().to_value());
+
+            Ok(())
+        }
+
+        Err(error) if error.kind() == ErrorKind::WouldBlock => {
+            interpreter.push(Value::None);
+            interpreter.push(Value::None);
+
+            Ok(())
         }
 
-        Ok(())
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not receive datagram: {}.", error),
+        ),
+    }
+}
+
+/// Copy count bytes starting at the buffer's current position, making sure they're all within
+/// the buffer.  The position is left unchanged.
+fn take_buffer_bytes(
+    interpreter: &mut dyn Interpreter,
+    buffer: &BufferPtr,
+    count: usize,
+) -> error::Result<Vec<u8>> {
+    let (position, len) = {
+        let buffer = buffer.borrow();
+        (buffer.position(), buffer.len())
+    };
+
+    if position + count > len {
+        return script_error(
+            interpreter,
+            format!(
+                "Reading {} bytes at position {} would exceed the buffer size {}.",
+                count, position, len
+            ),
+        );
     }
 
+    Ok(buffer.borrow().get_bytes(position, count))
+}
+
+/// Switch an fd between blocking and non-blocking mode.  Reads from a non-blocking fd give none
+/// when there is nothing to read yet, instead of waiting for it.
+///
+/// Signature: `flag fd -- `
+fn word_file_nonblocking_write(interpreter: &mut dyn Interpreter) -> error::Result<()> {
     let fd = interpreter.pop_as_int()?;
-    let file = get_file(interpreter, fd)?;
+    let nonblocking = interpreter.pop_as_bool()?;
 
-    match file {
-        FileObject::File(file) => read(interpreter, &mut BufReader::new(file)),
-        FileObject::Stream(stream) => read(interpreter, &mut BufReader::new(stream)),
-    }
+    with_entry(interpreter, fd, "set non-blocking mode", |entry| {
+        entry.set_nonblocking(nonblocking)
+    })
 }
 
-fn word_file_read_string(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    fn read<T>(interpreter: &mut dyn Interpreter, reader: &mut BufReader<T>) -> error::Result<()>
-    where
-        T: Read,
-    {
-        let mut string = String::new();
+/// Check if an fd is in non-blocking mode.
+///
+/// Signature: `fd -- flag`
+fn word_file_nonblocking_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let fd = interpreter.pop_as_int()?;
+    let nonblocking = with_entry(interpreter, fd, "get non-blocking mode", |entry| {
+        Ok(entry.nonblocking)
+    })?;
 
-        match reader.read_to_string(&mut string) {
-            Ok(0) => {
-                interpreter.push("".to_string().to_value());
-            }
+    interpreter.push(nonblocking.to_value());
+
+    Ok(())
+}
 
-            Ok(_) => {
-                interpreter.push(string.to_value());
+/// Poll flag, the fd has data to read, a connection to accept, or has reached the end.
+const POLL_READ: i64 = 0b0001;
+
+/// Poll flag, the fd can be written to without blocking.
+const POLL_WRITE: i64 = 0b0010;
+
+/// Poll flag, the other end hung up or the fd is in an error state.  This is always reported,
+/// whether it was asked for or not.
+const POLL_HANGUP: i64 = 0b0100;
+
+/// Pop the fds to poll, an array of [ fd , interest ] pairs.
+fn pop_poll_requests(interpreter: &mut dyn Interpreter) -> error::Result<Vec<(i64, i64)>> {
+    let array = interpreter.pop_as_array()?;
+    let mut requests = Vec::new();
+
+    for value in array.borrow().iter() {
+        let request = match value {
+            Value::Vec(pair) if pair.borrow().len() == 2 => {
+                match (&pair.borrow()[0], &pair.borrow()[1]) {
+                    (Value::Int(fd), Value::Int(interest)) => Some((*fd, *interest)),
+                    _ => None,
+                }
             }
 
-            Err(error) => {
-                return script_error(interpreter, format!("Could not read from file: {}.", error));
+            _ => None,
+        };
+
+        match request {
+            Some(request) => requests.push(request),
+            None => {
+                return script_error(
+                    interpreter,
+                    format!(
+                        "Poll requests must be [ fd , interest ] pairs, found {}.",
+                        value
+                    ),
+                );
             }
         }
-
-        Ok(())
     }
 
-    let fd = interpreter.pop_as_int()?;
-    let file = get_file(interpreter, fd)?;
-
-    match file {
-        FileObject::File(file) => read(interpreter, &mut BufReader::new(file)),
-        FileObject::Stream(stream) => read(interpreter, &mut BufReader::new(stream)),
-    }
+    Ok(requests)
 }
 
-fn word_file_write(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    fn write<T>(
-        interpreter: &mut dyn Interpreter,
-        string: String,
-        writer: &mut BufWriter<T>,
-    ) -> error::Result<()>
-    where
-        T: Write,
-    {
-        let bytes = string.into_bytes();
+/// Call poll(2), retrying with whatever is left of the timeout when interrupted by a signal.  No
+/// timeout waits until one of the descriptors is ready.
+#[cfg(unix)]
+fn poll_descriptors(poll_fds: &mut [libc::pollfd], timeout: Option<Duration>) -> io::Result<()> {
+    use std::time::Instant;
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+    loop {
+        let milliseconds = match deadline {
+            Some(deadline) => deadline
+                .saturating_duration_since(Instant::now())
+                .as_nanos()
+                .div_ceil(1_000_000)
+                .min(libc::c_int::MAX as u128) as libc::c_int,
+            None => -1,
+        };
+
+        let result = unsafe {
+            libc::poll(
+                poll_fds.as_mut_ptr(),
+                poll_fds.len() as libc::nfds_t,
+                milliseconds,
+            )
+        };
+
+        if result >= 0 {
+            return Ok(());
+        }
 
-        match writer.write_all(bytes.as_slice()) {
-            // TODO: Handle partial writes.
-            Ok(_) => Ok(()),
+        let error = io::Error::last_os_error();
 
-            Err(error) => {
-                script_error(interpreter, format!("Could not read from file: {}.", error))
-            }
+        if error.kind() != ErrorKind::Interrupted {
+            return Err(error);
         }
     }
+}
 
-    // TODO: Implement ByteBuffer and better string conversion.
-    let fd = interpreter.pop_as_int()?;
-    let string = interpreter.pop_as_string()?;
-    let file = get_file(interpreter, fd)?;
+/// Wait until at least one of the fds is ready, or the timeout in milliseconds runs out.  A
+/// timeout of none, or a negative one, waits for as long as it takes and zero checks without
+/// waiting at all.  Float timeouts may wait for a fraction of a millisecond.  The ready fds are
+/// returned as [ fd , events ] pairs in the order they were asked for.  Data that was read ahead,
+/// such as by file.line@, counts as ready to read.
+///
+/// Signature: `requests timeout -- ready`
+#[cfg(unix)]
+fn word_io_poll(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let timeout = match interpreter.pop()? {
+        Value::None => None,
+        Value::Int(milliseconds) if milliseconds < 0 => None,
+        Value::Int(milliseconds) => Some(Duration::from_millis(milliseconds as u64)),
+        Value::Float(milliseconds) if milliseconds < 0.0 || milliseconds == f64::INFINITY => None,
+        Value::Float(milliseconds) if milliseconds.is_finite() => {
+            Some(Duration::from_secs_f64(milliseconds / 1000.0))
+        }
+        value => {
+            return script_error(
+                interpreter,
+                format!("Poll timeout {} is not a number of milliseconds.", value),
+            );
+        }
+    };
+    let requests = pop_poll_requests(interpreter)?;
 
-    match file {
-        FileObject::File(file) => write(interpreter, string, &mut BufWriter::new(file)),
-        FileObject::Stream(stream) => write(interpreter, string, &mut BufWriter::new(stream)),
-    }
-}
+    let mut poll_fds = Vec::with_capacity(requests.len());
+    let mut read_ahead = Vec::with_capacity(requests.len());
 
-fn word_file_line_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    fn read<T>(interpreter: &mut dyn Interpreter, buffer: &mut BufReader<T>) -> error::Result<()>
-    where
-        T: Read,
-    {
-        let mut line = String::new();
+    for (fd, interest) in &requests {
+        let (raw_fd, has_pending) = with_entry(interpreter, *fd, "poll fd", |entry| {
+            Ok((entry.object.raw_fd()?, !entry.pending.is_empty()))
+        })?;
 
-        match buffer.read_line(&mut line) {
-            Ok(0) => {
-                interpreter.push("".to_string().to_value());
-            }
+        let mut events = 0;
 
-            Ok(_) => {
-                let line = line.trim_end_matches(&['\n', '\r'][..]).to_string();
-                interpreter.push(line.to_value());
-            }
+        if interest & POLL_READ != 0 {
+            events |= libc::POLLIN;
+        }
 
-            Err(error) => {
-                return script_error(interpreter, format!("Could not read from file: {}.", error));
-            }
+        if interest & POLL_WRITE != 0 {
+            events |= libc::POLLOUT;
         }
 
-        Ok(())
+        poll_fds.push(libc::pollfd {
+            fd: raw_fd,
+            events,
+            revents: 0,
+        });
+        read_ahead.push(has_pending && interest & POLL_READ != 0);
     }
 
-    let fd = interpreter.pop_as_int()?;
-    let file = get_file(interpreter, fd)?;
+    // Anything already read ahead is ready now, so there's no reason to wait on the others.
+    let timeout = match read_ahead.contains(&true) {
+        true => Some(Duration::ZERO),
+        false => timeout,
+    };
 
-    match file {
-        FileObject::File(file) => read(interpreter, &mut BufReader::new(file)),
-        FileObject::Stream(stream) => read(interpreter, &mut BufReader::new(stream)),
+    if let Err(error) = poll_descriptors(&mut poll_fds, timeout) {
+        return script_error(interpreter, format!("Could not poll fds: {}.", error));
     }
-}
 
-fn word_file_line_write(interpreter: &mut dyn Interpreter) -> error::Result<()> {
-    fn write<T>(
-        interpreter: &mut dyn Interpreter,
-        string: String,
-        writer: &mut BufWriter<T>,
-    ) -> error::Result<()>
-    where
-        T: Write,
-    {
-        let bytes = (string + "\n").into_bytes();
+    let mut ready = Vec::new();
 
-        match writer.write_all(bytes.as_slice()) {
-            // TODO: Handle partial writes.
-            Ok(_) => Ok(()),
+    for (((fd, _), poll_fd), read_ahead) in requests.iter().zip(&poll_fds).zip(&read_ahead) {
+        let mut events = 0;
 
-            Err(error) => {
-                script_error(interpreter, format!("Could not read from file: {}.", error))
-            }
+        if *read_ahead || poll_fd.revents & libc::POLLIN != 0 {
+            events |= POLL_READ;
         }
-    }
 
-    // TODO: Implement better string conversion.
-    let fd = interpreter.pop_as_int()?;
-    let string = interpreter.pop_as_string()?;
-    let file = get_file(interpreter, fd)?;
+        if poll_fd.revents & libc::POLLOUT != 0 {
+            events |= POLL_WRITE;
+        }
+
+        if poll_fd.revents & (libc::POLLHUP | libc::POLLERR | libc::POLLNVAL) != 0 {
+            events |= POLL_HANGUP;
+        }
 
-    match file {
-        FileObject::File(file) => write(interpreter, string, &mut BufWriter::new(file)),
-        FileObject::Stream(stream) => write(interpreter, string, &mut BufWriter::new(stream)),
+        if events != 0 {
+            ready.push(ValueVec::from_vec(vec![fd.to_value(), events.to_value()]).to_value());
+        }
     }
+
+    interpreter.push(ValueVec::from_vec(ready).to_value());
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn word_io_poll(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    script_error_str(interpreter, "io.poll is only supported on Unix platforms.")
 }
 
 pub fn register_io_words(interpreter: &mut dyn Interpreter) {
@@ -511,6 +2017,14 @@ pub fn register_io_words(interpreter: &mut dyn Interpreter) {
         "flags -- path fd"
     );
 
+    add_native_word!(
+        interpreter,
+        "dir.create.temp",
+        word_dir_create_temp,
+        "Create an unique temporary directory and return a fd for it.",
+        "flags -- path fd"
+    );
+
     add_native_word!(
         interpreter,
         "file.close",
@@ -531,10 +2045,130 @@ pub fn register_io_words(interpreter: &mut dyn Interpreter) {
         interpreter,
         "socket.connect",
         word_socket_connect,
-        "Connect to Unix domain socket at the given path.",
+        "Connect to a Unix domain socket path, or a TCP host:port.",
         "path -- fd"
     );
 
+    add_native_word!(
+        interpreter,
+        "socket.shutdown",
+        word_socket_shutdown,
+        "Shut down reading, writing, or both on a connected socket.",
+        "how fd -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.read-timeout!",
+        word_socket_read_timeout_write,
+        "Set how many milliseconds a read can block, zero or none for no limit.",
+        "milliseconds fd -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.write-timeout!",
+        word_socket_write_timeout_write,
+        "Set how many milliseconds a write can block, zero or none for no limit.",
+        "milliseconds fd -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.available@",
+        word_socket_available_read,
+        "Get how many bytes can be read from a socket without blocking.",
+        "fd -- count"
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.listen",
+        word_socket_listen,
+        "Listen for connections on a TCP host:port, or a Unix domain socket path.",
+        "address options -- fd"
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.accept",
+        word_socket_accept,
+        "Wait for a connection on a listening socket.",
+        "fd -- connection-fd peer-address"
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.local-address@",
+        word_socket_local_address_read,
+        "Get the address a socket is bound to.",
+        "fd -- address"
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.nodelay!",
+        word_socket_nodelay_write,
+        "Turn Nagle's algorithm off or on for a TCP connection.",
+        "flag fd -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.nodelay@",
+        word_socket_nodelay_read,
+        "Check if Nagle's algorithm is off for a TCP connection.",
+        "fd -- flag"
+    );
+
+    add_native_word!(
+        interpreter,
+        "udp.bind",
+        word_udp_bind,
+        "Create a UDP socket bound to a host:port.",
+        "address options -- fd"
+    );
+
+    add_native_word!(
+        interpreter,
+        "udp.send-to",
+        word_udp_send_to,
+        "Send count bytes from a buffer's position as a datagram.",
+        "buffer count address fd -- sent"
+    );
+
+    add_native_word!(
+        interpreter,
+        "udp.recv-from",
+        word_udp_recv_from,
+        "Receive a datagram into a buffer at it's position.",
+        "buffer fd -- count sender-address"
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.nonblocking!",
+        word_file_nonblocking_write,
+        "Switch an fd between blocking and non-blocking reads.",
+        "flag fd -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.nonblocking@",
+        word_file_nonblocking_read,
+        "Check if an fd is in non-blocking mode.",
+        "fd -- flag"
+    );
+
+    add_native_word!(
+        interpreter,
+        "io.poll",
+        word_io_poll,
+        "Wait until some of an array of [ fd , interest ] pairs are ready.",
+        "requests timeout -- ready"
+    );
+
     add_native_word!(
         interpreter,
         "file.size@",
@@ -571,8 +2205,72 @@ pub fn register_io_words(interpreter: &mut dyn Interpreter) {
         interpreter,
         "file.@",
         word_file_read,
-        "Read from a given file.  (Unimplemented.)",
-        " -- "
+        "Read up to count bytes from a file into a new buffer.",
+        "count fd -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.read-into",
+        word_file_read_into,
+        "Fill a buffer from it's position, returning the number of bytes read.",
+        "buffer fd -- count"
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.write-buffer",
+        word_file_write_buffer,
+        "Write count bytes from a buffer's position.",
+        "buffer count fd -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.seek",
+        word_file_seek,
+        "Move a file's cursor relative to the file.seek/ origin.",
+        "offset whence fd -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.tell",
+        word_file_tell,
+        "Get the position of a file's cursor.",
+        "fd -- position"
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.truncate",
+        word_file_truncate,
+        "Cut down or extend a file to the given size.",
+        "size fd -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.sync",
+        word_file_sync,
+        "Make sure all writes to a file have reached the disk.",
+        "fd -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.read-all",
+        word_file_read_all,
+        "Read the rest of a file into a new buffer.",
+        "fd -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.write-all",
+        word_file_write_all,
+        "Write the entire contents of a buffer.",
+        "buffer fd -- "
     );
 
     add_native_word!(
@@ -647,6 +2345,138 @@ pub fn register_io_words(interpreter: &mut dyn Interpreter) {
         "Constant for opening a file for both reading and writing.",
         " -- flag"
     );
+
+    add_native_word!(
+        interpreter,
+        "file.auto-delete",
+        |interpreter| {
+            interpreter.push(FILE_AUTO_DELETE.to_value());
+            Ok(())
+        },
+        "Flag for deleting a temporary file or directory when it's closed or on reset.",
+        " -- flag"
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.seek/start",
+        |interpreter| {
+            interpreter.push(0_i64.to_value());
+            Ok(())
+        },
+        "Constant for seeking relative to the start of a file.",
+        " -- whence"
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.seek/current",
+        |interpreter| {
+            interpreter.push(1_i64.to_value());
+            Ok(())
+        },
+        "Constant for seeking relative to the current position in a file.",
+        " -- whence"
+    );
+
+    add_native_word!(
+        interpreter,
+        "file.seek/end",
+        |interpreter| {
+            interpreter.push(2_i64.to_value());
+            Ok(())
+        },
+        "Constant for seeking relative to the end of a file.",
+        " -- whence"
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.shut/r",
+        |interpreter| {
+            interpreter.push(0b0001_i64.to_value());
+            Ok(())
+        },
+        "Constant for shutting down the reading half of a socket.",
+        " -- how"
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.shut/w",
+        |interpreter| {
+            interpreter.push(0b0010_i64.to_value());
+            Ok(())
+        },
+        "Constant for shutting down the writing half of a socket.",
+        " -- how"
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.shut/rw",
+        |interpreter| {
+            interpreter.push(0b0011_i64.to_value());
+            Ok(())
+        },
+        "Constant for shutting down both halves of a socket.",
+        " -- how"
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.opt/reuse-addr",
+        |interpreter| {
+            interpreter.push(SOCKET_REUSE_ADDR.to_value());
+            Ok(())
+        },
+        "Option for reusing an address, or replacing a stale Unix socket file.",
+        " -- option"
+    );
+
+    add_native_word!(
+        interpreter,
+        "socket.opt/nodelay",
+        |interpreter| {
+            interpreter.push(SOCKET_NODELAY.to_value());
+            Ok(())
+        },
+        "Option for turning off Nagle's algorithm on accepted TCP connections.",
+        " -- option"
+    );
+
+    add_native_word!(
+        interpreter,
+        "io.poll/read",
+        |interpreter| {
+            interpreter.push(POLL_READ.to_value());
+            Ok(())
+        },
+        "Poll flag for an fd that has something to read.",
+        " -- flag"
+    );
+
+    add_native_word!(
+        interpreter,
+        "io.poll/write",
+        |interpreter| {
+            interpreter.push(POLL_WRITE.to_value());
+            Ok(())
+        },
+        "Poll flag for an fd that can be written to.",
+        " -- flag"
+    );
+
+    add_native_word!(
+        interpreter,
+        "io.poll/hangup",
+        |interpreter| {
+            interpreter.push(POLL_HANGUP.to_value());
+            Ok(())
+        },
+        "Poll flag for an fd that was hung up on or has an error.",
+        " -- flag"
+    );
 }
 
 #[cfg(test)]
@@ -0,0 +1,608 @@
+use crate::{
+    add_native_word,
+    runtime::{
+        data_structures::{
+            value::{ToValue, Value},
+            value_hash::ValueHash,
+            value_vec::ValueVec,
+        },
+        error::{self, script_error},
+        interpreter::Interpreter,
+    },
+};
+use std::fmt::Write;
+
+/// How deeply values can be nested before writing or parsing gives up.  This protects against
+/// cyclic data when writing and against malicious input when parsing.
+const MAX_DEPTH: usize = 512;
+
+/// Convert a value into JSON text.  If an indent is given the output is pretty printed with that
+/// many spaces per nesting level, otherwise the output is kept compact.
+///
+/// Hash table keys are written out in sorted order so that the output is stable between runs.
+/// Structures are written as objects using their field names, in the order the fields were defined.
+pub fn value_to_json(value: &Value, indent: Option<usize>) -> Result<String, String> {
+    let mut output = String::new();
+
+    write_json_value(&mut output, value, indent, 0)?;
+    Ok(output)
+}
+
+/// Parse JSON text into a value.  Objects become hash tables, arrays become arrays, and null
+/// becomes none.  Numbers without a fraction or exponent are read as integers when they fit.
+pub fn json_to_value(text: &str) -> Result<Value, String> {
+    let mut parser = JsonParser::new(text);
+
+    parser.skip_whitespace();
+    let value = parser.read_value(0)?;
+    parser.skip_whitespace();
+
+    if !parser.is_eos() {
+        return Err(parser.error("Unexpected text after the JSON value."));
+    }
+
+    Ok(value)
+}
+
+/// Write out the newline and indentation for the given nesting depth, if pretty printing.
+fn write_json_indent(output: &mut String, indent: Option<usize>, depth: usize) {
+    if let Some(indent) = indent {
+        output.push('\n');
+        output.push_str(&" ".repeat(indent * depth));
+    }
+}
+
+/// Write a string out as a quoted and escaped JSON string.
+fn write_json_string(output: &mut String, text: &str) {
+    output.push('"');
+
+    for next in text.chars() {
+        match next {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\u{08}' => output.push_str("\\b"),
+            '\u{0c}' => output.push_str("\\f"),
+            next if (next as u32) < 0x20 => {
+                let _ = write!(output, "\\u{:04x}", next as u32);
+            }
+            next => output.push(next),
+        }
+    }
+
+    output.push('"');
+}
+
+/// Write out a list of key/value pairs as a JSON object.
+fn write_json_object(
+    output: &mut String,
+    members: &[(String, Value)],
+    indent: Option<usize>,
+    depth: usize,
+) -> Result<(), String> {
+    if members.is_empty() {
+        output.push_str("{}");
+        return Ok(());
+    }
+
+    output.push('{');
+
+    for (index, (key, value)) in members.iter().enumerate() {
+        if index > 0 {
+            output.push(',');
+        }
+
+        write_json_indent(output, indent, depth + 1);
+        write_json_string(output, key);
+        output.push_str(if indent.is_some() { ": " } else { ":" });
+        write_json_value(output, value, indent, depth + 1)?;
+    }
+
+    write_json_indent(output, indent, depth);
+    output.push('}');
+
+    Ok(())
+}
+
+/// Write out a single value, recursing into any sub-values as needed.
+fn write_json_value(
+    output: &mut String,
+    value: &Value,
+    indent: Option<usize>,
+    depth: usize,
+) -> Result<(), String> {
+    if depth > MAX_DEPTH {
+        return Err("Value is nested too deeply to write as JSON.".to_string());
+    }
+
+    match value {
+        Value::None => output.push_str("null"),
+        Value::Int(value) => {
+            let _ = write!(output, "{}", value);
+        }
+        Value::Float(value) => {
+            if !value.is_finite() {
+                return Err(format!(
+                    "The value {} can not be represented in JSON.",
+                    value
+                ));
+            }
+
+            // Debug formatting keeps the fractional part, so floats read back in as floats.
+            let _ = write!(output, "{:?}", value);
+        }
+        Value::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+        Value::String(text) => write_json_string(output, text),
+
+        Value::Vec(vec_ptr) => {
+            let vec = vec_ptr.borrow();
+
+            if vec.is_empty() {
+                output.push_str("[]");
+                return Ok(());
+            }
+
+            output.push('[');
+
+            for (index, item) in vec.iter().enumerate() {
+                if index > 0 {
+                    output.push(',');
+                }
+
+                write_json_indent(output, indent, depth + 1);
+                write_json_value(output, item, indent, depth + 1)?;
+            }
+
+            write_json_indent(output, indent, depth);
+            output.push(']');
+        }
+
+        Value::HashMap(hash_ptr) => {
+            let mut members: Vec<(String, Value)> = hash_ptr
+                .borrow()
+                .iter()
+                .map(|(key, value)| {
+                    let key = if key.is_string() {
+                        key.get_string_val()
+                    } else {
+                        key.to_string()
+                    };
+
+                    (key, value.clone())
+                })
+                .collect();
+
+            members.sort_by(|a, b| a.0.cmp(&b.0));
+            write_json_object(output, &members, indent, depth)?;
+        }
+
+        Value::DataObject(data_ptr) => {
+            let members: Vec<(String, Value)> = {
+                let data = data_ptr.borrow();
+                let definition = data.definition_ptr.borrow();
+
+                definition
+                    .field_names()
+                    .iter()
+                    .cloned()
+                    .zip(data.fields.iter().cloned())
+                    .collect()
+            };
+
+            write_json_object(output, &members, indent, depth)?;
+        }
+
+        Value::ByteBuffer(_) | Value::Regex(_) | Value::Token(_) | Value::Code(_) => {
+            return Err(format!("Unsupported json value type for value {}.", value));
+        }
+    }
+
+    Ok(())
+}
+
+/// Simple recursive descent parser for JSON text.  Keeps track of it's position within the source
+/// so that errors can be reported with a line and column.
+struct JsonParser {
+    source: Vec<char>,
+    index: usize,
+}
+
+impl JsonParser {
+    /// Create a new parser for the given text.
+    fn new(text: &str) -> JsonParser {
+        JsonParser {
+            source: text.chars().collect(),
+            index: 0,
+        }
+    }
+
+    /// Create an error message that includes the line and column of the current position.
+    fn error(&self, message: &str) -> String {
+        let mut line = 1;
+        let mut column = 1;
+
+        for next in self.source.iter().take(self.index) {
+            if *next == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+
+        format!(
+            "JSON error at line {}, column {}: {}",
+            line, column, message
+        )
+    }
+
+    /// Have we reached the end of the source text?
+    fn is_eos(&self) -> bool {
+        self.index >= self.source.len()
+    }
+
+    /// Look at the next character without consuming it.
+    fn peek(&self) -> Option<char> {
+        self.source.get(self.index).copied()
+    }
+
+    /// Consume and return the next character.
+    fn next(&mut self) -> Option<char> {
+        let next = self.peek();
+
+        if next.is_some() {
+            self.index += 1;
+        }
+
+        next
+    }
+
+    /// Skip past any whitespace in the source.
+    fn skip_whitespace(&mut self) {
+        while let Some(' ' | '\t' | '\n' | '\r') = self.peek() {
+            self.index += 1;
+        }
+    }
+
+    /// Make sure that the next character is the expected one.
+    fn expect_char(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(found) if found == expected => {
+                self.index += 1;
+                Ok(())
+            }
+            Some(found) => Err(self.error(&format!(
+                "Expected the character '{}', found '{}'.",
+                expected, found
+            ))),
+            None => Err(self.error(&format!(
+                "Expected the character '{}', found the end of the text.",
+                expected
+            ))),
+        }
+    }
+
+    /// Make sure that the next characters are the expected keyword.
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), String> {
+        for expected in keyword.chars() {
+            self.expect_char(expected)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read any JSON value from the current position.
+    fn read_value(&mut self, depth: usize) -> Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("The JSON text is nested too deeply."));
+        }
+
+        match self.peek() {
+            Some('{') => self.read_object(depth),
+            Some('[') => self.read_array(depth),
+            Some('"') => Ok(self.read_string()?.to_value()),
+            Some('t') => {
+                self.expect_keyword("true")?;
+                Ok(true.to_value())
+            }
+            Some('f') => {
+                self.expect_keyword("false")?;
+                Ok(false.to_value())
+            }
+            Some('n') => {
+                self.expect_keyword("null")?;
+                Ok(Value::None)
+            }
+            Some('-' | '0'..='9') => self.read_number(),
+            Some(found) => Err(self.error(&format!("Unexpected character '{}'.", found))),
+            None => Err(self.error("Unexpected end of the JSON text.")),
+        }
+    }
+
+    /// Read an object into a new hash table.
+    fn read_object(&mut self, depth: usize) -> Result<Value, String> {
+        let hash = ValueHash::new();
+
+        self.expect_char('{')?;
+        self.skip_whitespace();
+
+        if self.peek() == Some('}') {
+            self.index += 1;
+            return Ok(hash.to_value());
+        }
+
+        loop {
+            self.skip_whitespace();
+
+            if self.peek() != Some('"') {
+                return Err(self.error("Expected a string key for the object member."));
+            }
+
+            let key = self.read_string()?;
+
+            self.skip_whitespace();
+            self.expect_char(':')?;
+            self.skip_whitespace();
+
+            let value = self.read_value(depth + 1)?;
+
+            hash.borrow_mut().insert(key.to_value(), value);
+
+            self.skip_whitespace();
+
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => break,
+                _ => {
+                    self.index = self.index.saturating_sub(1);
+                    return Err(self.error("Expected ',' or '}' in the object."));
+                }
+            }
+        }
+
+        Ok(hash.to_value())
+    }
+
+    /// Read an array of values.
+    fn read_array(&mut self, depth: usize) -> Result<Value, String> {
+        let mut values = Vec::new();
+
+        self.expect_char('[')?;
+        self.skip_whitespace();
+
+        if self.peek() == Some(']') {
+            self.index += 1;
+            return Ok(ValueVec::from_vec(values).to_value());
+        }
+
+        loop {
+            self.skip_whitespace();
+            values.push(self.read_value(depth + 1)?);
+            self.skip_whitespace();
+
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => break,
+                _ => {
+                    self.index = self.index.saturating_sub(1);
+                    return Err(self.error("Expected ',' or ']' in the array."));
+                }
+            }
+        }
+
+        Ok(ValueVec::from_vec(values).to_value())
+    }
+
+    /// Read the four hex digits of a \u escape.
+    fn read_hex4(&mut self) -> Result<u32, String> {
+        let mut code = 0;
+
+        for _ in 0..4 {
+            match self.next().and_then(|next| next.to_digit(16)) {
+                Some(digit) => code = code * 16 + digit,
+                None => {
+                    self.index = self.index.saturating_sub(1);
+                    return Err(self.error("Invalid \\u escape sequence."));
+                }
+            }
+        }
+
+        Ok(code)
+    }
+
+    /// Read a quoted string, processing any escape sequences.
+    fn read_string(&mut self) -> Result<String, String> {
+        let mut text = String::new();
+
+        self.expect_char('"')?;
+
+        loop {
+            match self.next() {
+                Some('"') => break,
+                Some('\\') => match self.next() {
+                    Some('"') => text.push('"'),
+                    Some('\\') => text.push('\\'),
+                    Some('/') => text.push('/'),
+                    Some('b') => text.push('\u{08}'),
+                    Some('f') => text.push('\u{0c}'),
+                    Some('n') => text.push('\n'),
+                    Some('r') => text.push('\r'),
+                    Some('t') => text.push('\t'),
+                    Some('u') => {
+                        let mut code = self.read_hex4()?;
+
+                        // Characters outside of the basic plane are encoded as surrogate pairs.
+                        if (0xd800..0xdc00).contains(&code) {
+                            if self.next() != Some('\\') || self.next() != Some('u') {
+                                return Err(self.error("Expected a low surrogate escape."));
+                            }
+
+                            let low = self.read_hex4()?;
+
+                            if !(0xdc00..0xe000).contains(&low) {
+                                return Err(self.error("Invalid low surrogate escape."));
+                            }
+
+                            code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                        }
+
+                        match char::from_u32(code) {
+                            Some(next) => text.push(next),
+                            None => return Err(self.error("Invalid unicode code point.")),
+                        }
+                    }
+                    Some(found) => {
+                        self.index -= 1;
+                        return Err(self.error(&format!("Invalid escape sequence '\\{}'.", found)));
+                    }
+                    None => return Err(self.error("Unexpected end of the JSON text in string.")),
+                },
+                Some(next) if (next as u32) < 0x20 => {
+                    self.index -= 1;
+                    return Err(self.error("Unescaped control character in string."));
+                }
+                Some(next) => text.push(next),
+                None => return Err(self.error("Unexpected end of the JSON text in string.")),
+            }
+        }
+
+        Ok(text)
+    }
+
+    /// Read a number.  Integers are kept as integers if they fit, everything else becomes a float.
+    fn read_number(&mut self) -> Result<Value, String> {
+        let start = self.index;
+        let mut is_float = false;
+
+        fn skip_digits(parser: &mut JsonParser) -> usize {
+            let start = parser.index;
+
+            while let Some('0'..='9') = parser.peek() {
+                parser.index += 1;
+            }
+
+            parser.index - start
+        }
+
+        if self.peek() == Some('-') {
+            self.index += 1;
+        }
+
+        if skip_digits(self) == 0 {
+            return Err(self.error("Expected a digit in number."));
+        }
+
+        if self.peek() == Some('.') {
+            is_float = true;
+            self.index += 1;
+
+            if skip_digits(self) == 0 {
+                return Err(self.error("Expected a digit after the decimal point."));
+            }
+        }
+
+        if let Some('e' | 'E') = self.peek() {
+            is_float = true;
+            self.index += 1;
+
+            if let Some('+' | '-') = self.peek() {
+                self.index += 1;
+            }
+
+            if skip_digits(self) == 0 {
+                return Err(self.error("Expected a digit in the exponent."));
+            }
+        }
+
+        let text: String = self.source[start..self.index].iter().collect();
+
+        if !is_float && let Ok(value) = text.parse::<i64>() {
+            return Ok(value.to_value());
+        }
+
+        match text.parse::<f64>() {
+            Ok(value) => Ok(value.to_value()),
+            Err(_) => Err(self.error(&format!("Invalid number '{}'.", text))),
+        }
+    }
+}
+
+/// Parse a JSON string into a value.  Any JSON value is accepted at the top level.
+///
+/// Signature: `json_string -- value`
+fn word_json_parse(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let text = interpreter.pop_as_string()?;
+
+    match json_to_value(&text) {
+        Ok(value) => interpreter.push(value),
+        Err(message) => script_error(interpreter, message)?,
+    }
+
+    Ok(())
+}
+
+/// Convert a value into a compact JSON string.
+///
+/// Signature: `value -- json_string`
+fn word_json_stringify(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let value = interpreter.pop()?;
+
+    match value_to_json(&value, None) {
+        Ok(text) => interpreter.push(text.to_value()),
+        Err(message) => script_error(interpreter, message)?,
+    }
+
+    Ok(())
+}
+
+/// Convert a value into a pretty printed JSON string, indenting each level by the given number of
+/// spaces.
+///
+/// Signature: `value indent -- json_string`
+fn word_json_stringify_pretty(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let indent = interpreter.pop_as_int()?;
+    let value = interpreter.pop()?;
+
+    if indent < 0 {
+        return script_error(
+            interpreter,
+            format!("JSON indent {} can not be negative.", indent),
+        );
+    }
+
+    match value_to_json(&value, Some(indent as usize)) {
+        Ok(text) => interpreter.push(text.to_value()),
+        Err(message) => script_error(interpreter, message)?,
+    }
+
+    Ok(())
+}
+
+/// Register the JSON words with the interpreter.
+pub fn register_json_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "json.parse",
+        word_json_parse,
+        "Parse a JSON string into a value.  Any JSON value is accepted at the top level.",
+        "json_string -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "json.stringify",
+        word_json_stringify,
+        "Convert a value into a compact JSON string.",
+        "value -- json_string"
+    );
+
+    add_native_word!(
+        interpreter,
+        "json.stringify-pretty",
+        word_json_stringify_pretty,
+        "Convert a value into a JSON string, indenting each level by the given number of spaces.",
+        "value indent -- json_string"
+    );
+}
@@ -10,5 +10,41 @@ pub mod terminal_words;
 /// Words that access the user's environment.
 pub mod user_words;
 
+/// Words that inspect and change the file system, and work with paths.
+pub mod filesystem_words;
+
+/// Words that run and manage child processes.
+pub mod process_words;
+
+/// Words that make and serve HTTP requests.
+pub mod http_words;
+
+/// Words that read clocks, sleep, and work with dates and times.
+pub mod time_words;
+
+/// Words that generate seedable pseudo-random numbers, and random bytes from the OS.
+pub mod random_words;
+
 /// Words that interface with foreign functions.
 pub mod ffi_words;
+
+/// Generates ffi bindings from C headers.
+pub mod ffi_header;
+
+/// Words that work with regular expressions.
+pub mod regex_words;
+
+/// Words that read and write JSON.
+pub mod json_words;
+
+/// Words that read and write CSV data.
+pub mod csv_words;
+
+/// Words that serialize values to and from MessagePack.
+pub mod msgpack_words;
+
+/// Words that pack and unpack structures using binary layouts.
+pub mod binary_layout_words;
+
+/// Words that encode, decode, and hash buffers and strings.
+pub mod encoding_words;
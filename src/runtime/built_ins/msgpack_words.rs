@@ -0,0 +1,549 @@
+use crate::{
+    add_native_word,
+    runtime::{
+        data_structures::{
+            byte_buffer::ByteBuffer,
+            data_object::DataObject,
+            value::{ToValue, Value},
+            value_hash::ValueHash,
+            value_vec::ValueVec,
+        },
+        error::{self, script_error},
+        interpreter::Interpreter,
+    },
+};
+
+/// The MessagePack extension type used to hold structures.  The payload is an encoded array of the
+/// structure's definition name followed by a map of it's field names to values.
+const STRUCTURE_EXT_TYPE: i8 = 1;
+
+/// How deeply values can be nested before encoding or decoding gives up.  This protects against
+/// cyclic data when encoding and against malicious input when decoding.
+const MAX_DEPTH: usize = 512;
+
+/// Write a MessagePack type marker followed by a big-endian length using the smallest of the 8, 16,
+/// or 32 bit forms.
+fn write_length(output: &mut Vec<u8>, markers: [u8; 3], length: usize) -> Result<(), String> {
+    if length <= u8::MAX as usize {
+        output.push(markers[0]);
+        output.push(length as u8);
+    } else if length <= u16::MAX as usize {
+        output.push(markers[1]);
+        output.extend_from_slice(&(length as u16).to_be_bytes());
+    } else if length <= u32::MAX as usize {
+        output.push(markers[2]);
+        output.extend_from_slice(&(length as u32).to_be_bytes());
+    } else {
+        return Err(format!("Length {} is too large for MessagePack.", length));
+    }
+
+    Ok(())
+}
+
+/// Write a string value.
+fn write_str(output: &mut Vec<u8>, text: &str) -> Result<(), String> {
+    let bytes = text.as_bytes();
+
+    if bytes.len() < 32 {
+        output.push(0xa0 | bytes.len() as u8);
+    } else {
+        write_length(output, [0xd9, 0xda, 0xdb], bytes.len())?;
+    }
+
+    output.extend_from_slice(bytes);
+    Ok(())
+}
+
+/// Write the header for an array or a map, these have no 8 bit length form.
+fn write_container_header(
+    output: &mut Vec<u8>,
+    fix_marker: u8,
+    markers: [u8; 2],
+    length: usize,
+) -> Result<(), String> {
+    if length < 16 {
+        output.push(fix_marker | length as u8);
+    } else if length <= u16::MAX as usize {
+        output.push(markers[0]);
+        output.extend_from_slice(&(length as u16).to_be_bytes());
+    } else if length <= u32::MAX as usize {
+        output.push(markers[1]);
+        output.extend_from_slice(&(length as u32).to_be_bytes());
+    } else {
+        return Err(format!("Length {} is too large for MessagePack.", length));
+    }
+
+    Ok(())
+}
+
+/// Write an integer using the most compact form that can hold it.
+fn write_int(output: &mut Vec<u8>, value: i64) {
+    if (0..128).contains(&value) {
+        output.push(value as u8);
+    } else if (-32..0).contains(&value) {
+        output.push(value as i8 as u8);
+    } else if value >= 0 {
+        if value <= u8::MAX as i64 {
+            output.push(0xcc);
+            output.push(value as u8);
+        } else if value <= u16::MAX as i64 {
+            output.push(0xcd);
+            output.extend_from_slice(&(value as u16).to_be_bytes());
+        } else if value <= u32::MAX as i64 {
+            output.push(0xce);
+            output.extend_from_slice(&(value as u32).to_be_bytes());
+        } else {
+            output.push(0xcf);
+            output.extend_from_slice(&(value as u64).to_be_bytes());
+        }
+    } else if value >= i8::MIN as i64 {
+        output.push(0xd0);
+        output.push(value as i8 as u8);
+    } else if value >= i16::MIN as i64 {
+        output.push(0xd1);
+        output.extend_from_slice(&(value as i16).to_be_bytes());
+    } else if value >= i32::MIN as i64 {
+        output.push(0xd2);
+        output.extend_from_slice(&(value as i32).to_be_bytes());
+    } else {
+        output.push(0xd3);
+        output.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+/// Encode a value, and all of it's sub-values, as MessagePack.
+pub fn value_to_msgpack(output: &mut Vec<u8>, value: &Value, depth: usize) -> Result<(), String> {
+    if depth > MAX_DEPTH {
+        return Err("Value is nested too deeply to encode as MessagePack.".to_string());
+    }
+
+    match value {
+        Value::None => output.push(0xc0),
+        Value::Bool(value) => output.push(if *value { 0xc3 } else { 0xc2 }),
+        Value::Int(value) => write_int(output, *value),
+
+        Value::Float(value) => {
+            output.push(0xcb);
+            output.extend_from_slice(&value.to_be_bytes());
+        }
+
+        Value::String(text) => write_str(output, text)?,
+
+        Value::Vec(vec_ptr) => {
+            let vec = vec_ptr.borrow();
+
+            write_container_header(output, 0x90, [0xdc, 0xdd], vec.len())?;
+
+            for item in vec.iter() {
+                value_to_msgpack(output, item, depth + 1)?;
+            }
+        }
+
+        Value::HashMap(hash_ptr) => {
+            let hash = hash_ptr.borrow();
+
+            write_container_header(output, 0x80, [0xde, 0xdf], hash.len())?;
+
+            for (key, value) in hash.iter() {
+                value_to_msgpack(output, key, depth + 1)?;
+                value_to_msgpack(output, value, depth + 1)?;
+            }
+        }
+
+        Value::ByteBuffer(buffer_ptr) => {
+            let buffer = buffer_ptr.borrow();
+
+            buffer.check_bounds()?;
+            write_length(output, [0xc4, 0xc5, 0xc6], buffer.len())?;
+            output.extend_from_slice(&buffer.get_bytes(0, buffer.len()));
+        }
+
+        Value::DataObject(data_ptr) => {
+            let data = data_ptr.borrow();
+            let definition = data.definition_ptr.borrow();
+            let mut payload = Vec::new();
+
+            write_container_header(&mut payload, 0x90, [0xdc, 0xdd], 2)?;
+            write_str(&mut payload, definition.name())?;
+            write_container_header(&mut payload, 0x80, [0xde, 0xdf], data.fields.len())?;
+
+            for (name, field) in definition.field_names().iter().zip(data.fields.iter()) {
+                write_str(&mut payload, name)?;
+                value_to_msgpack(&mut payload, field, depth + 1)?;
+            }
+
+            write_length(output, [0xc7, 0xc8, 0xc9], payload.len())?;
+            output.push(STRUCTURE_EXT_TYPE as u8);
+            output.extend_from_slice(&payload);
+        }
+
+        Value::Regex(_) | Value::Token(_) | Value::Code(_) => {
+            return Err(format!(
+                "Value {} can not be encoded as MessagePack.",
+                value
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads MessagePack data from a slice of bytes, keeping track of the current offset so that errors
+/// can report where things went wrong.
+struct MsgPackReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> MsgPackReader<'a> {
+    /// Take the next count bytes from the input.
+    fn take(&mut self, count: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() - self.position < count {
+            return Err(format!(
+                "MessagePack data truncated at offset {}, needed {} more bytes.",
+                self.position, count
+            ));
+        }
+
+        let bytes = &self.bytes[self.position..self.position + count];
+
+        self.position += count;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    /// Read a UTF-8 string of the given byte length.
+    fn read_str(&mut self, length: usize) -> Result<String, String> {
+        let start = self.position;
+        let bytes = self.take(length)?;
+
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Ok(text.to_string()),
+            Err(_) => Err(format!(
+                "Invalid UTF-8 in MessagePack string at offset {}.",
+                start
+            )),
+        }
+    }
+
+    /// Read a count of items for an array or map, making sure that the count is at least plausible
+    /// for the remaining data so that bad input can't trigger huge allocations.
+    fn check_count(&self, count: usize) -> Result<usize, String> {
+        if count > self.bytes.len() - self.position {
+            return Err(format!(
+                "MessagePack container at offset {} claims {} items, more than the remaining data.",
+                self.position, count
+            ));
+        }
+
+        Ok(count)
+    }
+
+    /// Read a single value, recursing into any sub-values.
+    fn read_value(&mut self, interpreter: &dyn Interpreter, depth: usize) -> Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err("MessagePack data is nested too deeply.".to_string());
+        }
+
+        let start = self.position;
+        let marker = self.read_u8()?;
+
+        let value = match marker {
+            0x00..=0x7f => (marker as i64).to_value(),
+            0xe0..=0xff => (marker as i8 as i64).to_value(),
+
+            0xc0 => Value::None,
+            0xc2 => false.to_value(),
+            0xc3 => true.to_value(),
+
+            0xcc => (self.read_u8()? as i64).to_value(),
+            0xcd => (self.read_u16()? as i64).to_value(),
+            0xce => (self.read_u32()? as i64).to_value(),
+            0xcf => {
+                let value = self.read_u64()?;
+
+                match i64::try_from(value) {
+                    Ok(value) => value.to_value(),
+                    Err(_) => {
+                        return Err(format!(
+                            "MessagePack integer {} at offset {} is too large.",
+                            value, start
+                        ));
+                    }
+                }
+            }
+
+            0xd0 => (self.read_u8()? as i8 as i64).to_value(),
+            0xd1 => (self.read_u16()? as i16 as i64).to_value(),
+            0xd2 => (self.read_u32()? as i32 as i64).to_value(),
+            0xd3 => (self.read_u64()? as i64).to_value(),
+
+            0xca => (f32::from_bits(self.read_u32()?) as f64).to_value(),
+            0xcb => f64::from_bits(self.read_u64()?).to_value(),
+
+            0xa0..=0xbf => self.read_str((marker & 0x1f) as usize)?.to_value(),
+            0xd9 => {
+                let length = self.read_u8()? as usize;
+                self.read_str(length)?.to_value()
+            }
+            0xda => {
+                let length = self.read_u16()? as usize;
+                self.read_str(length)?.to_value()
+            }
+            0xdb => {
+                let length = self.read_u32()? as usize;
+                self.read_str(length)?.to_value()
+            }
+
+            0xc4..=0xc6 => {
+                let length = match marker {
+                    0xc4 => self.read_u8()? as usize,
+                    0xc5 => self.read_u16()? as usize,
+                    _ => self.read_u32()? as usize,
+                };
+
+                let bytes = self.take(length)?;
+                let buffer = ByteBuffer::new_ptr(length);
+
+                buffer.borrow_mut().buffer_mut().copy_from_slice(bytes);
+                buffer.to_value()
+            }
+
+            0x90..=0x9f | 0xdc | 0xdd => {
+                let count = match marker {
+                    0xdc => self.read_u16()? as usize,
+                    0xdd => self.read_u32()? as usize,
+                    _ => (marker & 0x0f) as usize,
+                };
+
+                let count = self.check_count(count)?;
+                let mut items = Vec::with_capacity(count);
+
+                for _ in 0..count {
+                    items.push(self.read_value(interpreter, depth + 1)?);
+                }
+
+                ValueVec::from_vec(items).to_value()
+            }
+
+            0x80..=0x8f | 0xde | 0xdf => {
+                let count = match marker {
+                    0xde => self.read_u16()? as usize,
+                    0xdf => self.read_u32()? as usize,
+                    _ => (marker & 0x0f) as usize,
+                };
+
+                let count = self.check_count(count)?;
+                let hash = ValueHash::new();
+
+                for _ in 0..count {
+                    let key = self.read_value(interpreter, depth + 1)?;
+                    let value = self.read_value(interpreter, depth + 1)?;
+
+                    hash.borrow_mut().insert(key, value);
+                }
+
+                hash.to_value()
+            }
+
+            0xd4..=0xd8 | 0xc7..=0xc9 => {
+                let length = match marker {
+                    0xd4 => 1,
+                    0xd5 => 2,
+                    0xd6 => 4,
+                    0xd7 => 8,
+                    0xd8 => 16,
+                    0xc7 => self.read_u8()? as usize,
+                    0xc8 => self.read_u16()? as usize,
+                    _ => self.read_u32()? as usize,
+                };
+
+                let ext_type = self.read_u8()? as i8;
+
+                if ext_type != STRUCTURE_EXT_TYPE {
+                    return Err(format!(
+                        "Unsupported MessagePack extension type {} at offset {}.",
+                        ext_type, start
+                    ));
+                }
+
+                let end = self.position + length;
+                let value = self.read_structure(interpreter, depth)?;
+
+                if self.position != end {
+                    return Err(format!(
+                        "MessagePack structure at offset {} has an invalid length.",
+                        start
+                    ));
+                }
+
+                value
+            }
+
+            _ => {
+                return Err(format!(
+                    "Invalid MessagePack marker 0x{:02x} at offset {}.",
+                    marker, start
+                ));
+            }
+        };
+
+        Ok(value)
+    }
+
+    /// Read the payload of a structure extension.  If a structure with the same name is defined the
+    /// structure is rebuilt, with any fields not found in the data keeping their defaults.
+    /// Otherwise the fields are returned as a hash table.
+    fn read_structure(
+        &mut self,
+        interpreter: &dyn Interpreter,
+        depth: usize,
+    ) -> Result<Value, String> {
+        let start = self.position;
+        let payload = self.read_value(interpreter, depth + 1)?;
+
+        let (name, fields) = match &payload {
+            Value::Vec(items) if items.borrow().len() == 2 => {
+                let items = items.borrow();
+                let mut iter = items.iter();
+
+                match (iter.next(), iter.next()) {
+                    (Some(Value::String(name)), Some(Value::HashMap(fields))) => {
+                        (name.clone(), fields.clone())
+                    }
+                    _ => {
+                        return Err(format!(
+                            "Invalid MessagePack structure payload at offset {}.",
+                            start
+                        ));
+                    }
+                }
+            }
+
+            _ => {
+                return Err(format!(
+                    "Invalid MessagePack structure payload at offset {}.",
+                    start
+                ));
+            }
+        };
+
+        let definition = interpreter
+            .structure_definitions()
+            .iter()
+            .filter(|definition| *definition.borrow().name() == name)
+            .last()
+            .cloned();
+
+        let Some(definition) = definition else {
+            return Ok(fields.to_value());
+        };
+
+        let data_ptr = DataObject::new(&definition);
+
+        {
+            let definition = definition.borrow();
+            let fields = fields.borrow();
+            let mut data = data_ptr.borrow_mut();
+
+            for (index, field_name) in definition.field_names().iter().enumerate() {
+                if let Some(value) = fields.get(&field_name.to_value()) {
+                    data.fields[index] = value.clone();
+                }
+            }
+        }
+
+        Ok(data_ptr.to_value())
+    }
+}
+
+/// Decode a single MessagePack value from the bytes, returning the value and the number of bytes
+/// that were used.
+pub fn msgpack_to_value(
+    interpreter: &dyn Interpreter,
+    bytes: &[u8],
+) -> Result<(Value, usize), String> {
+    let mut reader = MsgPackReader { bytes, position: 0 };
+    let value = reader.read_value(interpreter, 0)?;
+
+    Ok((value, reader.position))
+}
+
+/// Encode a value and all of it's sub-values into a new byte buffer as MessagePack.
+///
+/// Signature: `value -- buffer`
+fn word_value_to_msgpack(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let value = interpreter.pop()?;
+    let mut bytes = Vec::new();
+
+    if let Err(message) = value_to_msgpack(&mut bytes, &value, 0) {
+        return script_error(interpreter, message);
+    }
+
+    let buffer = ByteBuffer::new_ptr(bytes.len());
+
+    buffer.borrow_mut().buffer_mut().copy_from_slice(&bytes);
+    interpreter.push(buffer.to_value());
+
+    Ok(())
+}
+
+/// Decode a MessagePack value from a byte buffer.  Decoding starts at the buffer's current position
+/// and the position is moved past the decoded value, so multiple values can be read in a row.
+///
+/// Signature: `buffer -- value`
+fn word_value_from_msgpack(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let buffer_ptr = interpreter.pop_as_byte_buffer()?;
+
+    let result = {
+        let buffer = buffer_ptr.borrow();
+        let bytes = buffer.get_bytes(buffer.position(), buffer.len() - buffer.position());
+
+        msgpack_to_value(interpreter, &bytes)
+    };
+
+    match result {
+        Ok((value, used)) => {
+            buffer_ptr.borrow_mut().increment_position(used);
+            interpreter.push(value);
+        }
+
+        Err(message) => script_error(interpreter, message)?,
+    }
+
+    Ok(())
+}
+
+/// Register the MessagePack serialization words.
+pub fn register_msgpack_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "value.to-msgpack",
+        word_value_to_msgpack,
+        "Encode a value and all of it's sub-values into a new byte buffer as MessagePack.",
+        "value -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "value.from-msgpack",
+        word_value_from_msgpack,
+        "Decode a MessagePack value starting at the buffer's current position.",
+        "buffer -- value"
+    );
+}
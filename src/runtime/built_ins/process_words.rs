@@ -0,0 +1,526 @@
+use crate::{
+    add_native_word,
+    runtime::{
+        built_ins::io_words::{ProcessPipe, add_process_pipe, close_process_pipe, fd_to_stdio},
+        data_structures::{
+            value::{ToValue, Value},
+            value_hash::ValueHashPtr,
+        },
+        error::{self, script_error, script_error_str},
+        interpreter::Interpreter,
+    },
+};
+use lazy_static::lazy_static;
+use std::{
+    collections::HashMap,
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{
+        Mutex,
+        atomic::{AtomicI64, Ordering},
+    },
+};
+
+/// A spawned child process along with the fds of it's pipes, none if that stream wasn't piped.
+/// Once the process has been waited on it's exit code is kept so that later waits return it
+/// again.
+struct ProcessEntry {
+    child: Child,
+    stdin: Option<i64>,
+    stdout: Option<i64>,
+    stderr: Option<i64>,
+    exit_code: Option<i64>,
+}
+
+lazy_static! {
+    // The counter for generating new process handles.
+    static ref HANDLE_COUNTER: AtomicI64 = AtomicI64::new(1);
+
+    // Keep a table to map process handles to the spawned child processes.
+    static ref PROCESS_TABLE: Mutex<HashMap<i64, ProcessEntry>> = Mutex::new(HashMap::new());
+}
+
+/// Run an operation on a spawned process's entry in the process table.
+fn with_process<T>(
+    interpreter: &mut dyn Interpreter,
+    handle: i64,
+    operation: impl FnOnce(&mut ProcessEntry) -> T,
+) -> error::Result<T> {
+    let result = PROCESS_TABLE
+        .lock()
+        .unwrap()
+        .get_mut(&handle)
+        .map(operation);
+
+    match result {
+        Some(value) => Ok(value),
+        None => script_error(interpreter, format!("Process handle {} not found.", handle)),
+    }
+}
+
+/// Convert an exit status to a script exit code.  A process killed by a signal has the negated
+/// signal number as it's exit code.
+fn exit_code(status: ExitStatus) -> i64 {
+    if let Some(code) = status.code() {
+        return code as i64;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            return -(signal as i64);
+        }
+    }
+
+    -1
+}
+
+/// Build a command from an array of the program and it's arguments.
+fn pop_command(interpreter: &mut dyn Interpreter) -> error::Result<(String, Command)> {
+    let array = interpreter.pop_as_array()?;
+    let mut arguments = Vec::new();
+
+    for value in array.borrow().iter() {
+        if !value.is_stringable() {
+            return script_error(
+                interpreter,
+                format!("Command arguments must be strings, found {}.", value),
+            );
+        }
+
+        arguments.push(value.get_string_val());
+    }
+
+    let Some((program, arguments)) = arguments.split_first() else {
+        return script_error_str(interpreter, "The command array is empty.");
+    };
+
+    let mut command = Command::new(program);
+
+    command.args(arguments);
+
+    Ok((program.clone(), command))
+}
+
+/// Pop the options hash table, none means that all of the defaults are used.
+fn pop_options(interpreter: &mut dyn Interpreter) -> error::Result<Option<ValueHashPtr>> {
+    let options = interpreter.pop()?;
+
+    match options {
+        Value::None => Ok(None),
+        Value::HashMap(hash) => Ok(Some(hash)),
+        _ => script_error(
+            interpreter,
+            format!(
+                "Expected a hash table of process options, found {}.",
+                options
+            ),
+        ),
+    }
+}
+
+fn get_option(options: &Option<ValueHashPtr>, key: &str) -> Option<Value> {
+    options
+        .as_ref()
+        .and_then(|hash| hash.borrow().get(&key.to_string().to_value()).cloned())
+}
+
+/// Apply the env, clear-env and cwd options to the command.  The env option is a hash table of
+/// variables to set, a value of none removes the variable instead.
+fn apply_environment(
+    interpreter: &mut dyn Interpreter,
+    command: &mut Command,
+    options: &Option<ValueHashPtr>,
+) -> error::Result<()> {
+    if let Some(clear) = get_option(options, "clear-env")
+        && clear.get_bool_val()
+    {
+        command.env_clear();
+    }
+
+    match get_option(options, "env") {
+        None | Some(Value::None) => {}
+
+        Some(Value::HashMap(env)) => {
+            for (name, value) in env.borrow().iter() {
+                match value {
+                    Value::None => command.env_remove(name.get_string_val()),
+                    _ => command.env(name.get_string_val(), value.get_string_val()),
+                };
+            }
+        }
+
+        Some(env) => {
+            return script_error(
+                interpreter,
+                format!("The env option must be a hash table, found {}.", env),
+            );
+        }
+    }
+
+    if let Some(cwd) = get_option(options, "cwd")
+        && !cwd.is_none()
+    {
+        command.current_dir(cwd.get_string_val());
+    }
+
+    Ok(())
+}
+
+/// Get how one of the standard streams should be connected.  The option can be "pipe",
+/// "inherit", "null", or the fd of an open file or of another process's pipe.  Fds are added to
+/// handed_fds so that pipes can be closed once the process has started.
+fn stdio_option(
+    interpreter: &mut dyn Interpreter,
+    options: &Option<ValueHashPtr>,
+    key: &str,
+    default: &str,
+    handed_fds: &mut Vec<i64>,
+) -> error::Result<Stdio> {
+    let option = get_option(options, key).unwrap_or(Value::None);
+
+    let mode = match option {
+        Value::None => default.to_string(),
+        Value::Int(fd) => {
+            let stdio = fd_to_stdio(interpreter, fd)?;
+
+            handed_fds.push(fd);
+            return Ok(stdio);
+        }
+        _ => option.get_string_val(),
+    };
+
+    match mode.as_str() {
+        "pipe" => Ok(Stdio::piped()),
+        "inherit" => Ok(Stdio::inherit()),
+        "null" => Ok(Stdio::null()),
+        _ => script_error(
+            interpreter,
+            format!(
+                "The {} option must be pipe, inherit, null or an fd, found {}.",
+                key, mode
+            ),
+        ),
+    }
+}
+
+/// Close the pipes that were handed to a process that has started, they now belong to it.  The
+/// processes the pipes came from no longer report them as their fds.  Regular files stay open.
+fn close_handed_pipes(handed_fds: &[i64]) {
+    for fd in handed_fds {
+        if !close_process_pipe(*fd) {
+            continue;
+        }
+
+        for entry in PROCESS_TABLE.lock().unwrap().values_mut() {
+            for pipe in [&mut entry.stdin, &mut entry.stdout, &mut entry.stderr] {
+                if *pipe == Some(*fd) {
+                    *pipe = None;
+                }
+            }
+        }
+    }
+}
+
+/// Run a command to completion, capturing it's output.  Standard input defaults to null, and
+/// standard output and error default to being captured.  Output sent elsewhere by the options is
+/// returned as an empty string.
+///
+/// Signature: `command options -- stdout stderr exit-code`
+fn word_process_run(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let options = pop_options(interpreter)?;
+    let (program, mut command) = pop_command(interpreter)?;
+
+    apply_environment(interpreter, &mut command, &options)?;
+
+    let mut handed_fds = Vec::new();
+    let stdin = stdio_option(interpreter, &options, "stdin", "null", &mut handed_fds)?;
+    let stdout = stdio_option(interpreter, &options, "stdout", "pipe", &mut handed_fds)?;
+    let stderr = stdio_option(interpreter, &options, "stderr", "pipe", &mut handed_fds)?;
+
+    command.stdin(stdin).stdout(stdout).stderr(stderr);
+
+    match command.output() {
+        Ok(output) => {
+            close_handed_pipes(&handed_fds);
+
+            interpreter.push(
+                String::from_utf8_lossy(&output.stdout)
+                    .to_string()
+                    .to_value(),
+            );
+            interpreter.push(
+                String::from_utf8_lossy(&output.stderr)
+                    .to_string()
+                    .to_value(),
+            );
+            interpreter.push(exit_code(output.status).to_value());
+
+            Ok(())
+        }
+
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not run {}: {}.", program, error),
+        ),
+    }
+}
+
+/// Start a command without waiting for it.  Standard input and output default to pipes, which
+/// become fds in the file table, and standard error is inherited.
+///
+/// Signature: `command options -- handle`
+fn word_process_spawn(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let options = pop_options(interpreter)?;
+    let (program, mut command) = pop_command(interpreter)?;
+
+    apply_environment(interpreter, &mut command, &options)?;
+
+    let mut handed_fds = Vec::new();
+    let stdin = stdio_option(interpreter, &options, "stdin", "pipe", &mut handed_fds)?;
+    let stdout = stdio_option(interpreter, &options, "stdout", "pipe", &mut handed_fds)?;
+    let stderr = stdio_option(interpreter, &options, "stderr", "inherit", &mut handed_fds)?;
+
+    command.stdin(stdin).stdout(stdout).stderr(stderr);
+
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(error) => {
+            return script_error(
+                interpreter,
+                format!("Could not run {}: {}.", program, error),
+            );
+        }
+    };
+
+    close_handed_pipes(&handed_fds);
+
+    let entry = ProcessEntry {
+        stdin: child
+            .stdin
+            .take()
+            .map(|pipe| add_process_pipe(ProcessPipe::Input(pipe))),
+        stdout: child
+            .stdout
+            .take()
+            .map(|pipe| add_process_pipe(ProcessPipe::Output(pipe))),
+        stderr: child
+            .stderr
+            .take()
+            .map(|pipe| add_process_pipe(ProcessPipe::Error(pipe))),
+        child,
+        exit_code: None,
+    };
+
+    let handle = HANDLE_COUNTER.fetch_add(1, Ordering::SeqCst);
+
+    PROCESS_TABLE.lock().unwrap().insert(handle, entry);
+    interpreter.push(handle.to_value());
+
+    Ok(())
+}
+
+/// Wait for a spawned process to exit and get it's exit code.  Close the process's input fd
+/// first if the process reads until the end of it's input.
+///
+/// Signature: `handle -- exit-code`
+fn word_process_wait(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let handle = interpreter.pop_as_int()?;
+
+    // Take the process out of the table while waiting so that other threads aren't blocked.
+    let entry = PROCESS_TABLE.lock().unwrap().remove(&handle);
+
+    let Some(mut entry) = entry else {
+        return script_error(interpreter, format!("Process handle {} not found.", handle));
+    };
+
+    let result = match entry.exit_code {
+        Some(code) => Ok(code),
+        None => entry.child.wait().map(exit_code),
+    };
+
+    if let Ok(code) = result {
+        entry.exit_code = Some(code);
+    }
+
+    PROCESS_TABLE.lock().unwrap().insert(handle, entry);
+
+    match result {
+        Ok(code) => {
+            interpreter.push(code.to_value());
+            Ok(())
+        }
+
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not wait for process {}: {}.", handle, error),
+        ),
+    }
+}
+
+/// Forcibly stop a spawned process.  It still needs to be waited on to get it's exit code.
+///
+/// Signature: `handle -- `
+fn word_process_kill(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let handle = interpreter.pop_as_int()?;
+    let result = with_process(interpreter, handle, |entry| match entry.exit_code {
+        Some(_) => Ok(()),
+        None => entry.child.kill(),
+    })?;
+
+    if let Err(error) = result {
+        script_error(
+            interpreter,
+            format!("Could not kill process {}: {}.", handle, error),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Remove a process that has exited from the process table.  It's handle can't be used after
+/// this, but any of it's pipe fds that are still open stay open until they're closed.
+///
+/// Signature: `handle -- `
+fn word_process_free(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let handle = interpreter.pop_as_int()?;
+    let result = with_process(interpreter, handle, |entry| match entry.exit_code {
+        Some(_) => Ok(true),
+        None => entry.child.try_wait().map(|status| status.is_some()),
+    })?;
+
+    match result {
+        Ok(true) => {
+            PROCESS_TABLE.lock().unwrap().remove(&handle);
+            Ok(())
+        }
+
+        Ok(false) => script_error(
+            interpreter,
+            format!(
+                "Process {} is still running, wait for it or kill it before freeing it.",
+                handle
+            ),
+        ),
+
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not check on process {}: {}.", handle, error),
+        ),
+    }
+}
+
+/// Get the operating system's id for a spawned process.
+///
+/// Signature: `handle -- pid`
+fn word_process_pid_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let handle = interpreter.pop_as_int()?;
+    let pid = with_process(interpreter, handle, |entry| entry.child.id())?;
+
+    interpreter.push((pid as i64).to_value());
+
+    Ok(())
+}
+
+/// Push the fd of one of a process's pipes, or none if that stream wasn't piped.
+fn push_pipe_fd(
+    interpreter: &mut dyn Interpreter,
+    pipe: fn(&ProcessEntry) -> Option<i64>,
+) -> error::Result<()> {
+    let handle = interpreter.pop_as_int()?;
+    let fd = with_process(interpreter, handle, |entry| pipe(entry))?;
+
+    match fd {
+        Some(fd) => interpreter.push(fd.to_value()),
+        None => interpreter.push(Value::None),
+    }
+
+    Ok(())
+}
+
+fn word_process_stdin_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    push_pipe_fd(interpreter, |entry| entry.stdin)
+}
+
+fn word_process_stdout_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    push_pipe_fd(interpreter, |entry| entry.stdout)
+}
+
+fn word_process_stderr_read(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    push_pipe_fd(interpreter, |entry| entry.stderr)
+}
+
+pub fn register_process_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "process.run",
+        word_process_run,
+        "Run a command array to completion and capture it's output.",
+        "command options -- stdout stderr exit-code"
+    );
+
+    add_native_word!(
+        interpreter,
+        "process.spawn",
+        word_process_spawn,
+        "Start a command array running and return it's handle.",
+        "command options -- handle"
+    );
+
+    add_native_word!(
+        interpreter,
+        "process.wait",
+        word_process_wait,
+        "Wait for a spawned process to exit.",
+        "handle -- exit-code"
+    );
+
+    add_native_word!(
+        interpreter,
+        "process.kill",
+        word_process_kill,
+        "Forcibly stop a spawned process.",
+        "handle -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "process.free",
+        word_process_free,
+        "Remove a process that has exited from the process table.",
+        "handle -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "process.pid@",
+        word_process_pid_read,
+        "Get the operating system's id for a spawned process.",
+        "handle -- pid"
+    );
+
+    add_native_word!(
+        interpreter,
+        "process.stdin@",
+        word_process_stdin_read,
+        "Get the fd for writing to a process's input, or none.",
+        "handle -- fd"
+    );
+
+    add_native_word!(
+        interpreter,
+        "process.stdout@",
+        word_process_stdout_read,
+        "Get the fd for reading a process's output, or none.",
+        "handle -- fd"
+    );
+
+    add_native_word!(
+        interpreter,
+        "process.stderr@",
+        word_process_stderr_read,
+        "Get the fd for reading a process's errors, or none.",
+        "handle -- fd"
+    );
+}
@@ -0,0 +1,508 @@
+use crate::{
+    add_native_word,
+    runtime::{
+        data_structures::{
+            byte_buffer::ByteBuffer,
+            value::{ToValue, Value},
+        },
+        error::{self, script_error, script_error_str},
+        interpreter::Interpreter,
+    },
+};
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The handle of the generator used by the random words that don't take one.
+const DEFAULT_GENERATOR: i64 = 0;
+
+/// A xoshiro256** pseudo-random number generator.  It's small and fast, and the numbers it gives
+/// for a seed never change, so that seeded runs can be replayed.  It is not suitable for secrets,
+/// random.secure-bytes reads from the operating system for those.
+#[derive(Clone)]
+pub struct Generator {
+    state: [u64; 4],
+}
+
+impl Generator {
+    /// Create a generator, the seed is spread over the generator's state with splitmix64.
+    pub fn from_seed(seed: u64) -> Generator {
+        let mut seed = seed;
+        let mut next = || {
+            seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+
+        Generator {
+            state: [next(), next(), next(), next()],
+        }
+    }
+
+    /// Create a generator seeded by the operating system, falling back to the clock if it can't
+    /// provide any random bytes.
+    pub fn from_entropy() -> Generator {
+        let mut bytes = [0; 8];
+
+        let seed = match getrandom::fill(&mut bytes) {
+            Ok(()) => u64::from_le_bytes(bytes),
+            Err(_) => SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos() as u64,
+        };
+
+        Generator::from_seed(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let shifted = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= shifted;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    /// Get a number from 0 up to, but not including, the bound.  Draws that would favour some
+    /// numbers over others are thrown away and drawn again.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        let threshold = bound.wrapping_neg() % bound;
+
+        loop {
+            let product = self.next_u64() as u128 * bound as u128;
+
+            if product as u64 >= threshold {
+                return (product >> 64) as u64;
+            }
+        }
+    }
+
+    /// Get a number from low to high, including both.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        let span = (high as i128 - low as i128 + 1) as u128;
+
+        match u64::try_from(span) {
+            Ok(span) => (low as i128 + self.below(span) as i128) as i64,
+            Err(_) => self.next_u64() as i64,
+        }
+    }
+
+    /// Get a float from 0.0 up to, but not including, 1.0.
+    pub fn float(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    pub fn fill(&mut self, bytes: &mut [u8]) {
+        for chunk in bytes.chunks_mut(8) {
+            let random = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&random[..chunk.len()]);
+        }
+    }
+}
+
+/// The per-interpreter random number generators.  The default generator starts out seeded by the
+/// operating system, and scripts can create more generators for independent streams of numbers.
+pub struct RandomGenerators {
+    generators: HashMap<i64, Generator>,
+    next_handle: i64,
+}
+
+impl Default for RandomGenerators {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RandomGenerators {
+    /// Create the generator list with only the default generator.
+    pub fn new() -> RandomGenerators {
+        RandomGenerators {
+            generators: HashMap::from([(DEFAULT_GENERATOR, Generator::from_entropy())]),
+            next_handle: DEFAULT_GENERATOR + 1,
+        }
+    }
+
+    /// Free all of the created generators and reseed the default one from the operating system.
+    pub fn reset(&mut self) {
+        *self = RandomGenerators::new();
+    }
+
+    pub fn get_mut(&mut self, handle: i64) -> Option<&mut Generator> {
+        self.generators.get_mut(&handle)
+    }
+}
+
+/// Run an operation with one of the interpreter's generators.
+fn with_generator<T>(
+    interpreter: &mut dyn Interpreter,
+    handle: i64,
+    operation: impl FnOnce(&mut Generator) -> T,
+) -> error::Result<T> {
+    let result = interpreter.random_mut().get_mut(handle).map(operation);
+
+    match result {
+        Some(value) => Ok(value),
+        None => script_error(
+            interpreter,
+            format!("Random generator {} not found.", handle),
+        ),
+    }
+}
+
+/// Replace a generator with a new one created from the seed.  The same seed always gives the same
+/// numbers.
+///
+/// Signature: `seed -- `
+fn random_seed(interpreter: &mut dyn Interpreter, handle: i64) -> error::Result<()> {
+    let seed = interpreter.pop_as_int()?;
+
+    with_generator(interpreter, handle, |generator| {
+        *generator = Generator::from_seed(seed as u64)
+    })
+}
+
+/// Get a random int from low to high, including both.
+///
+/// Signature: `low high -- value`
+fn random_int(interpreter: &mut dyn Interpreter, handle: i64) -> error::Result<()> {
+    let high = interpreter.pop_as_int()?;
+    let low = interpreter.pop_as_int()?;
+
+    if low > high {
+        return script_error(
+            interpreter,
+            format!("The random range {} to {} is empty.", low, high),
+        );
+    }
+
+    let value = with_generator(interpreter, handle, |generator| generator.range(low, high))?;
+
+    interpreter.push(value.to_value());
+
+    Ok(())
+}
+
+/// Get a random float from 0.0 up to, but not including, 1.0.
+///
+/// Signature: ` -- value`
+fn random_float(interpreter: &mut dyn Interpreter, handle: i64) -> error::Result<()> {
+    let value = with_generator(interpreter, handle, Generator::float)?;
+
+    interpreter.push(value.to_value());
+
+    Ok(())
+}
+
+/// Get true or false with even odds.
+///
+/// Signature: ` -- value`
+fn random_bool(interpreter: &mut dyn Interpreter, handle: i64) -> error::Result<()> {
+    let value = with_generator(interpreter, handle, |generator| {
+        generator.next_u64() >> 63 == 1
+    })?;
+
+    interpreter.push(value.to_value());
+
+    Ok(())
+}
+
+/// Pick a random value from an array.
+///
+/// Signature: `array -- value`
+fn random_choice(interpreter: &mut dyn Interpreter, handle: i64) -> error::Result<()> {
+    let array = interpreter.pop_as_array()?;
+    let len = array.borrow().len();
+
+    if len == 0 {
+        return script_error_str(interpreter, "Can not choose from an empty array.");
+    }
+
+    let index = with_generator(interpreter, handle, |generator| {
+        generator.below(len as u64) as usize
+    })?;
+
+    interpreter.push(array.borrow()[index].clone());
+
+    Ok(())
+}
+
+/// Shuffle the values of an array in place.
+///
+/// Signature: `array -- `
+fn random_shuffle(interpreter: &mut dyn Interpreter, handle: i64) -> error::Result<()> {
+    let array = interpreter.pop_as_array()?;
+    let len = array.borrow().len();
+
+    let swaps = with_generator(interpreter, handle, |generator| {
+        (1..len)
+            .rev()
+            .map(|index| (index, generator.below(index as u64 + 1) as usize))
+            .collect::<Vec<_>>()
+    })?;
+
+    for (first, second) in swaps {
+        array.borrow_mut().swap(first, second);
+    }
+
+    Ok(())
+}
+
+/// Create a buffer of count pseudo-random bytes.
+///
+/// Signature: `count -- buffer`
+fn random_bytes(interpreter: &mut dyn Interpreter, handle: i64) -> error::Result<()> {
+    let count = interpreter.pop_as_usize()?;
+    let buffer = ByteBuffer::new_ptr(count);
+
+    with_generator(interpreter, handle, |generator| {
+        generator.fill(buffer.borrow_mut().buffer_mut())
+    })?;
+
+    interpreter.push(buffer.to_value());
+
+    Ok(())
+}
+
+/// Create a buffer of count random bytes read from the operating system, suitable for keys and
+/// tokens.
+///
+/// Signature: `count -- buffer`
+fn word_random_secure_bytes(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let count = interpreter.pop_as_usize()?;
+    let buffer = ByteBuffer::new_ptr(count);
+
+    let result = getrandom::fill(buffer.borrow_mut().buffer_mut());
+
+    match result {
+        Ok(()) => {
+            interpreter.push(buffer.to_value());
+            Ok(())
+        }
+
+        Err(error) => script_error(
+            interpreter,
+            format!(
+                "Could not read random bytes from the operating system: {}.",
+                error
+            ),
+        ),
+    }
+}
+
+/// Create a new generator independent of the default one.  A seed of none seeds it from the
+/// operating system.
+///
+/// Signature: `seed -- generator`
+fn word_random_new(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let generator = match interpreter.pop()? {
+        Value::None => Generator::from_entropy(),
+        Value::Int(seed) => Generator::from_seed(seed as u64),
+        seed => {
+            return script_error(
+                interpreter,
+                format!("A random seed must be an int or none, found {}.", seed),
+            );
+        }
+    };
+
+    let random = interpreter.random_mut();
+    let handle = random.next_handle;
+
+    random.next_handle += 1;
+    random.generators.insert(handle, generator);
+
+    interpreter.push(handle.to_value());
+
+    Ok(())
+}
+
+/// Free a generator created by random.new.
+///
+/// Signature: `generator -- `
+fn word_random_free(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let handle = interpreter.pop_as_int()?;
+
+    if handle == DEFAULT_GENERATOR {
+        return script_error_str(
+            interpreter,
+            "The default random generator can not be freed.",
+        );
+    }
+
+    match interpreter.random_mut().generators.remove(&handle) {
+        Some(_) => Ok(()),
+        None => script_error(
+            interpreter,
+            format!("Random generator {} not found.", handle),
+        ),
+    }
+}
+
+pub fn register_random_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "random.seed",
+        |interpreter| random_seed(interpreter, DEFAULT_GENERATOR),
+        "Seed the default random generator, the same seed gives the same numbers.",
+        "seed -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.int",
+        |interpreter| random_int(interpreter, DEFAULT_GENERATOR),
+        "Get a random int from low to high, including both.",
+        "low high -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.float",
+        |interpreter| random_float(interpreter, DEFAULT_GENERATOR),
+        "Get a random float from 0.0 up to, but not including, 1.0.",
+        " -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.bool",
+        |interpreter| random_bool(interpreter, DEFAULT_GENERATOR),
+        "Get true or false with even odds.",
+        " -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.choice",
+        |interpreter| random_choice(interpreter, DEFAULT_GENERATOR),
+        "Pick a random value from an array.",
+        "array -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.shuffle",
+        |interpreter| random_shuffle(interpreter, DEFAULT_GENERATOR),
+        "Shuffle the values of an array in place.",
+        "array -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.bytes",
+        |interpreter| random_bytes(interpreter, DEFAULT_GENERATOR),
+        "Create a buffer of pseudo-random bytes.",
+        "count -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.secure-bytes",
+        word_random_secure_bytes,
+        "Create a buffer of random bytes from the operating system.",
+        "count -- buffer"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.new",
+        word_random_new,
+        "Create an independent random generator, seeded by the OS if the seed is none.",
+        "seed -- generator"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.free",
+        word_random_free,
+        "Free a random generator created by random.new.",
+        "generator -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.gen.seed",
+        |interpreter| {
+            let generator = interpreter.pop_as_int()?;
+            random_seed(interpreter, generator)
+        },
+        "Seed a random generator.",
+        "seed generator -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.gen.int",
+        |interpreter| {
+            let generator = interpreter.pop_as_int()?;
+            random_int(interpreter, generator)
+        },
+        "Get a random int from low to high from a generator.",
+        "low high generator -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.gen.float",
+        |interpreter| {
+            let generator = interpreter.pop_as_int()?;
+            random_float(interpreter, generator)
+        },
+        "Get a random float from 0.0 up to 1.0 from a generator.",
+        "generator -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.gen.bool",
+        |interpreter| {
+            let generator = interpreter.pop_as_int()?;
+            random_bool(interpreter, generator)
+        },
+        "Get true or false from a generator.",
+        "generator -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.gen.choice",
+        |interpreter| {
+            let generator = interpreter.pop_as_int()?;
+            random_choice(interpreter, generator)
+        },
+        "Pick a random value from an array with a generator.",
+        "array generator -- value"
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.gen.shuffle",
+        |interpreter| {
+            let generator = interpreter.pop_as_int()?;
+            random_shuffle(interpreter, generator)
+        },
+        "Shuffle the values of an array in place with a generator.",
+        "array generator -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "random.gen.bytes",
+        |interpreter| {
+            let generator = interpreter.pop_as_int()?;
+            random_bytes(interpreter, generator)
+        },
+        "Create a buffer of pseudo-random bytes from a generator.",
+        "count generator -- buffer"
+    );
+}
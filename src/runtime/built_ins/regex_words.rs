@@ -0,0 +1,300 @@
+use crate::{
+    add_native_word,
+    runtime::{
+        data_structures::{
+            compiled_regex::CompiledRegex,
+            value::{ToValue, Value},
+            value_hash::{ValueHash, ValueHashPtr},
+            value_vec::ValueVec,
+        },
+        error::{self, script_error},
+        interpreter::Interpreter,
+    },
+};
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+
+/// The most pattern strings that are kept compiled for words given a pattern instead of a regex.
+/// Once the cache is full it's emptied and starts filling again.
+const MAX_CACHED_PATTERNS: usize = 256;
+
+/// The per-interpreter cache of compiled regular expressions.  Patterns are compiled once and then
+/// looked up by their source text, so that using a pattern string directly inside of a loop stays
+/// cheap.
+pub struct RegexCache {
+    /// Map of pattern source text to the compiled pattern.
+    patterns: HashMap<String, CompiledRegex>,
+}
+
+impl Default for RegexCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegexCache {
+    /// Create a new and empty regex cache.
+    pub fn new() -> RegexCache {
+        RegexCache {
+            patterns: HashMap::new(),
+        }
+    }
+
+    /// Clear out all of the compiled patterns.  Regex values already handed out to scripts keep
+    /// their own compiled pattern and remain usable.
+    pub fn reset(&mut self) {
+        self.patterns.clear();
+    }
+
+    /// Get the compiled version of a pattern string, compiling and caching it if it hasn't been
+    /// seen before.
+    fn pattern(&mut self, pattern: &str) -> Result<CompiledRegex, regex::Error> {
+        if let Some(regex) = self.patterns.get(pattern) {
+            return Ok(regex.clone());
+        }
+
+        let regex = CompiledRegex::new(Regex::new(pattern)?);
+
+        if self.patterns.len() >= MAX_CACHED_PATTERNS {
+            self.patterns.clear();
+        }
+
+        self.patterns.insert(pattern.to_string(), regex.clone());
+
+        Ok(regex)
+    }
+}
+
+/// Compile a pattern string through the interpreter's cache, reporting a bad pattern as a script
+/// error.
+fn compile_pattern(
+    interpreter: &mut dyn Interpreter,
+    pattern: &str,
+) -> error::Result<CompiledRegex> {
+    match interpreter.regex_mut().pattern(pattern) {
+        Ok(regex) => Ok(regex),
+        Err(error) => script_error(
+            interpreter,
+            format!("Could not compile regex {}: {}", pattern, error),
+        ),
+    }
+}
+
+/// Pop a regex from the stack.  The value can either be a regex returned from `regex.compile` or
+/// the source text of a pattern, in which case the pattern is compiled through the cache.
+fn pop_regex(interpreter: &mut dyn Interpreter) -> error::Result<CompiledRegex> {
+    let value = interpreter.pop()?;
+
+    match &value {
+        Value::Regex(regex) => Ok(regex.clone()),
+        Value::String(pattern) => compile_pattern(interpreter, pattern),
+
+        _ => script_error(
+            interpreter,
+            format!("Value {} is not a regex or a pattern string.", value),
+        ),
+    }
+}
+
+/// Convert a byte offset within a string into a logical character index.
+fn char_index(text: &str, byte_index: usize) -> usize {
+    text[..byte_index].chars().count()
+}
+
+/// Create a hash table describing a single span of text within the searched string.  The start and
+/// end indices are character indices, matching the other string words.
+fn span_to_hash(text: &str, start: usize, end: usize) -> ValueHashPtr {
+    let span = ValueHash::new();
+
+    {
+        let mut span = span.borrow_mut();
+
+        span.insert(
+            "text".to_string().to_value(),
+            text[start..end].to_string().to_value(),
+        );
+        span.insert(
+            "start".to_string().to_value(),
+            char_index(text, start).to_value(),
+        );
+        span.insert(
+            "end".to_string().to_value(),
+            char_index(text, end).to_value(),
+        );
+    }
+
+    span
+}
+
+/// Convert a set of captures into a match hash table.  The table holds the text, start and end of
+/// the whole match, an array of all the capture groups, (none for groups that did not take part in
+/// the match,) and a table of the named groups.
+fn captures_to_hash(regex: &Regex, text: &str, captures: &Captures) -> Value {
+    let whole = captures.get(0).unwrap();
+    let result = span_to_hash(text, whole.start(), whole.end());
+
+    let groups = ValueVec::new(0);
+    let named = ValueHash::new();
+
+    for group in captures.iter() {
+        let group = match group {
+            Some(group) => span_to_hash(text, group.start(), group.end()).to_value(),
+            None => Value::None,
+        };
+
+        groups.borrow_mut().push_back(group);
+    }
+
+    for name in regex.capture_names().flatten() {
+        let group = match captures.name(name) {
+            Some(group) => span_to_hash(text, group.start(), group.end()).to_value(),
+            None => Value::None,
+        };
+
+        named
+            .borrow_mut()
+            .insert(name.to_string().to_value(), group);
+    }
+
+    result
+        .borrow_mut()
+        .insert("groups".to_string().to_value(), groups.to_value());
+    result
+        .borrow_mut()
+        .insert("named".to_string().to_value(), named.to_value());
+
+    result.to_value()
+}
+
+/// Compile a regular expression into a regex value that the other regex words accept in place of
+/// a pattern string.
+///
+/// Signature: `pattern -- regex`
+fn word_regex_compile(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let pattern = interpreter.pop_as_string()?;
+    let regex = compile_pattern(interpreter, &pattern)?;
+
+    interpreter.push(regex.to_value());
+    Ok(())
+}
+
+/// Check if the regex matches anywhere within the string.
+///
+/// Signature: `string regex -- bool`
+fn word_regex_is_match(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let regex = pop_regex(interpreter)?;
+    let text = interpreter.pop_as_string()?;
+
+    interpreter.push(regex.is_match(&text).to_value());
+    Ok(())
+}
+
+/// Find the first match of the regex within the string.  None is pushed if there is no match.
+///
+/// Signature: `string regex -- match-or-none`
+fn word_regex_find(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let regex = pop_regex(interpreter)?;
+    let text = interpreter.pop_as_string()?;
+
+    match regex.captures(&text) {
+        Some(captures) => interpreter.push(captures_to_hash(&regex, &text, &captures)),
+        None => interpreter.push(Value::None),
+    }
+
+    Ok(())
+}
+
+/// Find all of the non-overlapping matches of the regex within the string.
+///
+/// Signature: `string regex -- matches`
+fn word_regex_find_all(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let regex = pop_regex(interpreter)?;
+    let text = interpreter.pop_as_string()?;
+
+    let matches: Vec<Value> = regex
+        .captures_iter(&text)
+        .map(|captures| captures_to_hash(&regex, &text, &captures))
+        .collect();
+
+    interpreter.push(ValueVec::from_vec(matches).to_value());
+    Ok(())
+}
+
+/// Replace all matches of the regex within the string.  The replacement text can refer to capture
+/// groups with $1 or ${name} style back references.
+///
+/// Signature: `replacement string regex -- new-string`
+fn word_regex_replace(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let regex = pop_regex(interpreter)?;
+    let text = interpreter.pop_as_string()?;
+    let replacement = interpreter.pop_as_string()?;
+
+    let result = regex.replace_all(&text, replacement.as_str()).to_string();
+
+    interpreter.push(result.to_value());
+    Ok(())
+}
+
+/// Split a string into an array of strings using the regex as the separator.
+///
+/// Signature: `string regex -- array`
+fn word_regex_split(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let regex = pop_regex(interpreter)?;
+    let text = interpreter.pop_as_string()?;
+
+    let parts: Vec<String> = regex.split(&text).map(|part| part.to_string()).collect();
+
+    interpreter.push(Value::from(parts));
+    Ok(())
+}
+
+/// Register the regular expression words with the interpreter.
+pub fn register_regex_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "regex.compile",
+        word_regex_compile,
+        "Compile a regular expression into a regex value.",
+        "pattern -- regex"
+    );
+
+    add_native_word!(
+        interpreter,
+        "regex.match?",
+        word_regex_is_match,
+        "Does the regex or pattern match anywhere within the string?",
+        "string regex -- bool"
+    );
+
+    add_native_word!(
+        interpreter,
+        "regex.find",
+        word_regex_find,
+        "Find the first match within the string, or none if there isn't one.",
+        "string regex -- match_or_none"
+    );
+
+    add_native_word!(
+        interpreter,
+        "regex.find-all",
+        word_regex_find_all,
+        "Find all of the non-overlapping matches within the string.",
+        "string regex -- matches"
+    );
+
+    add_native_word!(
+        interpreter,
+        "regex.replace",
+        word_regex_replace,
+        "Replace all matches within the string.  Supports $1 and ${name} back references.",
+        "replacement string regex -- new_string"
+    );
+
+    add_native_word!(
+        interpreter,
+        "regex.split",
+        word_regex_split,
+        "Split a string using the regex as the separator.",
+        "string regex -- array"
+    );
+}
@@ -0,0 +1,471 @@
+use crate::{
+    add_native_word,
+    runtime::{
+        data_structures::{
+            value::{ToValue, Value},
+            value_hash::{ValueHash, ValueHashPtr},
+        },
+        error::{self, script_error, script_error_str},
+        interpreter::Interpreter,
+    },
+};
+use chrono::{
+    DateTime, Datelike, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime, Offset,
+    TimeZone, Timelike, Utc,
+    format::{Item, StrftimeItems},
+};
+use lazy_static::lazy_static;
+use std::{
+    fmt::Write,
+    thread::sleep,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+lazy_static! {
+    // The monotonic clock counts from the first time it's read.
+    static ref MONOTONIC_START: Instant = Instant::now();
+}
+
+/// Get the time since the Unix epoch.  A clock set before 1970 counts as the epoch itself.
+fn since_epoch() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Get the current time as seconds since the Unix epoch, with the fraction of a second.
+///
+/// Signature: ` -- seconds`
+fn word_time_now(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    interpreter.push(since_epoch().as_secs_f64().to_value());
+    Ok(())
+}
+
+/// Get the current time as whole nanoseconds since the Unix epoch.
+///
+/// Signature: ` -- nanoseconds`
+fn word_time_now_ns(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    interpreter.push((since_epoch().as_nanos() as i64).to_value());
+    Ok(())
+}
+
+/// Get seconds from a clock that never goes backwards, no matter what happens to the system
+/// clock.  Only the difference between two readings means anything.
+///
+/// Signature: ` -- seconds`
+fn word_time_monotonic(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    interpreter.push(MONOTONIC_START.elapsed().as_secs_f64().to_value());
+    Ok(())
+}
+
+/// Get whole nanoseconds from the monotonic clock.
+///
+/// Signature: ` -- nanoseconds`
+fn word_time_monotonic_ns(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    interpreter.push((MONOTONIC_START.elapsed().as_nanos() as i64).to_value());
+    Ok(())
+}
+
+/// Pause the current thread for a number of seconds, which can have a fraction.
+///
+/// Signature: `seconds -- `
+fn word_time_sleep(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let seconds = interpreter.pop_as_float()?;
+
+    match Duration::try_from_secs_f64(seconds) {
+        Ok(duration) => {
+            sleep(duration);
+            Ok(())
+        }
+
+        Err(_) => script_error(
+            interpreter,
+            format!("Can not sleep for {} seconds.", seconds),
+        ),
+    }
+}
+
+/// Pop an epoch time, whole seconds as an int or seconds with a fraction as a float.
+fn pop_epoch(interpreter: &mut dyn Interpreter) -> error::Result<DateTime<Utc>> {
+    let value = interpreter.pop()?;
+    value_to_epoch(interpreter, &value)
+}
+
+fn value_to_epoch(
+    interpreter: &mut dyn Interpreter,
+    value: &Value,
+) -> error::Result<DateTime<Utc>> {
+    let time = match value {
+        Value::Int(seconds) => DateTime::from_timestamp(*seconds, 0),
+
+        Value::Float(seconds) if seconds.is_finite() => {
+            let whole = seconds.floor();
+            let nanoseconds = ((seconds - whole) * 1e9).round().min(999_999_999.0);
+
+            DateTime::from_timestamp(whole as i64, nanoseconds as u32)
+        }
+
+        _ => {
+            return script_error(
+                interpreter,
+                format!("Expected an epoch time in seconds, found {}.", value),
+            );
+        }
+    };
+
+    match time {
+        Some(time) => Ok(time),
+        None => script_error(
+            interpreter,
+            format!("The epoch time {} is out of range.", value),
+        ),
+    }
+}
+
+/// Convert a time to seconds since the epoch, with the fraction of a second.
+fn time_to_epoch<Tz: TimeZone>(time: &DateTime<Tz>) -> Value {
+    let seconds = time.timestamp() as f64 + time.timestamp_subsec_nanos() as f64 / 1e9;
+    seconds.to_value()
+}
+
+/// Break a time down into a hash table of it's parts.  The weekday counts from Sunday as 0, and
+/// the offset is the seconds east of UTC the time was taken in.
+fn time_to_parts<Tz: TimeZone>(time: &DateTime<Tz>) -> Value {
+    let hash = ValueHash::new();
+
+    for (key, value) in [
+        ("year", time.year() as i64),
+        ("month", time.month() as i64),
+        ("day", time.day() as i64),
+        ("hour", time.hour() as i64),
+        ("minute", time.minute() as i64),
+        ("second", time.second() as i64),
+        ("nanosecond", time.nanosecond() as i64),
+        ("weekday", time.weekday().num_days_from_sunday() as i64),
+        ("yearday", time.ordinal() as i64),
+        ("offset", time.offset().fix().local_minus_utc() as i64),
+    ] {
+        hash.borrow_mut()
+            .insert(key.to_string().to_value(), value.to_value());
+    }
+
+    hash.to_value()
+}
+
+/// Read the date and time from a parts hash table.  The year, month and day are required, the
+/// rest default to zero.  The weekday and yearday are ignored.
+fn parts_to_naive(
+    interpreter: &mut dyn Interpreter,
+    parts: &ValueHashPtr,
+) -> error::Result<NaiveDateTime> {
+    let mut fields = [0_i64; 7];
+
+    for (index, key) in [
+        "year",
+        "month",
+        "day",
+        "hour",
+        "minute",
+        "second",
+        "nanosecond",
+    ]
+    .iter()
+    .enumerate()
+    {
+        match parts.borrow().get(&key.to_string().to_value()) {
+            Some(Value::Int(value)) => fields[index] = *value,
+            None if index >= 3 => {}
+            found => {
+                return script_error(
+                    interpreter,
+                    format!(
+                        "The time part {} must be an int, found {}.",
+                        key,
+                        found.cloned().unwrap_or(Value::None)
+                    ),
+                );
+            }
+        }
+    }
+
+    let [year, month, day, hour, minute, second, nanosecond] = fields;
+
+    let time = i32::try_from(year).ok().and_then(|year| {
+        NaiveDate::from_ymd_opt(year, u32::try_from(month).ok()?, u32::try_from(day).ok()?)?
+            .and_hms_nano_opt(
+                u32::try_from(hour).ok()?,
+                u32::try_from(minute).ok()?,
+                u32::try_from(second).ok()?,
+                u32::try_from(nanosecond).ok()?,
+            )
+    });
+
+    match time {
+        Some(time) => Ok(time),
+        None => script_error(
+            interpreter,
+            format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02} is not a valid date and time.",
+                year, month, day, hour, minute, second
+            ),
+        ),
+    }
+}
+
+/// Convert a parts hash table to a time in it's own offset, UTC if it doesn't have one.
+fn parts_to_time(
+    interpreter: &mut dyn Interpreter,
+    parts: &ValueHashPtr,
+) -> error::Result<DateTime<FixedOffset>> {
+    let naive = parts_to_naive(interpreter, parts)?;
+    let offset = match parts.borrow().get(&"offset".to_string().to_value()) {
+        None => Some(0),
+        Some(Value::Int(offset)) => i32::try_from(*offset).ok(),
+        Some(_) => None,
+    };
+
+    match offset
+        .and_then(FixedOffset::east_opt)
+        .and_then(|offset| offset.from_local_datetime(&naive).single())
+    {
+        Some(time) => Ok(time),
+        None => script_error_str(
+            interpreter,
+            "The time offset must be seconds east of UTC, less than a day.",
+        ),
+    }
+}
+
+/// Break an epoch time down into it's parts in UTC.
+///
+/// Signature: `epoch -- parts`
+fn word_time_utc(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let time = pop_epoch(interpreter)?;
+
+    interpreter.push(time_to_parts(&time));
+
+    Ok(())
+}
+
+/// Break an epoch time down into it's parts in the local time zone.
+///
+/// Signature: `epoch -- parts`
+fn word_time_local(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let time = pop_epoch(interpreter)?;
+
+    interpreter.push(time_to_parts(&time.with_timezone(&Local)));
+
+    Ok(())
+}
+
+/// Convert parts back to an epoch time.  The parts are taken to be in their offset, so parts from
+/// time.local and time.parse come back to the same time.  Parts without an offset are in UTC.
+///
+/// Signature: `parts -- epoch`
+fn word_time_from_parts(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let parts = interpreter.pop_as_hash_map()?;
+    let time = parts_to_time(interpreter, &parts)?;
+
+    interpreter.push(time_to_epoch(&time));
+
+    Ok(())
+}
+
+/// Convert parts to an epoch time using the local time zone's rules, ignoring any offset.  When
+/// the clocks go back the earlier of the two times is used, a time skipped when the clocks go
+/// forward is an error.
+///
+/// Signature: `parts -- epoch`
+fn word_time_from_local(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let parts = interpreter.pop_as_hash_map()?;
+    let naive = parts_to_naive(interpreter, &parts)?;
+
+    match Local.from_local_datetime(&naive) {
+        LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => {
+            interpreter.push(time_to_epoch(&time));
+            Ok(())
+        }
+
+        LocalResult::None => script_error(
+            interpreter,
+            format!("The time {} does not exist in the local time zone.", naive),
+        ),
+    }
+}
+
+/// Format a time with a strftime style pattern, such as "%Y-%m-%d %H:%M:%S".  An epoch time is
+/// formatted in UTC, parts are formatted in their own offset.
+///
+/// Signature: `time pattern -- string`
+fn word_time_format(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let pattern = interpreter.pop_as_string()?;
+    let value = interpreter.pop()?;
+
+    let time = match &value {
+        Value::HashMap(parts) => parts_to_time(interpreter, parts)?,
+        _ => value_to_epoch(interpreter, &value)?.fixed_offset(),
+    };
+
+    let items: Vec<Item> = StrftimeItems::new(&pattern).collect();
+
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return script_error(
+            interpreter,
+            format!("Invalid time format pattern '{}'.", pattern),
+        );
+    }
+
+    let mut formatted = String::new();
+
+    match write!(formatted, "{}", time.format_with_items(items.iter())) {
+        Ok(()) => {
+            interpreter.push(formatted.to_value());
+            Ok(())
+        }
+
+        Err(_) => script_error(
+            interpreter,
+            format!("Could not format time with pattern '{}'.", pattern),
+        ),
+    }
+}
+
+/// Parse an ISO-8601 or RFC-3339 date and time into it's parts.  The T between the date and time
+/// can be a space, the seconds can have a fraction, and a date on it's own is taken as midnight.
+/// Times without an offset or Z are in UTC.
+///
+/// Signature: `string -- parts`
+fn word_time_parse(interpreter: &mut dyn Interpreter) -> error::Result<()> {
+    let text = interpreter.pop_as_string()?;
+    let trimmed = text.trim();
+
+    let time = DateTime::parse_from_rfc3339(trimmed)
+        .ok()
+        .or_else(|| {
+            [
+                "%Y-%m-%dT%H:%M:%S%.f%:z",
+                "%Y-%m-%dT%H:%M%:z",
+                "%Y-%m-%d %H:%M%:z",
+            ]
+            .iter()
+            .find_map(|format| DateTime::parse_from_str(trimmed, format).ok())
+        })
+        .or_else(|| {
+            [
+                "%Y-%m-%dT%H:%M:%S%.f",
+                "%Y-%m-%d %H:%M:%S%.f",
+                "%Y-%m-%dT%H:%M",
+                "%Y-%m-%d %H:%M",
+            ]
+            .iter()
+            .find_map(|format| NaiveDateTime::parse_from_str(trimmed, format).ok())
+            .or_else(|| {
+                NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+            })
+            .map(|naive| naive.and_utc().fixed_offset())
+        });
+
+    match time {
+        Some(time) => {
+            interpreter.push(time_to_parts(&time));
+            Ok(())
+        }
+
+        None => script_error(
+            interpreter,
+            format!("Could not parse '{}' as an ISO-8601 date and time.", text),
+        ),
+    }
+}
+
+pub fn register_time_words(interpreter: &mut dyn Interpreter) {
+    add_native_word!(
+        interpreter,
+        "time.now",
+        word_time_now,
+        "Get the current time as seconds since the Unix epoch.",
+        " -- seconds"
+    );
+
+    add_native_word!(
+        interpreter,
+        "time.now-ns",
+        word_time_now_ns,
+        "Get the current time as nanoseconds since the Unix epoch.",
+        " -- nanoseconds"
+    );
+
+    add_native_word!(
+        interpreter,
+        "time.monotonic",
+        word_time_monotonic,
+        "Get seconds from a clock that never goes backwards.",
+        " -- seconds"
+    );
+
+    add_native_word!(
+        interpreter,
+        "time.monotonic-ns",
+        word_time_monotonic_ns,
+        "Get nanoseconds from a clock that never goes backwards.",
+        " -- nanoseconds"
+    );
+
+    add_native_word!(
+        interpreter,
+        "time.sleep",
+        word_time_sleep,
+        "Pause for a number of seconds.",
+        "seconds -- "
+    );
+
+    add_native_word!(
+        interpreter,
+        "time.utc",
+        word_time_utc,
+        "Break an epoch time down into a hash table of it's parts in UTC.",
+        "epoch -- parts"
+    );
+
+    add_native_word!(
+        interpreter,
+        "time.local",
+        word_time_local,
+        "Break an epoch time down into a hash table of it's parts in the local time zone.",
+        "epoch -- parts"
+    );
+
+    add_native_word!(
+        interpreter,
+        "time.from-parts",
+        word_time_from_parts,
+        "Convert a hash table of time parts in their own offset to an epoch time.",
+        "parts -- epoch"
+    );
+
+    add_native_word!(
+        interpreter,
+        "time.from-local",
+        word_time_from_local,
+        "Convert a hash table of time parts in the local time zone to an epoch time.",
+        "parts -- epoch"
+    );
+
+    add_native_word!(
+        interpreter,
+        "time.format",
+        word_time_format,
+        "Format an epoch time or time parts with a strftime style pattern.",
+        "time pattern -- string"
+    );
+
+    add_native_word!(
+        interpreter,
+        "time.parse",
+        word_time_parse,
+        "Parse an ISO-8601 or RFC-3339 date and time into a hash table of it's parts.",
+        "string -- parts"
+    );
+}
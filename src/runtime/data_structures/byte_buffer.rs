@@ -2,19 +2,32 @@ use crate::runtime::data_structures::value::{DeepClone, ToValue, Value};
 use std::{
     cell::RefCell,
     fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
     os::raw::c_void,
     rc::Rc,
 };
 
+/// The byte order used when reading and writing multi-byte values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Hash)]
+pub enum Endian {
+    /// Least significant byte first.  This is the default for new buffers.
+    #[default]
+    Little,
+
+    /// Most significant byte first, also known as network byte order.
+    Big,
+}
+
 /// Trait to represent byte buffers.  It uses a cursor to perform reads and writes.  If a read or
-/// write would exceed the bounds of the buffer the operation will panic.
+/// write would exceed the bounds of the buffer the operation will panic.  Script facing code is
+/// expected to validate sizes and bounds before calling into the buffer.
 ///
 /// The byte buffer is a mutable buffer of bytes that is meant for use in the creation of binary
 /// data where every byte counts.
 ///
 /// The byte buffer is read from and written to in a linear fashion like a stream.  It includes
-/// methods for reading and writing integers, floats, (of various sizes,) and strings of constrained
-/// sizes.
+/// methods for reading and writing integers, floats, (of various sizes and byte orders,) strings of
+/// constrained sizes, and individual bits.
 ///
 /// This buffer should be most useful for binary data protocols and file formats.
 pub trait Buffer {
@@ -28,6 +41,17 @@ pub trait Buffer {
     /// zeros.  If the new size is smaller the buffer will be truncated.
     fn resize(&mut self, new_size: usize);
 
+    /// Make sure a resize to the new size is possible.  Returns an error message if it is not.
+    fn check_resize(&self, _new_size: usize) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Make sure the buffer's storage is still valid to access.  For example, a slice is no longer
+    /// valid if it's parent has shrunk out from under it.
+    fn check_bounds(&self) -> Result<(), String> {
+        Ok(())
+    }
+
     /// Get the length of the buffer.
     fn len(&self) -> usize;
 
@@ -44,44 +68,78 @@ pub trait Buffer {
     fn position_ptr_mut(&mut self) -> *mut c_void;
 
     /// Set the cursor position in the buffer.  If the position is greater than the buffer size the
-    /// operation will panic.
+    /// operation will panic.  Setting the position also resets the bit cursor to the start of the
+    /// byte.
     fn set_position(&mut self, position: usize);
 
     /// Increment the cursor position by a given amount.  If the new position is greater than the
     /// buffer size the operation will panic.
     fn increment_position(&mut self, increment: usize);
 
-    /// Write an integer to the buffer.  The integer will be written in little endian format.
+    /// Get the default byte order used by the buffer's reads and writes.
+    fn endian(&self) -> Endian;
+
+    /// Set the default byte order used by the buffer's reads and writes.
+    fn set_endian(&mut self, endian: Endian);
+
+    /// Get the bit cursor, the number of bits already used within the byte at the current position.
+    fn bit_offset(&self) -> usize;
+
+    /// Set the bit cursor within the byte at the current position.
+    fn set_bit_offset(&mut self, bit_offset: usize);
+
+    /// Write an integer to the buffer using the given byte order.
     ///
-    /// The byte size must be 1, 2, 4, or 8.  If the byte size is not one of these values the
+    /// The byte size must be from 1 to 8.  If the byte size is not one of these values the
     /// operation will panic.
     ///
     /// If the write would exceed the bounds of the buffer the operation will panic.
-    fn write_int(&mut self, byte_size: usize, value: i64);
+    fn write_int_endian(&mut self, byte_size: usize, value: i64, endian: Endian);
 
-    /// Read an integer from the buffer.  The integer will be read in little endian format.
+    /// Read an integer from the buffer using the given byte order.  Signed values are sign
+    /// extended.
     ///
-    /// The byte size must be 1, 2, 4, or 8.  If the byte size is not one of these values the
+    /// The byte size must be from 1 to 8.  If the byte size is not one of these values the
     /// operation will panic.
     ///
     /// If the read would exceed the bounds of the buffer the operation will panic.
-    fn read_int(&mut self, byte_size: usize, is_signed: bool) -> i64;
+    fn read_int_endian(&mut self, byte_size: usize, is_signed: bool, endian: Endian) -> i64;
 
-    /// Write a float to the buffer.  The float will be written in little endian format.
+    /// Write a float to the buffer using the given byte order.
     ///
     /// The byte size must be 4 or 8.  If the byte size is not one of these values the operation
     /// will panic.
     ///
     /// If the write would exceed the bounds of the buffer the operation will panic.
-    fn write_float(&mut self, byte_size: usize, value: f64);
+    fn write_float_endian(&mut self, byte_size: usize, value: f64, endian: Endian);
 
-    /// Read a float from the buffer.  The float will be read in little endian format.
+    /// Read a float from the buffer using the given byte order.
     ///
     /// The byte size must be a 4 or 8.  If the byte size is not one of these values the operation
     /// will panic.
     ///
     /// If the read would exceed the bounds of the buffer the operation will panic.
-    fn read_float(&mut self, byte_size: usize) -> f64;
+    fn read_float_endian(&mut self, byte_size: usize, endian: Endian) -> f64;
+
+    /// Write an integer to the buffer using the buffer's default byte order.
+    fn write_int(&mut self, byte_size: usize, value: i64) {
+        self.write_int_endian(byte_size, value, self.endian());
+    }
+
+    /// Read an integer from the buffer using the buffer's default byte order.
+    fn read_int(&mut self, byte_size: usize, is_signed: bool) -> i64 {
+        self.read_int_endian(byte_size, is_signed, self.endian())
+    }
+
+    /// Write a float to the buffer using the buffer's default byte order.
+    fn write_float(&mut self, byte_size: usize, value: f64) {
+        self.write_float_endian(byte_size, value, self.endian());
+    }
+
+    /// Read a float from the buffer using the buffer's default byte order.
+    fn read_float(&mut self, byte_size: usize) -> f64 {
+        self.read_float_endian(byte_size, self.endian())
+    }
 
     /// Write a string to the buffer.  If the string is larger than the given size, it will be
     /// truncated.  If the string is smaller than the given size, it will be padded with zeros.
@@ -92,6 +150,90 @@ pub trait Buffer {
     /// Read a string from the buffer.  The string will be read up to the given size.  If the string
     /// is smaller than the given size it will be terminated with a zero byte.
     fn read_string(&mut self, max_size: usize) -> String;
+
+    /// Copy a range of bytes out of the buffer.  The cursor is not used or moved.
+    ///
+    /// If the range exceeds the bounds of the buffer the operation will panic.
+    fn get_bytes(&self, offset: usize, count: usize) -> Vec<u8>;
+
+    /// Overwrite a range of the buffer's bytes.  The cursor is not used or moved.
+    ///
+    /// If the range exceeds the bounds of the buffer the operation will panic.
+    fn set_bytes(&mut self, offset: usize, bytes: &[u8]);
+
+    /// Copy all of the buffer's bytes.  A buffer that fails it's bounds check, such as a slice
+    /// whose parent has shrunk, has no bytes that can be accessed so the copy is empty.
+    fn contents(&self) -> Vec<u8> {
+        match self.check_bounds() {
+            Ok(()) => self.get_bytes(0, self.len()),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// How many bits are left to be read or written from the bit cursor to the end of the buffer?
+    fn bits_remaining(&self) -> usize {
+        (self.len() - self.position()) * 8 - self.bit_offset()
+    }
+
+    /// Write the low bit_count bits of the value at the bit cursor, most significant bit first.
+    /// Bits are packed starting at the high bit of each byte, and the other bits of a partially
+    /// written byte are left unchanged.
+    ///
+    /// If the write would exceed the bounds of the buffer the operation will panic.
+    fn write_bits(&mut self, bit_count: usize, value: u64) {
+        let mut bit_offset = self.bit_offset();
+
+        for index in (0..bit_count).rev() {
+            let position = self.position();
+            let mut byte = self.read_int_endian(1, false, Endian::Little) as u8;
+            let mask = 0x80u8 >> bit_offset;
+
+            if (value >> index) & 1 == 1 {
+                byte |= mask;
+            } else {
+                byte &= !mask;
+            }
+
+            self.set_position(position);
+            self.write_int_endian(1, byte as i64, Endian::Little);
+
+            bit_offset += 1;
+
+            if bit_offset < 8 {
+                self.set_position(position);
+            } else {
+                bit_offset = 0;
+            }
+
+            self.set_bit_offset(bit_offset);
+        }
+    }
+
+    /// Read bit_count bits from the bit cursor, most significant bit first.
+    ///
+    /// If the read would exceed the bounds of the buffer the operation will panic.
+    fn read_bits(&mut self, bit_count: usize) -> u64 {
+        let mut bit_offset = self.bit_offset();
+        let mut value = 0u64;
+
+        for _ in 0..bit_count {
+            let position = self.position();
+            let byte = self.read_int_endian(1, false, Endian::Little) as u8;
+
+            value = (value << 1) | ((byte >> (7 - bit_offset)) & 1) as u64;
+            bit_offset += 1;
+
+            if bit_offset < 8 {
+                self.set_position(position);
+            } else {
+                bit_offset = 0;
+            }
+
+            self.set_bit_offset(bit_offset);
+        }
+
+        value
+    }
 }
 
 impl Display for dyn Buffer {
@@ -102,12 +244,7 @@ impl Display for dyn Buffer {
         // 00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  | ........ ........ |
         // 00000010  00 00 00 00 00 00                                 | ......            |
 
-        let bytes = unsafe {
-            let ptr = self.byte_ptr();
-            let ptr_u8 = ptr as *const u8;
-
-            std::slice::from_raw_parts(ptr_u8, self.len())
-        };
+        let bytes = self.contents();
 
         writeln!(
             f,
@@ -160,6 +297,27 @@ impl Display for dyn Buffer {
     }
 }
 
+/// Buffers are equal if their contents are equal, regardless of their cursors.
+impl PartialEq for dyn Buffer {
+    fn eq(&self, other: &Self) -> bool {
+        self.contents() == other.contents()
+    }
+}
+
+/// Hash a buffer by it's contents.
+impl Hash for dyn Buffer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.contents().hash(state);
+    }
+}
+
+/// Buffers are ordered by their contents, byte by byte.
+impl PartialOrd for dyn Buffer {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.contents().partial_cmp(&other.contents())
+    }
+}
+
 /// Generic pointer to a buffer object.
 pub type BufferPtr = Rc<RefCell<dyn Buffer>>;
 
@@ -178,6 +336,8 @@ pub type BufferPtr = Rc<RefCell<dyn Buffer>>;
 pub struct ByteBuffer {
     buffer: Vec<u8>,
     current_position: usize,
+    endian: Endian,
+    bit_offset: usize,
 }
 
 /// A reference counted pointer to a byte buffer.
@@ -196,7 +356,8 @@ impl Buffer for ByteBuffer {
         self.buffer.resize(new_size, 0);
 
         if self.current_position >= new_size {
-            self.current_position = new_size - 1;
+            self.current_position = new_size;
+            self.bit_offset = 0;
         }
     }
 
@@ -209,7 +370,7 @@ impl Buffer for ByteBuffer {
     }
 
     fn position_ptr_mut(&mut self) -> *mut c_void {
-        &mut self.current_position as *mut usize as *mut c_void
+        unsafe { self.buffer.as_mut_ptr().add(self.current_position) as *mut c_void }
     }
 
     fn set_position(&mut self, position: usize) {
@@ -222,19 +383,37 @@ impl Buffer for ByteBuffer {
         }
 
         self.current_position = position;
+        self.bit_offset = 0;
     }
 
     fn increment_position(&mut self, increment: usize) {
         self.set_position(self.current_position + increment);
     }
 
-    fn write_int(&mut self, byte_size: usize, value: i64) {
-        let bytes = match byte_size {
-            1 => value.to_le_bytes()[0..1].to_vec(),
-            2 => value.to_le_bytes()[0..2].to_vec(),
-            4 => value.to_le_bytes()[0..4].to_vec(),
-            8 => value.to_le_bytes()[0..8].to_vec(),
-            _ => panic!("Invalid byte size for integer write {}.", byte_size),
+    fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    fn set_endian(&mut self, endian: Endian) {
+        self.endian = endian;
+    }
+
+    fn bit_offset(&self) -> usize {
+        self.bit_offset
+    }
+
+    fn set_bit_offset(&mut self, bit_offset: usize) {
+        self.bit_offset = bit_offset;
+    }
+
+    fn write_int_endian(&mut self, byte_size: usize, value: i64, endian: Endian) {
+        if !(1..=8).contains(&byte_size) {
+            panic!("Invalid byte size for integer write {}.", byte_size);
+        }
+
+        let bytes = match endian {
+            Endian::Little => value.to_le_bytes()[..byte_size].to_vec(),
+            Endian::Big => value.to_be_bytes()[8 - byte_size..].to_vec(),
         };
 
         let position = self.current_position;
@@ -243,63 +422,47 @@ impl Buffer for ByteBuffer {
         self.buffer[position..position + byte_size].copy_from_slice(&bytes);
     }
 
-    fn read_int(&mut self, byte_size: usize, is_signed: bool) -> i64 {
+    fn read_int_endian(&mut self, byte_size: usize, is_signed: bool, endian: Endian) -> i64 {
+        if !(1..=8).contains(&byte_size) {
+            panic!("Invalid byte size for integer read {}.", byte_size);
+        }
+
         let position = self.current_position;
 
         self.increment_position(byte_size);
 
-        match byte_size {
-            1 => {
-                let mut bytes = [0; 1];
-
-                bytes.copy_from_slice(&self.buffer[position..position + 1]);
-                bytes[0] as i64
-            }
-            2 => {
-                let mut bytes = [0; 2];
-
-                bytes.copy_from_slice(&self.buffer[position..position + 2]);
+        let bytes = &self.buffer[position..position + byte_size];
+        let mut full = [0; 8];
 
-                if is_signed {
-                    i16::from_le_bytes(bytes) as i64
-                } else {
-                    u16::from_le_bytes(bytes) as i64
-                }
+        let value = match endian {
+            Endian::Little => {
+                full[..byte_size].copy_from_slice(bytes);
+                u64::from_le_bytes(full)
             }
 
-            4 => {
-                let mut bytes = [0; 4];
-
-                bytes.copy_from_slice(&self.buffer[position..position + 4]);
-
-                if is_signed {
-                    i32::from_le_bytes(bytes) as i64
-                } else {
-                    u32::from_le_bytes(bytes) as i64
-                }
+            Endian::Big => {
+                full[8 - byte_size..].copy_from_slice(bytes);
+                u64::from_be_bytes(full)
             }
+        };
 
-            8 => {
-                let mut bytes = [0; 8];
-
-                bytes.copy_from_slice(&self.buffer[position..position + 8]);
-
-                if is_signed {
-                    i64::from_le_bytes(bytes)
-                } else {
-                    u64::from_le_bytes(bytes) as i64
-                }
-            }
+        // Sign extend the value if it's signed and smaller than the full 64 bits.
+        let shift = 64 - byte_size * 8;
 
-            _ => panic!("Invalid byte size for integer read {}.", byte_size),
+        if is_signed && shift > 0 {
+            ((value << shift) as i64) >> shift
+        } else {
+            value as i64
         }
     }
 
-    fn write_float(&mut self, byte_size: usize, value: f64) {
-        let bytes = match byte_size {
-            4 => (value as f32).to_le_bytes()[0..4].to_vec(),
-            8 => value.to_le_bytes()[0..8].to_vec(),
-            _ => panic!("Invalid byte size for integer write {}.", byte_size),
+    fn write_float_endian(&mut self, byte_size: usize, value: f64, endian: Endian) {
+        let bytes = match (byte_size, endian) {
+            (4, Endian::Little) => (value as f32).to_le_bytes().to_vec(),
+            (4, Endian::Big) => (value as f32).to_be_bytes().to_vec(),
+            (8, Endian::Little) => value.to_le_bytes().to_vec(),
+            (8, Endian::Big) => value.to_be_bytes().to_vec(),
+            _ => panic!("Invalid byte size for float write {}.", byte_size),
         };
 
         let position = self.current_position;
@@ -308,27 +471,22 @@ impl Buffer for ByteBuffer {
         self.buffer[position..position + byte_size].copy_from_slice(&bytes);
     }
 
-    fn read_float(&mut self, byte_size: usize) -> f64 {
+    fn read_float_endian(&mut self, byte_size: usize, endian: Endian) -> f64 {
         let position = self.current_position;
 
-        self.increment_position(byte_size);
-
-        match byte_size {
-            4 => {
-                let mut bytes = [0; 4];
-
-                bytes.copy_from_slice(&self.buffer[position..position + 4]);
-                f32::from_le_bytes(bytes) as f64
-            }
+        if byte_size != 4 && byte_size != 8 {
+            panic!("Invalid byte size for float read {}.", byte_size);
+        }
 
-            8 => {
-                let mut bytes = [0; 8];
+        self.increment_position(byte_size);
 
-                bytes.copy_from_slice(&self.buffer[position..position + 8]);
-                f64::from_le_bytes(bytes)
-            }
+        let bytes = &self.buffer[position..position + byte_size];
 
-            _ => panic!("Invalid byte size for integer read {}.", byte_size),
+        match (byte_size, endian) {
+            (4, Endian::Little) => f32::from_le_bytes(bytes.try_into().unwrap()) as f64,
+            (4, Endian::Big) => f32::from_be_bytes(bytes.try_into().unwrap()) as f64,
+            (_, Endian::Little) => f64::from_le_bytes(bytes.try_into().unwrap()),
+            (_, Endian::Big) => f64::from_be_bytes(bytes.try_into().unwrap()),
         }
     }
 
@@ -357,23 +515,49 @@ impl Buffer for ByteBuffer {
 
         String::from_utf8_lossy(&bytes[0..end]).to_string()
     }
+
+    fn get_bytes(&self, offset: usize, count: usize) -> Vec<u8> {
+        self.buffer[offset..offset + count].to_vec()
+    }
+
+    fn set_bytes(&mut self, offset: usize, bytes: &[u8]) {
+        self.buffer[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
 }
 
-/// Deep copy the byte buffer for the Value type.
-impl DeepClone for ByteBufferPtr {
+/// Deep copy a buffer for the Value type.  Copying a slice produces a new independent buffer with
+/// the slice's contents.
+impl DeepClone for BufferPtr {
     fn deep_clone(&self) -> Value {
-        let new_buffer = ByteBuffer::new_ptr(self.borrow().len());
+        let buffer = self.borrow();
+        let bytes = buffer.contents();
+        let new_buffer = ByteBuffer::new_ptr(bytes.len());
 
-        new_buffer
-            .borrow_mut()
-            .buffer
-            .copy_from_slice(&self.borrow().buffer[0..self.borrow().len()]);
-        new_buffer.borrow_mut().current_position = self.borrow().current_position;
+        {
+            let mut new_buffer = new_buffer.borrow_mut();
+
+            // A stale slice copies as empty, so it's cursor is left at the start.
+            if bytes.len() == buffer.len() {
+                new_buffer.current_position = buffer.position();
+                new_buffer.bit_offset = buffer.bit_offset();
+            }
+
+            new_buffer.buffer = bytes;
+            new_buffer.endian = buffer.endian();
+        }
 
         new_buffer.to_value()
     }
 }
 
+/// Allow a concrete byte buffer pointer to be converted into a Value.
+impl ToValue for ByteBufferPtr {
+    fn to_value(&self) -> Value {
+        let buffer: BufferPtr = self.clone();
+        buffer.to_value()
+    }
+}
+
 /// Display the byte buffer in a hex dump format.
 impl Display for ByteBuffer {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
@@ -390,6 +574,8 @@ impl ByteBuffer {
         ByteBuffer {
             buffer,
             current_position: 0,
+            endian: Endian::Little,
+            bit_offset: 0,
         }
     }
 
@@ -398,14 +584,19 @@ impl ByteBuffer {
         Rc::new(RefCell::new(ByteBuffer::new(new_len)))
     }
 
+    /// Get read only access to the raw bytes of the buffer.
+    pub fn buffer(&self) -> &Vec<u8> {
+        &self.buffer
+    }
+
     pub fn buffer_mut(&mut self) -> &mut Vec<u8> {
         &mut self.buffer
     }
 }
 
 /// A concrete implementation of the Buffer trait.  This buffer is a sub-buffer of another buffer
-/// and is meant to be used to read and write data from a specific range of the parent buffer.
-#[allow(dead_code)]
+/// and is meant to be used to read and write data from a specific range of the parent buffer.  The
+/// storage is shared with the parent, but the sub-buffer has it's own cursor.
 pub struct SubBuffer {
     /// The real backing store for this sub-buffer.
     parent: BufferPtr,
@@ -418,37 +609,67 @@ pub struct SubBuffer {
 
     /// This buffer's cursor position within it's allocated range.
     current_position: usize,
+
+    /// The bit cursor within the byte at the current position.
+    bit_offset: usize,
+
+    /// The default byte order of this view, starting out as the parent's.  Changing it doesn't
+    /// change the parent's.
+    endian: Endian,
 }
 
 impl Buffer for SubBuffer {
     fn byte_ptr(&self) -> *const c_void {
-        let position = self.parent.borrow().position();
+        let ptr = self.parent.borrow().byte_ptr() as *const u8;
+        unsafe { ptr.add(self.start) as *const c_void }
+    }
+
+    fn byte_ptr_mut(&mut self) -> *mut c_void {
+        let ptr = self.parent.borrow_mut().byte_ptr_mut() as *mut u8;
+        unsafe { ptr.add(self.start) as *mut c_void }
+    }
+
+    fn resize(&mut self, new_size: usize) {
+        if let Err(message) = self.check_resize(new_size) {
+            panic!("{}", message);
+        }
 
-        self.parent.borrow_mut().set_position(self.start);
-        let ptr = self.parent.borrow().byte_ptr();
-        self.parent.borrow_mut().set_position(position);
+        self.end = self.start + new_size;
 
-        ptr
+        if self.current_position >= new_size {
+            self.current_position = new_size;
+            self.bit_offset = 0;
+        }
     }
 
-    fn byte_ptr_mut(&mut self) -> *mut c_void {
-        let position = self.parent.borrow().position();
+    fn check_resize(&self, new_size: usize) -> Result<(), String> {
+        let parent_len = self.parent.borrow().len();
 
-        self.parent.borrow_mut().set_position(self.start);
-        let ptr = self.parent.borrow_mut().byte_ptr_mut();
-        self.parent.borrow_mut().set_position(position);
+        if self.start + new_size > parent_len {
+            return Err(format!(
+                "Resizing a slice at offset {} to size {} would exceed the parent buffer size {}.",
+                self.start, new_size, parent_len
+            ));
+        }
 
-        ptr
+        Ok(())
     }
 
-    fn resize(&mut self, new_size: usize) {
-        let new_end = self.start + new_size;
+    fn check_bounds(&self) -> Result<(), String> {
+        let parent = self.parent.borrow();
 
-        if new_end > self.parent.borrow().len() {
-            panic!("Attempted to resize a sub-buffer to a size larger than the parent buffer.");
+        parent.check_bounds()?;
+
+        if self.end > parent.len() {
+            return Err(format!(
+                "Slice range {}..{} is outside of the parent buffer of size {}.",
+                self.start,
+                self.end,
+                parent.len()
+            ));
         }
 
-        self.end = new_end;
+        Ok(())
     }
 
     fn len(&self) -> usize {
@@ -460,15 +681,8 @@ impl Buffer for SubBuffer {
     }
 
     fn position_ptr_mut(&mut self) -> *mut c_void {
-        let position = self.parent.borrow().position();
-
-        self.parent
-            .borrow_mut()
-            .set_position(self.start + self.current_position);
-        let ptr = self.parent.borrow_mut().byte_ptr_mut();
-        self.parent.borrow_mut().set_position(position);
-
-        ptr
+        let ptr = self.byte_ptr_mut() as *mut u8;
+        unsafe { ptr.add(self.current_position) as *mut c_void }
     }
 
     fn set_position(&mut self, position: usize) {
@@ -481,135 +695,365 @@ impl Buffer for SubBuffer {
         }
 
         self.current_position = position;
+        self.bit_offset = 0;
     }
 
     fn increment_position(&mut self, increment: usize) {
         self.set_position(self.current_position + increment);
     }
 
-    fn write_int(&mut self, byte_size: usize, value: i64) {
-        {
-            let mut parent = self.parent.borrow_mut();
-            let position = parent.position();
+    fn endian(&self) -> Endian {
+        self.endian
+    }
 
-            parent.set_position(self.start + self.current_position);
-            parent.write_int(byte_size, value);
-            parent.set_position(position);
-        }
+    fn set_endian(&mut self, endian: Endian) {
+        self.endian = endian;
+    }
 
-        self.increment_position(byte_size);
+    fn bit_offset(&self) -> usize {
+        self.bit_offset
     }
 
-    fn read_int(&mut self, byte_size: usize, is_signed: bool) -> i64 {
-        let value = {
-            let mut parent = self.parent.borrow_mut();
-            let position = parent.position();
+    fn set_bit_offset(&mut self, bit_offset: usize) {
+        self.bit_offset = bit_offset;
+    }
 
-            parent.set_position(self.start + self.current_position);
-            let value = parent.read_int(byte_size, is_signed);
-            parent.set_position(position);
+    fn write_int_endian(&mut self, byte_size: usize, value: i64, endian: Endian) {
+        self.with_parent(byte_size, |parent| {
+            parent.write_int_endian(byte_size, value, endian)
+        });
+    }
 
-            value
-        };
+    fn read_int_endian(&mut self, byte_size: usize, is_signed: bool, endian: Endian) -> i64 {
+        self.with_parent(byte_size, |parent| {
+            parent.read_int_endian(byte_size, is_signed, endian)
+        })
+    }
 
-        self.increment_position(byte_size);
+    fn write_float_endian(&mut self, byte_size: usize, value: f64, endian: Endian) {
+        self.with_parent(byte_size, |parent| {
+            parent.write_float_endian(byte_size, value, endian)
+        });
+    }
 
-        value
+    fn read_float_endian(&mut self, byte_size: usize, endian: Endian) -> f64 {
+        self.with_parent(byte_size, |parent| {
+            parent.read_float_endian(byte_size, endian)
+        })
     }
 
-    fn write_float(&mut self, byte_size: usize, value: f64) {
-        {
-            let mut parent = self.parent.borrow_mut();
-            let position = parent.position();
+    fn write_string(&mut self, max_size: usize, value: &str) {
+        self.with_parent(max_size, |parent| parent.write_string(max_size, value));
+    }
 
-            parent.set_position(self.start + self.current_position);
-            parent.write_float(byte_size, value);
-            parent.set_position(position);
+    fn read_string(&mut self, max_size: usize) -> String {
+        self.with_parent(max_size, |parent| parent.read_string(max_size))
+    }
+
+    fn get_bytes(&self, offset: usize, count: usize) -> Vec<u8> {
+        self.check_range(offset, count);
+        self.parent.borrow().get_bytes(self.start + offset, count)
+    }
+
+    fn set_bytes(&mut self, offset: usize, bytes: &[u8]) {
+        self.check_range(offset, bytes.len());
+        self.parent
+            .borrow_mut()
+            .set_bytes(self.start + offset, bytes);
+    }
+}
+
+/// Display the sub-buffer in a hex dump format.
+impl Display for SubBuffer {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let buffer = self as &dyn Buffer;
+        write!(f, "{}", buffer)
+    }
+}
+
+impl SubBuffer {
+    /// Create a new sub-buffer from a parent buffer with a specified range inside of that buffer.
+    pub fn new(parent: BufferPtr, start: usize, end: usize) -> SubBuffer {
+        let parent_len = parent.borrow().len();
+
+        if start > end || end > parent_len {
+            panic!("Attempted to create a sub-buffer with a range outside of the parent buffer.");
         }
 
-        self.increment_position(byte_size);
+        let endian = parent.borrow().endian();
+
+        SubBuffer {
+            parent,
+            start,
+            end,
+            current_position: 0,
+            bit_offset: 0,
+            endian,
+        }
     }
 
-    fn read_float(&mut self, byte_size: usize) -> f64 {
-        let value = {
+    /// Create a new sub-buffer ptr from a parent buffer within a specified range.
+    pub fn new_ptr(parent: BufferPtr, start: usize, end: usize) -> BufferPtr {
+        Rc::new(RefCell::new(SubBuffer::new(parent, start, end)))
+    }
+
+    /// Make sure that a range of this sub-buffer is within both the sub-buffer and the parent.
+    fn check_range(&self, offset: usize, count: usize) {
+        if offset + count > self.len() {
+            panic!(
+                "Attempted to access {} bytes at offset {} in a sub-buffer of size {}.",
+                count,
+                offset,
+                self.len()
+            );
+        }
+
+        if let Err(message) = self.check_bounds() {
+            panic!("{}", message);
+        }
+    }
+
+    /// Perform an operation on the parent buffer at this buffer's cursor, then advance this
+    /// buffer's cursor.  The parent's own cursor is left untouched.
+    fn with_parent<F, R>(&mut self, size: usize, operation: F) -> R
+    where
+        F: FnOnce(&mut dyn Buffer) -> R,
+    {
+        self.check_range(self.current_position, size);
+
+        let result = {
             let mut parent = self.parent.borrow_mut();
             let position = parent.position();
+            let bit_offset = parent.bit_offset();
 
             parent.set_position(self.start + self.current_position);
-            let value = parent.read_float(byte_size);
+            let result = operation(&mut *parent);
             parent.set_position(position);
+            parent.set_bit_offset(bit_offset);
 
-            value
+            result
         };
 
-        self.increment_position(byte_size);
+        self.increment_position(size);
 
-        value
+        result
     }
+}
 
-    fn write_string(&mut self, max_size: usize, value: &str) {
+/// A native function that releases memory, such as C's free.
+pub type NativeFree = unsafe extern "C" fn(*mut c_void);
+
+/// A concrete implementation of the Buffer trait over memory that lives outside of sorth, such as
+/// memory returned from a foreign function.  The buffer either borrows the memory, in which case
+/// the native code must keep it alive for as long as the buffer is used, or owns it and releases it
+/// with a native free function when the buffer is dropped.
+///
+/// Native memory can not be resized.
+pub struct NativeBuffer {
+    /// The start of the native memory.
+    address: *mut u8,
+
+    /// The size of the native memory in bytes.
+    size: usize,
+
+    /// The buffer's cursor position.
+    current_position: usize,
+
+    /// The default byte order for reads and writes.
+    endian: Endian,
+
+    /// The bit cursor within the byte at the current position.
+    bit_offset: usize,
+
+    /// The function to release the memory with, if the buffer owns it.
+    free: Option<NativeFree>,
+}
+
+impl Drop for NativeBuffer {
+    fn drop(&mut self) {
+        if let Some(free) = self.free
+            && !self.address.is_null()
         {
-            let mut parent = self.parent.borrow_mut();
-            let position = parent.position();
+            unsafe { free(self.address as *mut c_void) };
+        }
+    }
+}
 
-            parent.set_position(self.start + self.current_position);
-            parent.write_string(max_size, value);
-            parent.set_position(position);
+impl Buffer for NativeBuffer {
+    fn byte_ptr(&self) -> *const c_void {
+        self.address as *const c_void
+    }
+
+    fn byte_ptr_mut(&mut self) -> *mut c_void {
+        self.address as *mut c_void
+    }
+
+    fn resize(&mut self, new_size: usize) {
+        if let Err(message) = self.check_resize(new_size) {
+            panic!("{}", message);
         }
+    }
 
-        self.increment_position(max_size);
+    fn check_resize(&self, new_size: usize) -> Result<(), String> {
+        if new_size != self.size {
+            return Err(format!(
+                "A buffer of native memory can not be resized from {} to {}.",
+                self.size, new_size
+            ));
+        }
+
+        Ok(())
     }
 
-    fn read_string(&mut self, max_size: usize) -> String {
-        let value = {
-            let mut parent = self.parent.borrow_mut();
-            let position = parent.position();
+    fn len(&self) -> usize {
+        self.size
+    }
 
-            parent.set_position(self.start + self.current_position);
-            let value = parent.read_string(max_size);
-            parent.set_position(position);
+    fn position(&self) -> usize {
+        self.current_position
+    }
 
-            value
-        };
+    fn position_ptr_mut(&mut self) -> *mut c_void {
+        unsafe { self.address.add(self.current_position) as *mut c_void }
+    }
 
-        self.increment_position(max_size);
+    fn set_position(&mut self, position: usize) {
+        if position > self.size {
+            panic!(
+                "Attempted to set position to {} in a buffer of size {}.",
+                position, self.size
+            );
+        }
 
-        value
+        self.current_position = position;
+        self.bit_offset = 0;
+    }
+
+    fn increment_position(&mut self, increment: usize) {
+        self.set_position(self.current_position + increment);
+    }
+
+    fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    fn set_endian(&mut self, endian: Endian) {
+        self.endian = endian;
+    }
+
+    fn bit_offset(&self) -> usize {
+        self.bit_offset
+    }
+
+    fn set_bit_offset(&mut self, bit_offset: usize) {
+        self.bit_offset = bit_offset;
+    }
+
+    fn write_int_endian(&mut self, byte_size: usize, value: i64, endian: Endian) {
+        self.with_scratch(byte_size, |scratch| {
+            scratch.write_int_endian(byte_size, value, endian)
+        });
+    }
+
+    fn read_int_endian(&mut self, byte_size: usize, is_signed: bool, endian: Endian) -> i64 {
+        self.with_scratch(byte_size, |scratch| {
+            scratch.read_int_endian(byte_size, is_signed, endian)
+        })
+    }
+
+    fn write_float_endian(&mut self, byte_size: usize, value: f64, endian: Endian) {
+        self.with_scratch(byte_size, |scratch| {
+            scratch.write_float_endian(byte_size, value, endian)
+        });
+    }
+
+    fn read_float_endian(&mut self, byte_size: usize, endian: Endian) -> f64 {
+        self.with_scratch(byte_size, |scratch| {
+            scratch.read_float_endian(byte_size, endian)
+        })
+    }
+
+    fn write_string(&mut self, max_size: usize, value: &str) {
+        self.with_scratch(max_size, |scratch| scratch.write_string(max_size, value));
+    }
+
+    fn read_string(&mut self, max_size: usize) -> String {
+        self.with_scratch(max_size, |scratch| scratch.read_string(max_size))
+    }
+
+    fn get_bytes(&self, offset: usize, count: usize) -> Vec<u8> {
+        self.check_range(offset, count);
+        unsafe { std::slice::from_raw_parts(self.address.add(offset), count).to_vec() }
+    }
+
+    fn set_bytes(&mut self, offset: usize, bytes: &[u8]) {
+        self.check_range(offset, bytes.len());
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), self.address.add(offset), bytes.len())
+        };
     }
 }
 
-/// Display the sub-buffer in a hex dump format.
-impl Display for SubBuffer {
+/// Display the native buffer in a hex dump format.
+impl Display for NativeBuffer {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let buffer = self as &dyn Buffer;
         write!(f, "{}", buffer)
     }
 }
 
-impl SubBuffer {
-    ///// Create a new sub-buffer from a parent buffer with a specified range inside of that buffer.
-    //fn new(parent: BufferPtr, start: usize, end: usize) -> SubBuffer
-    //{
-    //    let parent_len = parent.borrow().len();
-    //
-    //    if    start > parent_len
-    //       || end > parent_len
-    //    {
-    //        panic!("Attempted to create a sub-buffer with a range outside of the parent buffer.");
-    //    }
-    //
-    //    SubBuffer
-    //        {
-    //            parent,
-    //            start,
-    //            end,
-    //            current_position: 0
-    //        }
-    //}
-    //
-    ///// Create a new sub-buffer ptr from a parent buffer within a specified range.
-    //fn new_ptr(parent: BufferPtr, start: usize, end: usize) -> BufferPtr
-    //{
-    //    Rc::new(RefCell::new(SubBuffer::new(parent, start, end)))
-    //}
+impl NativeBuffer {
+    /// Create a new buffer over native memory.  If a free function is given the buffer takes
+    /// ownership of the memory.  The memory must be valid for the given size.
+    pub fn new(address: *mut c_void, size: usize, free: Option<NativeFree>) -> NativeBuffer {
+        if address.is_null() && size > 0 {
+            panic!(
+                "Attempted to create a native buffer of size {} at null.",
+                size
+            );
+        }
+
+        NativeBuffer {
+            address: address as *mut u8,
+            size,
+            current_position: 0,
+            endian: Endian::Little,
+            bit_offset: 0,
+            free,
+        }
+    }
+
+    /// Create a new native buffer ptr over native memory.
+    pub fn new_ptr(address: *mut c_void, size: usize, free: Option<NativeFree>) -> BufferPtr {
+        Rc::new(RefCell::new(NativeBuffer::new(address, size, free)))
+    }
+
+    /// Make sure that a range is within the native memory.
+    fn check_range(&self, offset: usize, count: usize) {
+        if offset + count > self.size {
+            panic!(
+                "Attempted to access {} bytes at offset {} in a native buffer of size {}.",
+                count, offset, self.size
+            );
+        }
+    }
+
+    /// Perform an operation on a copy of the bytes at this buffer's cursor, write them back, then
+    /// advance the cursor.
+    fn with_scratch<F, R>(&mut self, size: usize, operation: F) -> R
+    where
+        F: FnOnce(&mut dyn Buffer) -> R,
+    {
+        let position = self.current_position;
+        let mut scratch = ByteBuffer::new(0);
+
+        scratch.buffer = self.get_bytes(position, size);
+
+        let result = operation(&mut scratch);
+
+        self.set_bytes(position, &scratch.buffer);
+        self.increment_position(size);
+
+        result
+    }
 }
@@ -0,0 +1,55 @@
+use regex::Regex;
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Deref,
+    rc::Rc,
+};
+
+/// A compiled regular expression as handed to scripts by regex.compile.  The compiled pattern is
+/// shared by reference between copies of the value.  Comparing, hashing and printing the value
+/// works with the pattern's source text.
+#[derive(Clone)]
+pub struct CompiledRegex(Rc<Regex>);
+
+impl CompiledRegex {
+    /// Wrap an already compiled regular expression.
+    pub fn new(regex: Regex) -> CompiledRegex {
+        CompiledRegex(Rc::new(regex))
+    }
+}
+
+/// Give access to the underlying regular expression for matching.
+impl Deref for CompiledRegex {
+    type Target = Regex;
+
+    fn deref(&self) -> &Regex {
+        &self.0
+    }
+}
+
+impl PartialEq for CompiledRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialOrd for CompiledRegex {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_str().partial_cmp(other.as_str())
+    }
+}
+
+impl Hash for CompiledRegex {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+/// Print the regex as it's pattern, the same way the regex crate does.
+impl Display for CompiledRegex {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
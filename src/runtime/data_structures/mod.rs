@@ -27,3 +27,6 @@ pub mod value_hash;
 
 /// Module for the ByteBuffer data structure.
 pub mod byte_buffer;
+
+/// The compiled regular expression value created by the regex words.
+pub mod compiled_regex;
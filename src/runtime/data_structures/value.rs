@@ -8,7 +8,8 @@ use crate::{
     },
     runtime::{
         data_structures::{
-            byte_buffer::ByteBufferPtr,
+            byte_buffer::BufferPtr,
+            compiled_regex::CompiledRegex,
             data_object::DataObjectPtr,
             value_hash::ValueHashPtr,
             value_vec::{ValueVec, ValueVecPtr},
@@ -53,7 +54,10 @@ pub enum Value {
     DataObject(DataObjectPtr),
 
     /// A buffer for holding binary data.
-    ByteBuffer(ByteBufferPtr),
+    ByteBuffer(BufferPtr),
+
+    /// A compiled regular expression.  The compiled pattern is shared by reference.
+    Regex(CompiledRegex),
 
     /// A Forth source code token.
     Token(Token),
@@ -133,6 +137,7 @@ impl PartialEq for Value {
                 (Value::Token(a), Value::Token(b)) => a == b,
                 (Value::HashMap(a), Value::HashMap(b)) => *a.borrow() == *b.borrow(),
                 (Value::ByteBuffer(a), Value::ByteBuffer(b)) => *a.borrow() == *b.borrow(),
+                (Value::Regex(a), Value::Regex(b)) => a == b,
                 (Value::Code(a), Value::Code(b)) => a == b,
 
                 _ => false,
@@ -154,6 +159,7 @@ impl Hash for Value {
             Value::HashMap(value) => value.borrow().hash(state),
             Value::DataObject(value) => value.borrow().hash(state),
             Value::ByteBuffer(value) => value.borrow().hash(state),
+            Value::Regex(value) => value.hash(state),
             Value::Token(value) => value.hash(state),
             Value::Code(value) => value.hash(state),
         }
@@ -173,6 +179,7 @@ impl Display for Value {
             Value::HashMap(value) => write!(f, "{}", value.borrow()),
             Value::DataObject(value) => write!(f, "{}", value.borrow()),
             Value::ByteBuffer(value) => write!(f, "{}", value.borrow()),
+            Value::Regex(value) => write!(f, "{}", value),
             Value::Token(value) => write!(f, "{}", value),
             Value::Code(value) => write!(f, "{}", pretty_print_code(None, value)),
         }
@@ -281,7 +288,8 @@ value_conversion!(String, String, as_string);
 value_conversion!(ValueVecPtr, Vec, as_vec);
 value_conversion!(ValueHashPtr, HashMap, as_hash_map);
 value_conversion!(DataObjectPtr, DataObject, as_data_object);
-value_conversion!(ByteBufferPtr, ByteBuffer, as_byte_buffer);
+value_conversion!(BufferPtr, ByteBuffer, as_byte_buffer);
+value_conversion!(CompiledRegex, Regex, as_regex);
 value_conversion!(Token, Token, as_token);
 value_conversion!(ByteCode, Code, as_code);
 
@@ -326,6 +334,7 @@ impl Value {
     is_variant!(is_hash_map, either_is_hash_map, HashMap);
     is_variant!(is_data_object, either_is_data_object, DataObject);
     is_variant!(is_byte_buffer, either_is_byte_buffer, ByteBuffer);
+    is_variant!(is_regex, either_is_regex, Regex);
     is_variant!(is_token, either_is_token, Token);
     is_variant!(is_code, either_is_code, Code);
 
@@ -494,6 +503,7 @@ impl DeepClone for Value {
             Value::HashMap(value) => value.deep_clone(),
             Value::DataObject(value) => value.deep_clone(),
             Value::ByteBuffer(value) => value.deep_clone(),
+            Value::Regex(value) => Value::Regex(value.clone()),
             Value::Token(value) => Value::Token(value.clone()),
             Value::Code(value) => Value::Code(value.clone()),
         }
@@ -136,6 +136,11 @@ impl ValueVec {
         let _ = self.values.remove(index);
     }
 
+    /// Swap the values at two locations within the ValueVec.
+    pub fn swap(&mut self, first: usize, second: usize) {
+        self.values.swap(first, second);
+    }
+
     /// Push a new value onto the front of the ValueVec.
     pub fn push_front(&mut self, value: Value) {
         self.values.push_front(value);
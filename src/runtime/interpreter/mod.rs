@@ -6,9 +6,12 @@ use crate::{
         tokenizing::{NumberType, Token, TokenList},
     },
     runtime::{
-        built_ins::ffi_words::FfiInterface,
+        built_ins::{
+            binary_layout_words::BinaryLayoutList, ffi_words::FfiInterface,
+            random_words::RandomGenerators, regex_words::RegexCache,
+        },
         data_structures::{
-            byte_buffer::ByteBufferPtr,
+            byte_buffer::BufferPtr,
             contextual_data::ContextualData,
             contextual_list::ContextualList,
             data_object::{DataDefinitionList, DataObjectDefinitionPtr, DataObjectPtr},
@@ -127,7 +130,7 @@ pub trait InterpreterStack {
 
     /// Pop the top value and attempt to convert it to a byte buffer.  If the value can not be
     /// converted an error is returned.  We also fail if the stack is empty.
-    fn pop_as_byte_buffer(&mut self) -> error::Result<ByteBufferPtr>;
+    fn pop_as_byte_buffer(&mut self) -> error::Result<BufferPtr>;
 
     /// Pop the top value and attempt to convert it to a token.  If the value can not be converted
     /// an error is returned.  We also fail if the stack is empty.
@@ -422,6 +425,24 @@ pub trait Ffi {
     fn ffi_mut(&mut self) -> &mut FfiInterface;
 }
 
+/// Trait for managing the interpreter's cache of compiled regular expressions.
+pub trait RegexPatterns {
+    fn regex(&self) -> &RegexCache;
+    fn regex_mut(&mut self) -> &mut RegexCache;
+}
+
+/// Trait for managing the interpreter's list of binary layouts.
+pub trait BinaryLayouts {
+    fn binary_layouts(&self) -> &BinaryLayoutList;
+    fn binary_layouts_mut(&mut self) -> &mut BinaryLayoutList;
+}
+
+/// Trait for managing the interpreter's random number generators.
+pub trait RandomNumbers {
+    fn random(&self) -> &RandomGenerators;
+    fn random_mut(&mut self) -> &mut RandomGenerators;
+}
+
 /// Core interpreter trait.
 ///
 /// This trait defines and brings together the traits that define the core functionality of the
@@ -431,7 +452,15 @@ pub trait Ffi {
 /// Managing and executing bytecode and words.  As well as managing interpreter sub-threads for user
 /// code.
 pub trait Interpreter:
-    ContextualData + InterpreterStack + CodeManagement + WordManagement + ThreadManagement + Ffi
+    ContextualData
+    + InterpreterStack
+    + CodeManagement
+    + WordManagement
+    + ThreadManagement
+    + Ffi
+    + RegexPatterns
+    + BinaryLayouts
+    + RandomNumbers
 {
     /// Add a new path to the search path list.  This path will be checked to make sure that it
     /// exists.
@@ -461,6 +490,10 @@ pub trait Interpreter:
     /// The current list of data object definitions known to the interpreter.
     fn structure_definitions(&self) -> &DataDefinitionList;
 
+    /// A number that tells this interpreter apart from any others in the process.  Used to find the
+    /// process wide resources, such as auto-delete files, that belong to this interpreter.
+    fn id(&self) -> u64;
+
     /// Reset the interpreter to a prior context state, while also clearing the data stack.  After
     /// reset a new context is created.
     fn reset(&mut self) -> error::Result<()>;
@@ -8,9 +8,13 @@ use crate::{
     },
     location_here,
     runtime::{
-        built_ins::ffi_words::FfiInterface,
+        built_ins::{
+            binary_layout_words::BinaryLayoutList, ffi_words::FfiInterface,
+            io_words::close_auto_delete_files, random_words::RandomGenerators,
+            regex_words::RegexCache,
+        },
         data_structures::{
-            byte_buffer::ByteBufferPtr,
+            byte_buffer::BufferPtr,
             contextual_data::ContextualData,
             contextual_list::ContextualList,
             data_object::{DataDefinitionList, DataObjectDefinitionPtr, DataObjectPtr},
@@ -21,9 +25,9 @@ use crate::{
         },
         error::{self, script_error, script_error_str},
         interpreter::{
-            CallItem, CallStack, CodeManagement, Ffi, Interpreter, InterpreterStack,
-            ThreadManagement, ValueStack, VariableList, WordHandler, WordHandlerInfo,
-            WordManagement,
+            BinaryLayouts, CallItem, CallStack, CodeManagement, Ffi, Interpreter, InterpreterStack,
+            RandomNumbers, RegexPatterns, ThreadManagement, ValueStack, VariableList, WordHandler,
+            WordHandlerInfo, WordManagement,
         },
     },
 };
@@ -31,6 +35,7 @@ use std::{
     fs::{canonicalize, metadata},
     path::{Path, PathBuf},
     rc::Rc,
+    sync::atomic::{AtomicU64, Ordering},
 };
 
 /// The search paths used to find sorth files.
@@ -41,6 +46,9 @@ pub type WordList = ContextualList<WordHandlerInfo>;
 
 /// The core interpreter implementation for the Strange Forth language.
 pub struct SorthInterpreter {
+    /// The number that identifies this interpreter within the process.
+    id: u64,
+
     /// The maximum depth of the data stack during execution.
     max_depth: usize,
 
@@ -71,6 +79,15 @@ pub struct SorthInterpreter {
     /// The FFI interface used by the interpreter.
     ffi: FfiInterface,
 
+    /// The cache of compiled regular expressions used by the regex words.
+    regex: RegexCache,
+
+    /// The binary layouts defined by the binary-layout words.
+    binary_layouts: BinaryLayoutList,
+
+    /// The random number generators used by the random words.
+    random: RandomGenerators,
+
     /// The stack of code construction contexts used to build up the code blocks for both words and
     /// script top level code.
     ///
@@ -148,6 +165,10 @@ impl Interpreter for SorthInterpreter {
         &self.data_definitions
     }
 
+    fn id(&self) -> u64 {
+        self.id
+    }
+
     fn reset(&mut self) -> error::Result<()> {
         // Clear the current context and the stack.  This should be enough to reset the interpreter
         // to a managed default state.
@@ -157,6 +178,18 @@ impl Interpreter for SorthInterpreter {
         // Make sure to reset the FFI subsystem state as well.
         self.ffi.reset();
 
+        // As well as any compiled regular expressions.
+        self.regex.reset();
+
+        // And the defined binary layouts.
+        self.binary_layouts.reset();
+
+        // The random generators are freed, and the default one is reseeded.
+        self.random.reset();
+
+        // Temporary files and directories that were meant to be deleted are cleaned up too.
+        close_auto_delete_files(self.id);
+
         // Make sure to make the new context in case we need to reset tot he prior state again.
         self.mark_context();
         Ok(())
@@ -280,14 +313,21 @@ impl InterpreterStack for SorthInterpreter {
         Ok(value.as_data_object(self)?.clone())
     }
 
-    fn pop_as_byte_buffer(&mut self) -> error::Result<ByteBufferPtr> {
+    fn pop_as_byte_buffer(&mut self) -> error::Result<BufferPtr> {
         let value = self.pop()?;
 
         if !value.is_byte_buffer() {
             script_error_str(self, "Expected a byte buffer.")?;
         }
 
-        Ok(value.as_byte_buffer(self)?.clone())
+        // Slices share their parent's storage, so make sure it hasn't shrunk out from under them.
+        let buffer = value.as_byte_buffer(self)?.clone();
+
+        if let Err(message) = buffer.borrow().check_bounds() {
+            script_error(self, message)?;
+        }
+
+        Ok(buffer)
     }
 
     fn pop_as_token(&mut self) -> error::Result<Token> {
@@ -897,9 +937,43 @@ impl Ffi for SorthInterpreter {
     }
 }
 
+impl RandomNumbers for SorthInterpreter {
+    fn random(&self) -> &RandomGenerators {
+        &self.random
+    }
+
+    fn random_mut(&mut self) -> &mut RandomGenerators {
+        &mut self.random
+    }
+}
+
+impl RegexPatterns for SorthInterpreter {
+    fn regex(&self) -> &RegexCache {
+        &self.regex
+    }
+
+    fn regex_mut(&mut self) -> &mut RegexCache {
+        &mut self.regex
+    }
+}
+
+impl BinaryLayouts for SorthInterpreter {
+    fn binary_layouts(&self) -> &BinaryLayoutList {
+        &self.binary_layouts
+    }
+
+    fn binary_layouts_mut(&mut self) -> &mut BinaryLayoutList {
+        &mut self.binary_layouts
+    }
+}
+
 impl SorthInterpreter {
     pub fn new() -> SorthInterpreter {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
         SorthInterpreter {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+
             max_depth: 0,
 
             search_paths: Vec::new(),
@@ -918,6 +992,10 @@ impl SorthInterpreter {
 
             ffi: FfiInterface::new(),
 
+            regex: RegexCache::new(),
+            binary_layouts: BinaryLayoutList::new(),
+            random: RandomGenerators::new(),
+
             constructors: CodeConstructorList::new(),
         }
     }
@@ -1589,6 +1589,12 @@
 ;
 
 
+: buffer.i24!! description: "Write a 24-bit signed integer to the buffer variable."
+               signature: "value buffer_variable -- "
+    @ 3 buffer.int!
+;
+
+
 : buffer.i32!! description: "Write a 32-bit signed integer to the buffer variable."
                signature: "value buffer_variable -- "
     @ 4 buffer.int!
@@ -1614,6 +1620,12 @@
 ;
 
 
+: buffer.i24@@ description: "Read a 24-bit signed integer from the buffer variable."
+               signature: "buffer_variable -- value"
+    @ 3 true buffer.int@
+;
+
+
 : buffer.i32@@ description: "Read a 32-bit signed integer from the buffer variable."
                signature: "buffer_variable -- value"
     @ 4 true buffer.int@
@@ -1639,6 +1651,12 @@
 ;
 
 
+: buffer.u24@@ description: "Read a 24-bit unsigned integer from the buffer variable."
+               signature: "buffer_variable -- value"
+    @ 3 false buffer.int@
+;
+
+
 : buffer.u32@@ description: "Read a 32-bit unsigned integer from the buffer variable."
                signature: "buffer_variable -- value"
     @ 4 false buffer.int@
@@ -1910,6 +1928,12 @@
 
 
 
+( Include the binary layout declarations. )
+[include] std/binary_layout.f
+
+
+
+
 ( Include the ffi system. )
 [include] std/ffi.f
 
@@ -0,0 +1,32 @@
+( The binary-layout declaration word.  The real work is done by the native binary-layout.define )
+( word, which is looked up by name when a layout is declared.  That way the standard library )
+( still loads for hosts that don't register the binary layout words. )
+
+
+
+( Binary layouts describe how a structure's fields are packed into a byte buffer.  For example: )
+(     binary-layout Header  magic u32  version u16  pad[2]  name string[16]  endian big ; )
+( creates the words Header.pack and Header.unpack, as well as the structure Header if it doesn't )
+( already exist. )
+: binary-layout immediate description: "Define a binary layout for packing structures into buffers."
+                          signature: "binary-layout name [ field type | pad[n] | endian big|little ] ... ;"
+    word variable! layout_name
+    0 [].new variable! items
+    variable next_item
+
+    begin
+        word next_item !
+        next_item @ ";" <>
+    while
+        next_item @ "(" =
+        if
+            "(" execute
+        else
+            next_item @ items [].push_back!!
+        then
+    repeat
+
+    layout_name @ op.push_constant_value
+    items @ op.push_constant_value
+    "binary-layout.define" op.execute
+;
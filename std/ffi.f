@@ -20,10 +20,11 @@
 
 
 : ffi.fn immediate description: "Register a function from a shared library"
-                   signature: "ffi.fn library-name fn-name [as alias] input-types -> return-type"
+                   signature: "ffi.fn library-name fn-name [as alias] [abi name] input-types [...] -> return-type"
     word variable! lib-name  ( Name of the library to reference. )
     word variable! fn-name   ( Name of the function we're binding. )
     "" variable! fn-alias    ( Optional alias to use for the function. )
+    "" variable! fn-abi      ( Optional calling convention, empty for the platform default. )
 
     0 [].new variable! fn-params  ( List of parameter types. )
 
@@ -36,6 +37,13 @@
         word  next !
     then
 
+    ( Check if a calling convention was given. )
+    next @  "abi"  =
+    if
+        word  fn-abi !
+        word  next !
+    then
+
     ( Load the parameter types until we get to the return keyword. )
     begin
         next @  "->"  <>
@@ -49,6 +57,7 @@
     lib-name @   op.push_constant_value
     fn-name @    op.push_constant_value
     fn-alias @   op.push_constant_value
+    fn-abi @     op.push_constant_value
     fn-params @  op.push_constant_value
     word         op.push_constant_value
     ` ffi.fn     op.execute
@@ -56,10 +65,47 @@
 
 
 
+: ffi.callback immediate description: "Wrap a word as a native function pointer, freed with ffi.callback.free."
+                         signature: "ffi.callback word-name [abi name] param-types -> return-type"
+    word variable! cb-word-name       ( Name of the word the callback runs. )
+    "" variable! cb-abi               ( Optional calling convention, empty for the platform default. )
+    0 [].new variable! cb-params      ( List of parameter types. )
+
+    word variable! cb-next
+
+    ( Check if a calling convention was given. )
+    cb-next @  "abi"  =
+    if
+        word  cb-abi !
+        word  cb-next !
+    then
+
+    ( Load the parameter types until we get to the return keyword. )
+    begin
+        cb-next @  "->"  <>
+    while
+        cb-next @  cb-params [].push_back!!
+        word cb-next !
+    repeat
+
+    ( Call the native word to create the callback when this code runs. )
+    cb-word-name @  op.push_constant_value
+    cb-abi @        op.push_constant_value
+    cb-params @     op.push_constant_value
+    word            op.push_constant_value
+    ` ffi.callback  op.execute
+;
+
+
+
 : as  description: "Part of the ffi loading syntax."
     "as" sentinel_word
 ;
 
+: abi description: "Part of the ffi function syntax, names the calling convention to use."
+    "abi" sentinel_word
+;
+
 
 
 : ffi.void description: "Corresponds to a C void type."
@@ -106,19 +152,40 @@
     "ffi.string" sentinel_word
 ;
 
+: ffi.buffer description: "Passes a byte buffer, or slice, to C as a pointer to it's data."
+    "ffi.buffer" sentinel_word
+;
+
+: ffi.ptr description: "Corresponds to a C pointer type, passed as an address."
+    "ffi.ptr" sentinel_word
+;
+
 : ffi.void-ptr description: "Corresponds to a C pointer type."
     "ffi.void-ptr" sentinel_word
 ;
 
+: ffi.fn-ptr description: "Corresponds to a C function pointer, such as one made by ffi.callback."
+    "ffi.fn-ptr" sentinel_word
+;
+
+: ... description: "Marks the rest of a function's arguments as variadic, passed as an array."
+    "..." sentinel_word
+;
+
+: ffi.len description: "Marks a parameter that's filled in with the length of the array next to it."
+    "ffi.len" sentinel_word
+;
+
 
 
 
 : ffi.# immediate description: "Create a structure compatible with the ffi interface."
-                  signature: "ffi# type field -> default ... ;"
+                  signature: "ffi.# name [hidden] [packing n] type field [-> default] , ... ;"
     word variable! struct_name
     false variable! is_hidden
 
-    4 variable! alignment
+    ( Zero uses the natural C alignment of each field. )
+    0 variable! alignment
 
     0 [].new variable! types
     0 [].new variable! field_names
@@ -148,6 +215,11 @@
                     continue
                 endof
 
+            "packing" of
+                    word alignment !
+                    continue
+                endof
+
             "{" of
                     "(" execute
                     continue
@@ -206,6 +278,15 @@
 
 
 
+: ffi.[] immediate description: "Register an array type for an existing ffi type."
+                   signature: "ffi.[] type[] | type[size]"
+    word op.push_constant_value
+    ` ffi.[] op.execute
+;
+
+
+
+
 (
 ffi.# point packing 1
     ffi.i32 x -> 0 ,
@@ -55,6 +55,150 @@ cr
 
 cr
 
+"--- Testing regular expressions. ---" .cr
+
+"tests/10_test_regex.f" include
+
+cr
+
+"--- Testing json. ---" .cr
+
+"tests/11_test_json.f" include
+
+cr
+
+"--- Testing csv. ---" .cr
+
+"tests/12_test_csv.f" include
+
+cr
+
+"--- Testing msgpack. ---" .cr
+
+"tests/13_test_msgpack.f" include
+
+cr
+
+"--- Testing binary layouts. ---" .cr
+
+"tests/14_test_binary_layout.f" include
+
+cr
+
+"--- Testing buffer byte orders and bits. ---" .cr
+
+"tests/15_test_buffer_bits.f" include
+
+cr
+
+"--- Testing buffer slices. ---" .cr
+
+"tests/16_test_buffer_slices.f" include
+
+cr
+
+"--- Testing encoding and hashing. ---" .cr
+
+"tests/17_test_encoding.f" include
+
+cr
+
+[is-linux?]
+[if]
+    "--- Testing ffi structures. ---" .cr
+
+    "tests/18_test_ffi_structs.f" include
+
+    cr
+
+    "--- Testing ffi arrays. ---" .cr
+
+    "tests/19_test_ffi_arrays.f" include
+
+    cr
+
+    "--- Testing ffi callbacks. ---" .cr
+
+    "tests/20_test_ffi_callbacks.f" include
+
+    cr
+
+    "--- Testing ffi pointers. ---" .cr
+
+    "tests/21_test_ffi_pointers.f" include
+
+    cr
+
+    "--- Testing ffi variadic functions. ---" .cr
+
+    "tests/22_test_ffi_variadic.f" include
+
+    cr
+
+    "--- Testing ffi header bindings. ---" .cr
+
+    "tests/23_test_ffi_header.f" include
+
+    cr
+
+    "--- Testing processes. ---" .cr
+
+    "tests/28_test_process.f" include
+
+    cr
+
+    "--- Testing io.poll and non-blocking fds. ---" .cr
+
+    "tests/29_test_poll.f" include
+
+    cr
+[then]
+
+
+
+
+"--- Testing sockets. ---" .cr
+
+"tests/24_test_sockets.f" include
+
+cr
+
+"--- Testing file buffers. ---" .cr
+
+"tests/25_test_file_buffers.f" include
+
+cr
+
+"--- Testing temporary files. ---" .cr
+
+"tests/26_test_temp_files.f" include
+
+cr
+
+"--- Testing the file system. ---" .cr
+
+"tests/27_test_filesystem.f" include
+
+cr
+
+"--- Testing the time words. ---" .cr
+
+"tests/30_test_time.f" include
+
+cr
+
+"--- Testing the random words. ---" .cr
+
+"tests/31_test_random.f" include
+
+cr
+
+"--- Testing the math words. ---" .cr
+
+"tests/32_test_math.f" include
+
+cr
+
 ( "--- Testing the ffi. ---" .cr )
 
 ( "tests/09_test_ffi.f" include )
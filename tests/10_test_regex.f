@@ -0,0 +1,65 @@
+
+( Compile a pattern once and use the compiled regex. )
+"(\\w+)@(\\w+)\\.com" regex.compile constant email
+
+
+"Mail bob@example.com now." email regex.match? "Matched: {}" string.format .cr
+"Nothing to see here." email regex.match? "Matched: {}" string.format .cr
+
+
+( Find the first match and look at it's capture groups. )
+"Mail bob@example.com now." email regex.find variable! found
+
+found { "text" }@@ "Found: {}" string.format .cr
+found { "start" }@@ found { "end" }@@ "Span: {} {}" string.format .cr
+found { "groups" }@@ [ 1 ]@ { "text" }@ "User: {}" string.format .cr
+found { "groups" }@@ [ 2 ]@ { "text" }@ "Host: {}" string.format .cr
+
+"Nothing to see here." email regex.find "No match: {}" string.format .cr
+
+
+( Raw pattern strings work too, they're compiled and cached by the interpreter. )
+"a1 b22 c333" "\\d+" regex.find-all variable! numbers
+
+numbers [].size@@ "Count: {}" string.format .cr
+numbers [ 2 ]@@ { "text" }@ "Last: {}" string.format .cr
+
+
+( Named groups. )
+"2024-06-30" "(?P<year>\\d{4})-(?P<month>\\d{2})" regex.find { "named" }@
+{ "month" }@ { "text" }@ "Month: {}" string.format .cr
+
+
+( Replacement with back references and splitting. )
+"$2 $1" "hello world" "(\\w+) (\\w+)" regex.replace "Swapped: {}" string.format .cr
+"one, two,three" ",\\s*" regex.split [ 1 ]@ "Second: {}" string.format .cr
+
+
+( Invalid patterns are reported as script errors. )
+try
+    "(unclosed" regex.compile
+catch
+    drop "Bad pattern caught." .cr
+endcatch
+
+try
+    "text" 12345 regex.match?
+catch
+    drop "Non-regex caught." .cr
+endcatch
+
+
+( Many different pattern strings can be used without the cache growing without bound. )
+true variable! regex_all_matched
+0 variable! regex_index
+"" variable! regex_text
+
+begin
+    regex_index @ 300 <
+while
+    regex_index @ "{}" string.format regex_text !
+    regex_text @ "^" regex_text @ + "$" + regex.match? regex_all_matched @ && regex_all_matched !
+    regex_index @ 1 + regex_index !
+repeat
+
+regex_all_matched @ "Many patterns matched: {}" string.format .cr
@@ -0,0 +1,80 @@
+( Any JSON value can be parsed at the top level, not just objects. )
+"[ 1, 2.5, -3e2, true, null, \"x\" ]" json.parse variable! items
+
+items [].size@@ "Items: {}" string.format .cr
+items [ 2 ]@@ "Exponent: {}" string.format .cr
+"42" json.parse "Scalar: {}" string.format .cr
+
+
+( Unicode escapes, including surrogate pairs. )
+"\"caf\\u00e9 \\ud83d\\ude00\"" json.parse string.size@ "Unicode size: {}" string.format .cr
+
+
+( Keys are written in a stable sorted order, and strings are properly escaped. )
+{}.new variable! json_table
+
+"line\none \"quoted\"" json_table { "b" }!!
+1 json_table { "a" }!!
+items @ json_table { "c" }!!
+
+json_table @ json.stringify .cr
+json_table @ {}.to_json json.parse json.stringify .cr
+
+
+( Pretty printing. )
+{}.new variable! small
+10 small { "x" }!!
+small @ 2 json.stringify-pretty .cr
+
+
+( Structures are converted using their field names. )
+# point x y ;
+
+point.new variable! pt
+3 pt point.x!!
+4 pt point.y!!
+
+pt @ #.to_json .cr
+
+
+( The old wrapper still only accepts objects, and errors report their location. )
+"{ \"k\": [ 1, 2 ] }" {}.from_json { "k" }@ [].size@ "Wrapped: {}" string.format .cr
+
+try
+    "[ 1 ]" {}.from_json
+catch
+    drop "Not an object caught." .cr
+endcatch
+
+try
+    "{\n  \"a\": tru }" json.parse
+catch
+    .cr
+endcatch
+
+
+( Nesting is limited, so deep input and cyclic values are errors rather than crashes. )
+"[" variable! json_deep
+0 variable! json_doublings
+
+begin
+    json_doublings @ 17 <
+while
+    json_deep @ json_deep @ + json_deep !
+    json_doublings ++!
+repeat
+
+try
+    json_deep @ json.parse
+catch
+    drop "Deep nesting caught." .cr
+endcatch
+
+[ 1 ] variable! json_cycle
+json_cycle @ json_cycle [].push_back!!
+
+try
+    json_cycle @ json.stringify
+catch
+    drop "Cyclic value caught." .cr
+endcatch
@@ -0,0 +1,59 @@
+( Quoted fields can hold delimiters, doubled quotes, and line breaks. )
+"name,note\r\nbob,\"likes \"\"quotes\"\", commas\"\r\nsue,\"two\nlines\"\r\n" variable! csv_text
+
+csv_text @ none csv.parse variable! rows
+
+rows [].size@@ "Rows: {}" string.format .cr
+rows [ 1 ]@@ [ 1 ]@ "Note: {}" string.format .cr
+rows [ 2 ]@@ [ 1 ]@ string.size@ "Multi-line size: {}" string.format .cr
+
+
+( With a header row each row becomes a hash table. )
+{}.new variable! header_options
+true header_options { "header" }!!
+
+csv_text @ header_options @ csv.parse variable! people
+
+people [ 1 ]@@ { "name" }@ "Name: {}" string.format .cr
+
+
+( Other delimiter and quote characters. )
+{}.new variable! semi_options
+";" semi_options { "delimiter" }!!
+"'" semi_options { "quote" }!!
+
+"a;'b;c';d" semi_options @ csv.parse [ 0 ]@ [ 1 ]@ "Semi: {}" string.format .cr
+
+
+( Formatting quotes only the fields that need it. )
+rows @ none csv.format csv_text @ = "Round trip: {}" string.format .cr
+people @ header_options @ csv.format csv_text @ = "Header round trip: {}" string.format .cr
+
+( Hash table rows with different keys share the columns of all of them. )
+[ { "a" -> 1 } , { "b" -> 2 , "a" -> 3 } ] header_options @ csv.format
+"a,b\r\n1,\r\n3,2\r\n" = "Mixed keys: {}" string.format .cr
+
+
+( Write rows to a file and then stream them back in. )
+"./test.csv" file.w/o file.create variable! fd
+
+[ "x" , "y, z" ] fd @ none csv.write-row
+[ "1" , "2" ] fd @ none csv.write-row
+fd @ file.close
+
+"./test.csv" file.r/o file.open fd !
+
+fd @ none csv.read-row [ 1 ]@ "Streamed: {}" string.format .cr
+fd @ none csv.read-row [ 0 ]@ "Streamed: {}" string.format .cr
+fd @ none csv.read-row "End: {}" string.format .cr
+fd @ file.close
+
+"./test.csv" file.delete
+
+
+( Unterminated quotes are reported as errors. )
+try
+    "a,\"b" none csv.parse
+catch
+    drop "Unterminated caught." .cr
+endcatch
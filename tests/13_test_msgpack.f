@@ -0,0 +1,55 @@
+( Build up a value tree that JSON can't represent faithfully. )
+4 buffer.new variable! msgpack_bytes
+42 msgpack_bytes buffer.i32!!
+0 msgpack_bytes buffer.position!!
+
+{}.new variable! original
+1 original { "int" }!!
+1.0 original { "float" }!!
+"text" original { "string" }!!
+none original { "nothing" }!!
+msgpack_bytes @ original { "bytes" }!!
+[ 1 , -200 , 70000 , true ] original { "list" }!!
+
+
+( Round trip the whole tree through a byte buffer. )
+original @ value.to-msgpack variable! packed
+packed @ value.from-msgpack variable! copy
+
+copy @ original @ = "Equal: {}" string.format .cr
+copy { "int" }@@ json.stringify "Int stays int: {}" string.format .cr
+copy { "float" }@@ json.stringify "Float stays float: {}" string.format .cr
+copy { "bytes" }@@ value.is-buffer? "Buffer stays buffer: {}" string.format .cr
+copy { "list" }@@ [ 2 ]@ "Large int: {}" string.format .cr
+
+
+( Structures are rebuilt by name. )
+# msg.point x y ;
+
+msg.point.new variable! pt
+3 pt msg.point.x!!
+4 pt msg.point.y!!
+
+pt @ value.to-msgpack value.from-msgpack variable! pt_copy
+
+pt_copy @ value.is-structure? "Structure: {}" string.format .cr
+pt_copy msg.point.y@@ "Field y: {}" string.format .cr
+
+
+( Several values can be read back to back from one buffer. )
+"first" value.to-msgpack variable! first_value
+first_value @ value.from-msgpack .cr
+
+
+( Malformed data is reported as an error. )
+3 buffer.new variable! bad
+0xdc bad buffer.i8!!
+0x00 bad buffer.i8!!
+0x05 bad buffer.i8!!
+0 bad buffer.position!!
+
+try
+    bad @ value.from-msgpack
+catch
+    drop "Malformed caught." .cr
+endcatch
@@ -0,0 +1,97 @@
+( A layout creates the structure if it doesn't already exist. )
+binary-layout Header
+    magic u32
+    version u16
+    pad[2]
+    name string[8]
+    endian big
+;
+
+Header.new variable! layout_header
+0xCAFEBABE layout_header Header.magic!!
+3 layout_header Header.version!!
+"hdr" layout_header Header.name!!
+
+layout_header @ Header.pack variable! layout_packed
+
+layout_packed @ buffer.size@ "Packed size: {}" string.format .cr
+layout_packed @ 1 false buffer.int@ "First byte: {}" string.format .cr
+
+0 layout_packed buffer.position!!
+layout_packed @ Header.unpack variable! layout_copy
+
+layout_copy Header.magic@@ 0xCAFEBABE = "Magic: {}" string.format .cr
+layout_copy Header.name@@ "Name: {}" string.format .cr
+
+
+( Layouts can be tied to existing structures, nest other layouts, and hold arrays and )
+( length-prefixed strings. )
+# Packet header points label note ;
+
+binary-layout Packet
+    header Header
+    points i16[3]
+    label string/u8
+;
+
+Packet.new variable! layout_packet
+layout_header @ layout_packet Packet.header!!
+[ 1 , -2 , 3 ] layout_packet Packet.points!!
+"a longer label" layout_packet Packet.label!!
+
+layout_packet @ Packet.pack variable! layout_packed_packet
+layout_packed_packet @ buffer.size@ "Packet size: {}" string.format .cr
+
+0 layout_packed_packet buffer.position!!
+layout_packed_packet @ Packet.unpack variable! layout_packet_copy
+
+layout_packet_copy Packet.points@@ [ 1 ]@ "Point: {}" string.format .cr
+layout_packet_copy Packet.label@@ "Label: {}" string.format .cr
+layout_packet_copy Packet.header@@ Header.version@ "Nested version: {}" string.format .cr
+layout_packed_packet @ buffer.position@ "Position after: {}" string.format .cr
+
+
+( Errors are reported for bad data. )
+Header.new variable! layout_range
+
+255 layout_range Header.magic!!
+-32768 layout_range Header.version!!
+
+try
+    layout_range @ Header.pack
+catch
+    drop "Signed into unsigned caught." .cr
+endcatch
+
+65535 layout_range Header.version!!
+layout_range @ Header.pack buffer.size@ "Largest u16 packed: {}" string.format .cr
+
+65536 layout_range Header.version!!
+
+try
+    layout_range @ Header.pack
+catch
+    drop "Out of range int caught." .cr
+endcatch
+
+-1 layout_range Header.magic!!
+0 layout_range Header.version!!
+
+try
+    layout_range @ Header.pack
+catch
+    drop "Negative unsigned caught." .cr
+endcatch
+
+try
+    "this name is too long" layout_header Header.name!!
+    layout_header @ Header.pack
+catch
+    drop "Long string caught." .cr
+endcatch
+
+try
+    4 buffer.new Header.unpack
+catch
+    drop "Short buffer caught." .cr
+endcatch
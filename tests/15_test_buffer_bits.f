@@ -0,0 +1,83 @@
+( Integers can be written in an explicit byte order, in any size from 1 to 8 bytes. )
+8 buffer.new variable! bits_buffer
+
+0x010203 bits_buffer @ 3 buffer.int-be!
+0 bits_buffer buffer.position!!
+bits_buffer @ 1 false buffer.int@ "First byte: {}" string.format .cr
+
+0 bits_buffer buffer.position!!
+bits_buffer @ 3 false buffer.int-be@ hex "Big endian: {}" string.format .cr
+
+0 bits_buffer buffer.position!!
+bits_buffer @ 3 false buffer.int-le@ hex "Little endian: {}" string.format .cr
+
+0 bits_buffer buffer.position!!
+-2 bits_buffer buffer.i24!!
+0 bits_buffer buffer.position!!
+bits_buffer buffer.i24@@ "Signed 24-bit: {}" string.format .cr
+
+
+( The buffer's default byte order applies to the plain words. )
+true bits_buffer @ buffer.big-endian!
+bits_buffer @ buffer.big-endian@ "Default big endian: {}" string.format .cr
+
+0 bits_buffer buffer.position!!
+0x0102 bits_buffer buffer.i16!!
+0 bits_buffer buffer.position!!
+bits_buffer buffer.u8@@ "Default first byte: {}" string.format .cr
+
+0 bits_buffer buffer.position!!
+1.5 bits_buffer @ 8 buffer.float-le!
+0 bits_buffer buffer.position!!
+bits_buffer @ 8 buffer.float-le@ "Float: {}" string.format .cr
+
+
+( LEB128 and zig-zag variable length integers. )
+0 bits_buffer buffer.position!!
+300 bits_buffer @ buffer.uvarint!
+-3 bits_buffer @ buffer.svarint!
+bits_buffer buffer.position@@ "Varint bytes: {}" string.format .cr
+
+0 bits_buffer buffer.position!!
+bits_buffer @ buffer.uvarint@ "Unsigned varint: {}" string.format .cr
+bits_buffer @ buffer.svarint@ "Signed varint: {}" string.format .cr
+
+
+( Packed bit fields are read and written high bit first. )
+0 bits_buffer buffer.position!!
+5 bits_buffer @ 3 buffer.bits!
+1 bits_buffer @ 1 buffer.bits!
+0x1ff bits_buffer @ 9 buffer.bits!
+bits_buffer @ buffer.bit-offset@ "Bit offset: {}" string.format .cr
+
+0 bits_buffer buffer.position!!
+bits_buffer buffer.u8@@ "Packed byte: {}" string.format .cr
+
+0 bits_buffer buffer.position!!
+bits_buffer @ 3 buffer.bits@ "Bits: {}" string.format .cr
+bits_buffer @ 1 buffer.bits@ "Bit: {}" string.format .cr
+bits_buffer @ 9 buffer.bits@ "More bits: {}" string.format .cr
+bits_buffer @ buffer.bits-align
+bits_buffer buffer.position@@ "Aligned position: {}" string.format .cr
+
+
+( Bad sizes and reads past the end are script errors. )
+try
+    1 bits_buffer @ 9 buffer.int!
+catch
+    drop "Bad size caught." .cr
+endcatch
+
+try
+    6 bits_buffer buffer.position!!
+    bits_buffer @ 4 buffer.float-be@
+catch
+    drop "Past the end caught." .cr
+endcatch
+
+try
+    0 bits_buffer buffer.position!!
+    bits_buffer @ 65 buffer.bits@
+catch
+    drop "Too many bits caught." .cr
+endcatch
@@ -0,0 +1,75 @@
+( Slices share storage with their parent, but have their own cursor. )
+16 buffer.new variable! slice_parent
+4 8 slice_parent @ buffer.slice variable! slice_view
+
+slice_view @ buffer.size@ "Slice size: {}" string.format .cr
+
+0x1234 slice_view buffer.i16!!
+slice_view buffer.position@@ "Slice position: {}" string.format .cr
+slice_parent buffer.position@@ "Parent position: {}" string.format .cr
+
+4 slice_parent buffer.position!!
+slice_parent buffer.u16@@ hex "Parent sees: {}" string.format .cr
+
+
+( Slices of slices are offset from the original buffer. )
+2 2 slice_view @ buffer.slice variable! slice_inner
+0x7f slice_inner @ 1 buffer.int!
+
+6 slice_parent buffer.position!!
+slice_parent buffer.u8@@ "Inner write: {}" string.format .cr
+
+
+( Copies are independent of the original. )
+4 4 slice_parent @ buffer.copy variable! slice_copy
+0 0 4 slice_copy @ buffer.fill
+4 slice_parent buffer.position!!
+slice_parent buffer.u16@@ hex "Still there: {}" string.format .cr
+slice_copy @ buffer.size@ "Copy size: {}" string.format .cr
+
+
+( A slice has it's own byte order, starting out as the parent's. )
+true slice_view @ buffer.big-endian!
+slice_view @ buffer.big-endian@ "Slice big endian: {}" string.format .cr
+slice_parent @ buffer.big-endian@ "Parent big endian: {}" string.format .cr
+
+
+( Filling, comparing, and finding byte patterns. )
+0xaa 10 3 slice_parent @ buffer.fill
+0 4 slice_parent @ buffer.copy variable! slice_a
+0 4 slice_parent @ buffer.copy variable! slice_b
+
+slice_a @ slice_b @ buffer.compare "Compare equal: {}" string.format .cr
+1 0 1 slice_b @ buffer.fill
+slice_a @ slice_b @ buffer.compare "Compare less: {}" string.format .cr
+
+3 buffer.new variable! slice_pattern
+0xaa 0 3 slice_pattern @ buffer.fill
+
+slice_pattern @ 0 slice_parent @ buffer.find "Found at: {}" string.format .cr
+slice_pattern @ 11 slice_parent @ buffer.find "Not found: {}" string.format .cr
+14 slice_parent buffer.position!!
+"AB" slice_parent @ 2 buffer.string!
+"AB" 0 slice_parent @ buffer.find "String pattern: {}" string.format .cr
+
+
+( Slices are checked against their parent on each access. )
+try
+    0 20 slice_parent @ buffer.slice
+catch
+    drop "Bad range caught." .cr
+endcatch
+
+6 slice_parent @ buffer.size!
+
+try
+    slice_view buffer.u8@@
+catch
+    drop "Stale slice caught." .cr
+endcatch
+
+( A stale slice can still be printed and compared, it just has no contents. )
+slice_view @ .cr
+"Stale slice printed." .cr
+slice_view @ slice_view @ = "Stale slice equal: {}" string.format .cr
+slice_view @ 0 buffer.new = "Stale slice is empty: {}" string.format .cr
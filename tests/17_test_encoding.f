@@ -0,0 +1,39 @@
+( Hex and base64 round trips. )
+"Hello" buffer.to-hex "Hex: {}" string.format .cr
+"48656C6C6f" hex.decode variable! enc_bytes
+enc_bytes @ base64.encode "Base64: {}" string.format .cr
+"SGVsbG8=" base64.decode enc_bytes @ buffer.compare "Decoded same: {}" string.format .cr
+
+"fbff" hex.decode base64.url-encode "URL safe: {}" string.format .cr
+"-_8" base64.url-decode buffer.to-hex "URL decoded: {}" string.format .cr
+
+enc_bytes @ buffer.hexdump
+
+
+( Checksums and digests. )
+"123456789" crc32 hex "CRC32: {}" string.format .cr
+"abc" sha256.hex "SHA-256: {}" string.format .cr
+"abc" sha256 buffer.size@ "Digest size: {}" string.format .cr
+"abc" sha1.hex "SHA-1: {}" string.format .cr
+"abc" md5.hex "MD5: {}" string.format .cr
+"what do ya want for nothing?" "Jefe" hmac-sha256.hex "HMAC: {}" string.format .cr
+
+
+( Bad input is reported as an error. )
+try
+    "abc" hex.decode
+catch
+    drop "Odd hex caught." .cr
+endcatch
+
+try
+    "+f0a" hex.decode
+catch
+    drop "Signed hex caught." .cr
+endcatch
+
+try
+    "!!!!" base64.decode
+catch
+    drop "Bad base64 caught." .cr
+endcatch
@@ -0,0 +1,117 @@
+( These tests bind to the C library, so they only run on Linux. )
+ffi.load libc.so.6 as libc
+
+
+( Structures returned by value. )
+ffi.# div_t
+    ffi.i32 quot
+    ffi.i32 rem
+;
+
+ffi.fn libc div as ffi_struct_div ffi.i32 ffi.i32 -> div_t
+
+17 5 ffi_struct_div variable! ffi_struct_result
+ffi_struct_result div_t.quot@@ ffi_struct_result div_t.rem@@ "Quotient: {} remainder: {}" string.format .cr
+
+
+( Structures passed by value. )
+ffi.# in_addr
+    ffi.u32 s_addr -> 0
+;
+
+ffi.fn libc inet_ntoa as ffi_struct_ntoa in_addr -> ffi.string
+
+in_addr.new variable! ffi_struct_addr
+0x0401A8C0 ffi_struct_addr in_addr.s_addr!!
+ffi_struct_addr @ ffi_struct_ntoa "Address: {}" string.format .cr
+
+
+( Structures passed by pointer are copied back after the call. )
+ffi.# timespec
+    ffi.i64 tv_sec -> 0 ,
+    ffi.i64 tv_nsec -> 0
+;
+
+ffi.fn libc clock_gettime as ffi_struct_clock_gettime ffi.i32 timespec-ptr -> ffi.i32
+
+timespec.new variable! ffi_struct_time
+0 ffi_struct_time @ ffi_struct_clock_gettime "Clock result: {}" string.format .cr
+ffi_struct_time timespec.tv_sec@@ 0 > "Clock filled in: {}" string.format .cr
+
+
+( Nested structures with padding, and strings. )
+ffi.# inner
+    ffi.u8 flag -> 0 ,
+    ffi.string label -> "" ,
+    ffi.i16 small -> 0
+;
+
+ffi.# outer
+    ffi.i8 tag -> 0 ,
+    inner nested -> inner.new ,
+    ffi.f64 ratio -> 0.0
+;
+
+ffi.fn libc memcpy as ffi_struct_memcpy outer-ptr outer-ptr ffi.u64 -> ffi.void
+
+outer.new variable! ffi_struct_src
+outer.new variable! ffi_struct_dst
+
+7 ffi_struct_src outer.tag!!
+1 ffi_struct_src outer.nested@@ inner.flag!
+"hello" ffi_struct_src outer.nested@@ inner.label!
+-3 ffi_struct_src outer.nested@@ inner.small!
+2.5 ffi_struct_src outer.ratio!!
+
+ffi_struct_dst @ ffi_struct_src @ 40 ffi_struct_memcpy
+
+ffi_struct_dst outer.tag@@ "Copied tag: {}" string.format .cr
+ffi_struct_dst outer.nested@@ inner.label@ "Copied label: {}" string.format .cr
+ffi_struct_dst outer.nested@@ inner.small@ "Copied small: {}" string.format .cr
+ffi_struct_dst outer.ratio@@ "Copied ratio: {}" string.format .cr
+
+
+( Packing changes the layout. )
+ffi.# packed packing 1
+    ffi.u8 a -> 1 ,
+    ffi.u32 b -> 0x02030405
+;
+
+4 buffer.new variable! ffi_struct_bytes
+ffi.fn libc memcpy as ffi_struct_peek ffi.buffer packed-ptr ffi.u64 -> ffi.void
+ffi_struct_bytes @ packed.new 4 ffi_struct_peek
+ffi_struct_bytes @ buffer.to-hex "Packed bytes: {}" string.format .cr
+
+( libffi only knows the natural layout, so packed structures can't be passed by value. )
+try
+    ffi.fn libc memcpy as ffi_struct_packed_value packed packed ffi.u64 -> ffi.void
+catch
+    drop "Packed by value caught." .cr
+endcatch
+
+ffi.# holds_packed
+    ffi.u8 flag -> 0 ,
+    packed inner -> packed.new
+;
+
+try
+    ffi.fn libc memcpy as ffi_struct_holds_packed ffi.ptr ffi.ptr ffi.u64 -> holds_packed
+catch
+    drop "Nested packed by value caught." .cr
+endcatch
+
+ffi.# tight packing 1
+    ffi.u8 first -> 1 ,
+    ffi.u8 second -> 2
+;
+
+ffi.fn libc memcpy as ffi_struct_tight_value ffi.ptr ffi.ptr ffi.u64 -> tight
+"Natural packed by value allowed." .cr
+
+
+( Structures of the wrong type are errors. )
+try
+    div_t.new ffi_struct_ntoa
+catch
+    drop "Wrong structure caught." .cr
+endcatch
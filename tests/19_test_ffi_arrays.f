@@ -0,0 +1,111 @@
+( These tests bind to the C library, so they only run on Linux. )
+ffi.load libc.so.6 as libc_arrays
+
+
+( Arrays are passed as pointers to contiguous memory and copied back after the call. )
+ffi.[] ffi.i32[]
+
+ffi.fn libc_arrays memcpy as ffi_array_copy ffi.i32[] ffi.i32[] ffi.u64 -> ffi.u64
+
+[ 0 , 0 , 0 ] variable! ffi_array_dst
+ffi_array_dst @ [ 10 , -20 , 30 ] 12 ffi_array_copy drop
+ffi_array_dst @ "Copied ints: {}" string.format .cr
+
+
+( Fixed size arrays can be returned by pointer, and short arrays are zero filled. )
+ffi.[] ffi.f64[3]
+
+ffi.fn libc_arrays memcpy as ffi_array_copy_f64 ffi.f64[3]-ptr ffi.f64[3]-ptr ffi.u64 -> ffi.f64[3]-ptr
+
+[ 9.0 ] [ 1.5 , 2.5 ] 24 ffi_array_copy_f64 "Returned floats: {}" string.format .cr
+
+
+( Out parameters. )
+ffi.[] ffi.i32[2]
+
+ffi.fn libc_arrays pipe as ffi_array_pipe ffi.i32[2]-ptr -> ffi.i32
+ffi.fn libc_arrays close as ffi_array_close ffi.i32 -> ffi.i32
+
+0 [].new variable! ffi_array_fds
+ffi_array_fds @ ffi_array_pipe "Pipe result: {}" string.format .cr
+ffi_array_fds [].size@@ "Descriptors: {}" string.format .cr
+
+
+( Pointer plus length conventions fill in the length automatically. )
+ffi.fn libc_arrays write as ffi_array_write ffi.i32 ffi.string ffi.len ffi.u64 -> ffi.i64
+ffi.fn libc_arrays read as ffi_array_read ffi.i32 ffi.buffer ffi.len ffi.u64 -> ffi.i64
+
+ffi_array_fds [ 1 ]@@ "hello pipe" ffi_array_write "Wrote: {}" string.format .cr
+
+32 buffer.new variable! ffi_array_bytes
+ffi_array_fds [ 0 ]@@ ffi_array_bytes @ ffi_array_read "Read: {}" string.format .cr
+ffi_array_bytes @ 10 buffer.string@ "Read text: {}" string.format .cr
+
+ffi_array_fds [ 0 ]@@ ffi_array_close drop
+ffi_array_fds [ 1 ]@@ ffi_array_close drop
+
+
+( The length can come before the array. )
+ffi.[] ffi.u32[]
+
+ffi.fn libc_arrays getgroups as ffi_array_getgroups ffi.len ffi.i32 ffi.u32[] -> ffi.i32
+
+256 [].new variable! ffi_array_groups
+ffi_array_groups @ ffi_array_getgroups 0 >= "Got groups: {}" string.format .cr
+
+
+( Arrays of strings are passed as arrays of pointers. )
+ffi.[] ffi.string[]
+
+ffi.fn libc_arrays memcpy as ffi_array_copy_strings ffi.string[] ffi.string[] ffi.u64 -> ffi.u64
+
+2 [].new variable! ffi_array_names
+ffi_array_names @ [ "alpha" , "beta" ] 16 ffi_array_copy_strings drop
+ffi_array_names @ "Copied strings: {}" string.format .cr
+
+
+( Arrays of structures, and fixed size arrays within structures. )
+ffi.# ffi_array_point
+    ffi.i16 x -> 0 ,
+    ffi.i16 y -> 0
+;
+
+ffi.[] ffi_array_point[]
+ffi.[] ffi.u8[3]
+
+ffi.# ffi_array_shape
+    ffi.u8[3] color -> [ 1 , 2 , 3 ] ,
+    ffi.i32 sides -> 4
+;
+
+ffi.fn libc_arrays memcpy as ffi_array_copy_points ffi_array_point[] ffi_array_point[] ffi.u64 -> ffi.u64
+ffi.fn libc_arrays memcpy as ffi_array_copy_shape ffi_array_shape-ptr ffi_array_shape-ptr ffi.u64 -> ffi.u64
+
+ffi_array_point.new variable! ffi_array_first
+[ ffi_array_first @ , ffi_array_point.new ] variable! ffi_array_points
+
+ffi_array_point.new variable! ffi_array_src
+-5 ffi_array_src ffi_array_point.x!!
+6 ffi_array_src ffi_array_point.y!!
+
+ffi_array_points @ [ ffi_array_src @ , ffi_array_src @ ] 8 ffi_array_copy_points drop
+ffi_array_first ffi_array_point.x@@ "Point updated in place: {}" string.format .cr
+
+ffi_array_shape.new variable! ffi_array_shape_dst
+ffi_array_shape_dst @ ffi_array_shape.new 8 ffi_array_copy_shape drop
+ffi_array_shape_dst ffi_array_shape.color@@ "Shape color: {}" string.format .cr
+ffi_array_shape_dst ffi_array_shape.sides@@ "Shape sides: {}" string.format .cr
+
+
+( Mistakes are reported as errors. )
+try
+    [ 1 , 2 , 3 , 4 ] [ 1 ] 8 ffi_array_copy_f64
+catch
+    drop "Too many items caught." .cr
+endcatch
+
+try
+    ffi.fn libc_arrays memcpy as ffi_array_bad ffi.f64[3] -> ffi.void
+catch
+    drop "Inline array parameter caught." .cr
+endcatch
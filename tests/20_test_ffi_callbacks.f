@@ -0,0 +1,130 @@
+( These tests bind to the C library, so they only run on Linux. )
+ffi.load libc.so.6 as libc_callbacks
+
+
+( Sort an array of integers with a sorth comparison word. )
+ffi.[] ffi.i32[]
+ffi.[] ffi.i32[1]
+
+ffi.fn libc_callbacks qsort as ffi_callback_qsort_ints ffi.i32[] ffi.len ffi.u64 ffi.u64 ffi.fn-ptr -> ffi.void
+
+0 variable! ffi_callback_calls
+
+: ffi_callback_compare_ints  ( a b -- order )
+    ffi_callback_calls ++!
+    [ 0 ]@ swap [ 0 ]@ swap -
+;
+
+ffi.callback ffi_callback_compare_ints ffi.i32[1]-ptr ffi.i32[1]-ptr -> ffi.i32
+variable! ffi_callback_int_cmp
+
+[ 5 , -3 , 9 , 1 , 0 ] variable! ffi_callback_ints
+ffi_callback_ints @ 4 ffi_callback_int_cmp @ ffi_callback_qsort_ints
+ffi_callback_ints @ "Sorted ints: {}" string.format .cr
+ffi_callback_calls @ 0 > "Comparisons made: {}" string.format .cr
+
+
+( Structures can be passed to callbacks by pointer. )
+ffi.# ffi_callback_item
+    ffi.i32 key -> 0 ,
+    ffi.f32 weight -> 0.0
+;
+
+ffi.[] ffi_callback_item[]
+
+ffi.fn libc_callbacks qsort as ffi_callback_qsort_items ffi_callback_item[] ffi.len ffi.u64 ffi.u64 ffi.fn-ptr -> ffi.void
+
+: ffi_callback_compare_items  ( a b -- order )
+    ffi_callback_item.weight@ swap ffi_callback_item.weight@ swap -
+    dup 0.0 >
+    if
+        drop 1
+    else
+        0.0 < if -1 else 0 then
+    then
+;
+
+ffi.callback ffi_callback_compare_items ffi_callback_item-ptr ffi_callback_item-ptr -> ffi.i32
+variable! ffi_callback_item_cmp
+
+ffi_callback_item.new variable! ffi_callback_first
+1 ffi_callback_first ffi_callback_item.key!!
+2.5 ffi_callback_first ffi_callback_item.weight!!
+
+ffi_callback_item.new variable! ffi_callback_second
+2 ffi_callback_second ffi_callback_item.key!!
+0.5 ffi_callback_second ffi_callback_item.weight!!
+
+ffi_callback_item.new variable! ffi_callback_third
+3 ffi_callback_third ffi_callback_item.key!!
+1.5 ffi_callback_third ffi_callback_item.weight!!
+
+[ ffi_callback_first @ , ffi_callback_second @ , ffi_callback_third @ ] variable! ffi_callback_items
+
+ffi_callback_items @ 8 ffi_callback_item_cmp @ ffi_callback_qsort_items
+ffi_callback_items [ 0 ]@@ ffi_callback_item.key@ "Lightest item: {}" string.format .cr
+ffi_callback_items [ 2 ]@@ ffi_callback_item.key@ "Heaviest item: {}" string.format .cr
+
+ffi_callback_item_cmp @ ffi.callback.free
+
+
+( Errors thrown in a callback are raised once the native function returns. )
+: ffi_callback_failing  ( a b -- order )
+    2drop
+    "Failed inside the callback." throw
+;
+
+ffi.callback ffi_callback_failing ffi.i32[1]-ptr ffi.i32[1]-ptr -> ffi.i32
+variable! ffi_callback_fail_cmp
+
+try
+    ffi_callback_ints @ 4 ffi_callback_fail_cmp @ ffi_callback_qsort_ints
+catch
+    "Failed inside the callback." swap string.find string.npos <> "Callback error caught: {}" string.format .cr
+endcatch
+
+ffi_callback_fail_cmp @ ffi.callback.free
+
+
+( A callback can free itself while it's running, it's released once the native function returns. )
+0 variable! ffi_callback_self_cmp
+
+: ffi_callback_freeing  ( a b -- order )
+    ffi_callback_self_cmp @ 0 <>
+    if
+        ffi_callback_self_cmp @ ffi.callback.free
+        0 ffi_callback_self_cmp !
+    then
+
+    ffi_callback_compare_ints
+;
+
+ffi.callback ffi_callback_freeing ffi.i32[1]-ptr ffi.i32[1]-ptr -> ffi.i32
+dup ffi_callback_self_cmp !
+variable! ffi_callback_self_address
+
+[ 4 , 2 , 8 , 6 ] variable! ffi_callback_self_ints
+ffi_callback_self_ints @ 4 ffi_callback_self_address @ ffi_callback_qsort_ints
+ffi_callback_self_ints @ "Sorted while freed: {}" string.format .cr
+
+try
+    ffi_callback_self_address @ ffi.callback.free
+catch
+    drop "Self freed callback is gone." .cr
+endcatch
+
+
+( Callbacks must be freed exactly once. )
+ffi_callback_int_cmp @ ffi.callback.free
+
+try
+    ffi_callback_int_cmp @ ffi.callback.free
+catch
+    drop "Double free caught." .cr
+endcatch
+
+try
+    ffi.callback ffi_callback_no_such_word ffi.i32 -> ffi.void
+catch
+    drop "Unknown word caught." .cr
+endcatch
@@ -0,0 +1,103 @@
+( These tests bind to the C library, so they only run on Linux. )
+ffi.load libc.so.6 as libc_pointers
+
+
+( Native memory can be allocated, written, and read back by type. )
+16 ffi.malloc variable! ffi_ptr_memory
+
+-42 ffi_ptr_memory @ "ffi.i32" ffi.poke
+2.25 ffi_ptr_memory @ 8 + "ffi.f64" ffi.poke
+
+ffi_ptr_memory @ "ffi.i32" ffi.peek "Peeked int: {}" string.format .cr
+ffi_ptr_memory @ 8 + "ffi.f64" ffi.peek "Peeked float: {}" string.format .cr
+ffi_ptr_memory @ "ffi.u8" ffi.peek "Peeked byte: {}" string.format .cr
+
+
+( Native memory can be used as a byte buffer without taking ownership. )
+ffi_ptr_memory @ 16 ffi.buffer.borrow variable! ffi_ptr_view
+
+ffi_ptr_view @ 4 true buffer.int@ "Buffer view: {}" string.format .cr
+7 ffi_ptr_view @ 4 buffer.int!
+ffi_ptr_memory @ 4 + "ffi.i32" ffi.peek "Written through buffer: {}" string.format .cr
+ffi_ptr_view @ ffi.buffer.address ffi_ptr_memory @ = "Same address: {}" string.format .cr
+
+
+( Raw pointers can be passed to and returned from functions. )
+ffi.fn libc_pointers memset as ffi_ptr_memset ffi.ptr ffi.i32 ffi.u64 -> ffi.ptr
+
+ffi_ptr_memory @ 255 4 ffi_ptr_memset ffi_ptr_memory @ = "Returned pointer: {}" string.format .cr
+ffi_ptr_memory @ "ffi.i32" ffi.peek "After memset: {}" string.format .cr
+
+ffi_ptr_memory @ ffi.free
+
+
+( Typed pointers work as out parameters when given a one item array. )
+ffi.fn libc_pointers frexp as ffi_ptr_frexp ffi.f64 ffi.i32-ptr -> ffi.f64
+
+[ 0 ] variable! ffi_ptr_exponent
+8.0 ffi_ptr_exponent @ ffi_ptr_frexp "Mantissa: {}" string.format .cr
+ffi_ptr_exponent [ 0 ]@@ "Exponent: {}" string.format .cr
+
+ffi.fn libc_pointers strtol as ffi_ptr_strtol ffi.ptr ffi.ptr-ptr ffi.i32 -> ffi.i64
+ffi.fn libc_pointers strdup as ffi_ptr_strdup ffi.string -> ffi.ptr
+
+"123xyz" ffi_ptr_strdup variable! ffi_ptr_digits
+[ ffi.null ] variable! ffi_ptr_end
+
+ffi_ptr_digits @ ffi_ptr_end @ 10 ffi_ptr_strtol "Parsed: {}" string.format .cr
+ffi_ptr_end [ 0 ]@@ ffi_ptr_digits @ - "Stopped after: {}" string.format .cr
+ffi_ptr_end [ 0 ]@@ "ffi.u8" ffi.peek "Stopped at: {}" string.format .cr
+ffi_ptr_digits @ ffi.free
+
+
+( Memory returned by C can be owned by a buffer and freed with it. )
+ffi.fn libc_pointers strlen as ffi_ptr_strlen ffi.ptr -> ffi.u64
+
+"duplicated" ffi_ptr_strdup variable! ffi_ptr_copy
+ffi_ptr_copy @ ffi_ptr_strlen "Length: {}" string.format .cr
+ffi_ptr_copy @ ffi_ptr_copy @ ffi_ptr_strlen ffi.buffer.own variable! ffi_ptr_owned
+ffi_ptr_owned @ 10 buffer.string@ "Owned text: {}" string.format .cr
+
+"owned with free" ffi_ptr_strdup 15 "free" "libc_pointers" ffi.sym ffi.buffer.own-with variable! ffi_ptr_owned_with
+ffi_ptr_owned_with @ 5 buffer.string@ "Freed with: {}" string.format .cr
+
+
+( Structure pointers also accept raw addresses. )
+ffi.# ffi_ptr_pair
+    ffi.i32 first -> 0 ,
+    ffi.i32 second -> 0
+;
+
+ffi.fn libc_pointers memcpy as ffi_ptr_copy_pair ffi_ptr_pair-ptr ffi_ptr_pair-ptr ffi.u64 -> ffi.ptr
+
+8 ffi.malloc variable! ffi_ptr_pair_memory
+ffi_ptr_pair.new variable! ffi_ptr_pair_src
+3 ffi_ptr_pair_src ffi_ptr_pair.first!!
+4 ffi_ptr_pair_src ffi_ptr_pair.second!!
+
+ffi_ptr_pair_memory @ ffi_ptr_pair_src @ 8 ffi_ptr_copy_pair drop
+ffi_ptr_pair_memory @ "ffi_ptr_pair" ffi.peek ffi_ptr_pair.second@ "Pair second: {}" string.format .cr
+ffi_ptr_pair_memory @ ffi.free
+
+
+( Errno is captured right after each call. )
+ffi.fn libc_pointers close as ffi_ptr_close ffi.i32 -> ffi.i32
+
+-1 ffi_ptr_close "Close result: {}" string.format .cr
+ffi.errno@ "Errno: {}" string.format .cr
+
+
+( Null addresses are errors. )
+ffi.null "Null: {}" string.format .cr
+
+try
+    ffi.null "ffi.i32" ffi.peek
+catch
+    drop "Null peek caught." .cr
+endcatch
+
+try
+    "text" 16 "ffi.string" ffi.poke
+catch
+    drop "String poke caught." .cr
+endcatch
@@ -0,0 +1,60 @@
+( These tests bind to the C library, so they only run on Linux. )
+ffi.load libc.so.6 as libc_variadic
+
+
+( Variadic functions take their extra arguments as an array, typed by their values. )
+ffi.fn libc_variadic snprintf as ffi_var_snprintf ffi.buffer ffi.u64 ffi.string ... -> ffi.i32
+
+64 buffer.new variable! ffi_var_buffer
+
+ffi_var_buffer @ 64 "%ld-%s-%.2f" [ 42 , "abc" , 1.5 ] ffi_var_snprintf
+"Formatted length: {}" string.format .cr
+
+0 ffi_var_buffer @ buffer.position!
+ffi_var_buffer @ 11 buffer.string@ "Formatted: {}" string.format .cr
+
+
+( The same binding can be called with a different number of arguments each time. )
+ffi_var_buffer @ 64 "no args" 0 [].new ffi_var_snprintf "No args length: {}" string.format .cr
+
+0 ffi_var_buffer @ buffer.position!
+ffi_var_buffer @ 7 buffer.string@ "No args: {}" string.format .cr
+
+
+( A type name and value pair passes the value as the given type. )
+ffi_var_buffer @ 64 "%d %u" [ [ "ffi.i32" , -7 ] , [ "ffi.u32" , 7 ] ] ffi_var_snprintf drop
+
+0 ffi_var_buffer @ buffer.position!
+ffi_var_buffer @ 5 buffer.string@ "Typed: {}" string.format .cr
+
+
+( Null pointers are passed for none. )
+ffi_var_buffer @ 64 "%p" [ none ] ffi_var_snprintf drop
+
+0 ffi_var_buffer @ buffer.position!
+ffi_var_buffer @ 5 buffer.string@ "Null: {}" string.format .cr
+
+
+( Non-variadic functions can name their calling convention. )
+ffi.fn libc_variadic abs as ffi_var_abs abi default ffi.i32 -> ffi.i32
+
+-12 ffi_var_abs "Explicit abi: {}" string.format .cr
+
+
+try
+    ffi.fn libc_variadic abs as ffi_var_bad_abs abi no-such-abi ffi.i32 -> ffi.i32
+catch
+    "Unknown calling convention" swap string.find string.npos <> "Bad abi caught: {}" string.format .cr
+endcatch
+
+try
+    ffi.fn libc_variadic printf as ffi_var_bad_printf ... ffi.string -> ffi.i32
+catch
+    drop "Misplaced ... caught." .cr
+endcatch
+
+try
+    ffi_var_buffer @ 64 "%s" [ 0 [].new ] ffi_var_snprintf
+catch
+    drop "Bad variadic argument caught." .cr
+endcatch
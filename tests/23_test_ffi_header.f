@@ -0,0 +1,60 @@
+( These tests bind to the C library, so they only run on Linux. )
+ffi.bind-header libc.so.6 tests/23_test_ffi_header.h
+
+
+( Integer #defines and enum values become constants. )
+HEADER_BUFFER_SIZE "Buffer size: {}" string.format .cr
+HEADER_FLAGS "Flags: {}" string.format .cr
+HEADER_NEGATIVE "Negative: {}" string.format .cr
+HEADER_GREEN HEADER_BLUE "Green: {} blue: {}" string.format .cr
+
+
+( Functions are bound under their C names, using the structures from the header. )
+17 5 div variable! ffi_header_div
+ffi_header_div header_div_t.quot@@ ffi_header_div header_div_t.rem@@
+"Quotient: {} remainder: {}" string.format .cr
+
+"hello" strlen "Length: {}" string.format .cr
+"ff" none 16 strtol "Parsed: {}" string.format .cr
+97 toupper "Upper: {}" string.format .cr
+
+header_timespec.new variable! ffi_header_time
+0 ffi_header_time @ clock_gettime "Clock result: {}" string.format .cr
+ffi_header_time header_timespec.tv_sec@@ 0 > "Clock filled in: {}" string.format .cr
+
+
+( Variadic prototypes take their extra arguments as an array. )
+HEADER_BUFFER_SIZE buffer.new variable! ffi_header_buffer
+ffi_header_buffer @ HEADER_BUFFER_SIZE "%ld/%s" [ 7 , "seven" ] snprintf drop
+
+0 ffi_header_buffer @ buffer.position!
+ffi_header_buffer @ 7 buffer.string@ "Formatted: {}" string.format .cr
+
+
+( Fields declared as arrays become fixed size arrays. )
+header_tagged.new variable! ffi_header_source
+header_tagged.new variable! ffi_header_tagged
+
+[ 1 , 2 , 3 , 4 ] ffi_header_source header_tagged.values!!
+HEADER_BLUE ffi_header_source header_tagged.color!!
+
+ffi_header_tagged @ ffi_header_source @ 20 memcpy drop
+ffi_header_tagged header_tagged.values@@ "Values: {}" string.format .cr
+ffi_header_tagged header_tagged.color@@ "Color: {}" string.format .cr
+
+
+( The declarations that can't be bound are reported. )
+"libc.so.6" "tests/23_test_ffi_header.h" ffi.header.generate
+variable! ffi_header_unmapped
+drop
+
+ffi_header_unmapped [].size@@ "Unmapped: {}" string.format .cr
+
+0 variable! ffi_header_index
+
+begin
+    ffi_header_index @ ffi_header_unmapped [].size@@ <
+while
+    ffi_header_unmapped [ ffi_header_index @ ]@@ .cr
+    ffi_header_index ++!
+repeat
@@ -0,0 +1,75 @@
+/* A small slice of the C library, declared for the ffi.bind-header tests. */
+#ifndef TEST_FFI_HEADER_H
+#define TEST_FFI_HEADER_H
+
+#define HEADER_API
+#define HEADER_BUFFER_SIZE 64
+#define HEADER_FLAGS (0x10 | 0x01)
+#define HEADER_SHIFTED (1 << 4)
+#define HEADER_NEGATIVE (-HEADER_BUFFER_SIZE)
+#define HEADER_VERSION "1.0"
+#define HEADER_MAX(a, b) ((a) > (b) ? (a) : (b))
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    HEADER_RED,
+    HEADER_GREEN = 5,
+    HEADER_BLUE
+} header_color;
+
+typedef struct {
+    int quot;
+    int rem;
+} header_div_t;
+
+struct header_timespec {
+    long tv_sec;
+    long tv_nsec;
+};
+
+struct header_tagged {
+    char tag[8];
+    unsigned short values[HEADER_SHIFTED / 4];
+    header_color color;
+};
+
+union header_value {
+    int i;
+    float f;
+};
+
+struct header_bits {
+    unsigned int flag : 1;
+};
+
+typedef int (*header_compare_fn)(const void *, const void *);
+
+extern int header_global;
+
+HEADER_API header_div_t div(int numerator, int denominator);
+HEADER_API int clock_gettime(int clock_id, struct header_timespec *time);
+HEADER_API unsigned long strlen(const char *string);
+HEADER_API long strtol(const char *string, char **end, int base);
+HEADER_API int snprintf(char *buffer, unsigned long size, const char *format, ...);
+HEADER_API int toupper(int character);
+HEADER_API void *memcpy(struct header_tagged *destination, const struct header_tagged *source,
+                        unsigned long count);
+HEADER_API void qsort(void *base, unsigned long count, unsigned long size,
+                      header_compare_fn compare);
+
+long double header_long_double(long double value);
+union header_value header_union(void);
+
+static inline int header_twice(int value)
+{
+    return value * 2;
+}
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
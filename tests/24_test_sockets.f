@@ -0,0 +1,99 @@
+( Listening on port 0 picks a free port, the address it picked can be read back. )
+"127.0.0.1:0" socket.opt/reuse-addr socket.opt/nodelay | socket.listen variable! sock_server
+sock_server @ socket.local-address@ variable! sock_address
+
+"127.0.0.1:0" sock_address @ <> "Picked a port: {}" string.format .cr
+
+
+( Connections are accepted as new fds, along with the address of the peer. )
+sock_address @ socket.connect variable! sock_client
+sock_server @ socket.accept variable! sock_peer variable! sock_connection
+
+"127.0.0.1:" sock_peer @ string.find 0 = "Peer address: {}" string.format .cr
+
+"ping" sock_client @ file.line!
+sock_connection @ file.line@ "Server read: {}" string.format .cr
+
+"pong" sock_connection @ file.line!
+sock_client @ file.line@ "Client read: {}" string.format .cr
+
+
+( Accepted connections inherit the nodelay option from the listener. )
+sock_connection @ socket.nodelay@ "Accepted nodelay: {}" string.format .cr
+sock_client @ socket.nodelay@ "Client nodelay: {}" string.format .cr
+
+true sock_client @ socket.nodelay!
+sock_client @ socket.nodelay@ "Client nodelay set: {}" string.format .cr
+
+
+try
+    sock_server @ file.line@
+catch
+    "socket.accept" swap string.find string.npos <> "Listener read caught: {}" string.format .cr
+endcatch
+
+sock_client @ file.close
+sock_connection @ file.close
+sock_server @ file.close
+
+
+( UDP sockets send and receive datagrams from ByteBuffers. )
+"127.0.0.1:0" none udp.bind variable! udp_sender
+"127.0.0.1:0" 0 udp.bind variable! udp_receiver
+
+16 buffer.new variable! udp_out
+"datagram" udp_out @ 8 buffer.string!
+0 udp_out @ buffer.position!
+
+udp_out @ 8 udp_receiver @ socket.local-address@ udp_sender @ udp.send-to
+"Sent: {}" string.format .cr
+
+udp_out @ buffer.position@ "Out position: {}" string.format .cr
+
+32 buffer.new variable! udp_in
+udp_in @ udp_receiver @ udp.recv-from variable! udp_from
+"Received: {}" string.format .cr
+
+udp_from @ udp_sender @ socket.local-address@ = "Sender matches: {}" string.format .cr
+udp_in @ buffer.position@ "In position: {}" string.format .cr
+
+0 udp_in @ buffer.position!
+udp_in @ 8 buffer.string@ "Datagram: {}" string.format .cr
+
+udp_sender @ file.close
+udp_receiver @ file.close
+
+
+try
+    "127.0.0.1:0" socket.opt/nodelay udp.bind
+catch
+    drop "UDP nodelay caught." .cr
+endcatch
+
+
+( Unix domain socket servers listen on a path. )
+[is-linux?] [if]
+    "/tmp/sorth_24_test_sockets.sock" variable! unix_path
+
+    unix_path @ socket.opt/reuse-addr socket.listen variable! unix_server
+    unix_server @ socket.local-address@ unix_path @ = "Unix address: {}" string.format .cr
+
+    unix_path @ socket.connect variable! unix_client
+    unix_server @ socket.accept variable! unix_peer variable! unix_connection
+
+    unix_peer @ "" = "Unix peer unnamed: {}" string.format .cr
+
+    "unix ping" unix_client @ file.line!
+    unix_connection @ file.line@ "Unix server read: {}" string.format .cr
+
+    unix_client @ file.close
+    unix_connection @ file.close
+    unix_server @ file.close
+
+    ( A stale socket file is replaced when listening with reuse-addr. )
+    unix_path @ socket.opt/reuse-addr socket.listen variable! unix_relisten
+    "Unix relisten worked." .cr
+
+    unix_relisten @ file.close
+    unix_path @ file.delete
+[then]
@@ -0,0 +1,101 @@
+( Buffers are written to and read back from files as raw bytes. )
+8 buffer.new variable! fbuf_out
+
+1 fbuf_out @ 4 buffer.int!
+2 fbuf_out @ 4 buffer.int!
+
+"./test_file_buffers.bin" file.r/w file.create variable! fbuf_fd
+
+fbuf_out @ fbuf_fd @ file.write-all
+"tail" fbuf_fd @ file.line!
+"end" fbuf_fd @ file.!
+
+fbuf_fd @ file.tell "Position after writes: {}" string.format .cr
+
+
+( Seeking moves the cursor relative to the start, current position, or end. )
+0 file.seek/start fbuf_fd @ file.seek
+
+4 fbuf_fd @ file.@ variable! fbuf_first
+0 fbuf_first @ buffer.position!
+fbuf_first @ 4 false buffer.int@ "First int: {}" string.format .cr
+
+4 file.seek/current fbuf_fd @ file.seek
+fbuf_fd @ file.tell "Position after skip: {}" string.format .cr
+
+-3 file.seek/end fbuf_fd @ file.seek
+fbuf_fd @ file.string@ "End: {}" string.format .cr
+
+( Asking for more than the file holds gives back what's there. )
+-3 file.seek/end fbuf_fd @ file.seek
+1000000000000 fbuf_fd @ file.@ buffer.size@ "Oversized read: {}" string.format .cr
+
+
+( Reading into an existing buffer stops at the end of the file. )
+4 file.seek/start fbuf_fd @ file.seek
+
+16 buffer.new variable! fbuf_in
+fbuf_in @ fbuf_fd @ file.read-into "Read into: {}" string.format .cr
+fbuf_in @ buffer.position@ "Read into position: {}" string.format .cr
+
+0 fbuf_in @ buffer.position!
+fbuf_in @ 4 false buffer.int@ "Second int: {}" string.format .cr
+
+
+( Line reads that read ahead don't throw off the cursor. )
+8 file.seek/start fbuf_fd @ file.seek
+fbuf_fd @ file.line@ "Line: {}" string.format .cr
+fbuf_fd @ file.tell "Position after line: {}" string.format .cr
+
+
+( Partial buffers are written from their position. )
+2 fbuf_out @ buffer.position!
+0 file.seek/start fbuf_fd @ file.seek
+
+fbuf_out @ 2 fbuf_fd @ file.write-buffer
+fbuf_out @ buffer.position@ "Write buffer position: {}" string.format .cr
+
+
+( Truncating cuts the file down without moving the cursor. )
+2 fbuf_fd @ file.truncate
+fbuf_fd @ file.sync
+
+fbuf_fd @ file.size@ "Truncated size: {}" string.format .cr
+fbuf_fd @ file.tell "Position after truncate: {}" string.format .cr
+
+0 file.seek/start fbuf_fd @ file.seek
+fbuf_fd @ file.read-all buffer.size@ "Read all size: {}" string.format .cr
+
+10 fbuf_fd @ file.@ buffer.size@ "Read at end: {}" string.format .cr
+
+fbuf_fd @ file.close
+"./test_file_buffers.bin" file.delete
+
+
+try
+    0 99 none file.seek
+catch
+    drop "Bad seek caught." .cr
+endcatch
+
+
+( The same words work with socket streams, but they can't seek. )
+"127.0.0.1:0" none socket.listen variable! fbuf_server
+fbuf_server @ socket.local-address@ socket.connect variable! fbuf_client
+fbuf_server @ socket.accept drop variable! fbuf_connection
+
+fbuf_out @ fbuf_client @ file.write-all
+socket.shut/w fbuf_client @ socket.shutdown
+
+4 fbuf_connection @ file.@ buffer.size@ "Socket read: {}" string.format .cr
+fbuf_connection @ file.read-all buffer.size@ "Socket read all: {}" string.format .cr
+
+try
+    0 file.seek/start fbuf_connection @ file.seek
+catch
+    drop "Socket seek caught." .cr
+endcatch
+
+fbuf_client @ file.close
+fbuf_connection @ file.close
+fbuf_server @ file.close
@@ -0,0 +1,43 @@
+( Temporary files get unique names and are kept unless they were asked to be deleted. )
+file.r/w file.create.tempfile variable! tmp_fd variable! tmp_path
+file.r/w file.create.tempfile variable! tmp_other_fd variable! tmp_other_path
+
+tmp_path @ file.exists? "Temp file exists: {}" string.format .cr
+tmp_path @ tmp_other_path @ <> "Temp names differ: {}" string.format .cr
+
+"scratch" tmp_fd @ file.line!
+0 file.seek/start tmp_fd @ file.seek
+tmp_fd @ file.line@ "Temp file read: {}" string.format .cr
+
+tmp_fd @ file.close
+tmp_other_fd @ file.close
+
+tmp_path @ file.exists? "Kept after close: {}" string.format .cr
+
+tmp_path @ file.delete
+tmp_other_path @ file.delete
+
+
+( Auto-delete temp files are removed when their fd is closed. )
+file.w/o file.auto-delete | file.create.tempfile variable! tmp_auto_fd variable! tmp_auto_path
+
+"gone soon" tmp_auto_fd @ file.line!
+tmp_auto_fd @ file.close
+
+tmp_auto_path @ file.exists? "Exists after auto-delete: {}" string.format .cr
+
+
+( Temporary directories are removed along with everything in them. )
+file.auto-delete dir.create.temp variable! tmp_dir_fd variable! tmp_dir_path
+
+tmp_dir_path @ "/inner.txt" + file.w/o file.create file.close
+tmp_dir_path @ "/inner.txt" + file.exists? "Inner file exists: {}" string.format .cr
+
+try
+    tmp_dir_fd @ file.line@
+catch
+    drop "Directory read caught." .cr
+endcatch
+
+tmp_dir_fd @ file.close
+tmp_dir_path @ file.exists? "Exists after directory close: {}" string.format .cr
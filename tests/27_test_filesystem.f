@@ -0,0 +1,108 @@
+( Build a small tree in a temporary directory to look at. )
+file.auto-delete dir.create.temp variable! fs_root_fd variable! fs_root
+
+fs_root @ "src/nested/deep" path.join dir.create
+fs_root @ "src/nested/deep" path.join file.exists? "Created nested: {}" string.format .cr
+
+fs_root @ "src/main.rs" path.join file.w/o file.create variable! fs_fd
+"fn main() {}" fs_fd @ file.line!
+fs_fd @ file.close
+
+fs_root @ "src/nested/lib.rs" path.join file.w/o file.create file.close
+fs_root @ "src/nested/deep/notes.txt" path.join file.w/o file.create file.close
+fs_root @ "src/.hidden.rs" path.join file.w/o file.create file.close
+
+
+( Print an array of paths relative to the tree's root. )
+0 [].new variable! fs_list
+0 variable! fs_index
+
+: fs_show ( paths -- )
+    fs_list !
+    0 fs_index !
+
+    begin
+        fs_index @ fs_list [].size@@ <
+    while
+        fs_list [ fs_index @ ]@@ fs_root @ path.relative .cr
+        fs_index ++!
+    repeat
+;
+
+
+( Directory listings describe each entry. )
+fs_root @ "src" path.join dir.list variable! fs_entries
+
+fs_entries [].size@@ "Entries: {}" string.format .cr
+
+fs_entries [ 1 ]@@ variable! fs_main
+"name" fs_main @ {}@ "Name: {}" string.format .cr
+"type" fs_main @ {}@ "Type: {}" string.format .cr
+"size" fs_main @ {}@ "Size: {}" string.format .cr
+"mtime" fs_main @ {}@ 0 > "Has mtime: {}" string.format .cr
+
+"type" fs_entries [ 2 ]@@ {}@ "Nested type: {}" string.format .cr
+
+
+( Globs match within a directory, or across any number of them with ** . )
+"Glob *.rs:" .cr
+fs_root @ "src/*.rs" path.join file.glob fs_show
+
+"Glob **/*.rs:" .cr
+fs_root @ "src/**/*.rs" path.join file.glob fs_show
+
+( A string starting with a star is a multi-line string, so the star is escaped. )
+"Glob **/n*:" .cr
+fs_root @ "\**/n*" path.join file.glob fs_show
+
+
+( Walking calls a word for every entry under a directory. )
+"Walk:" .cr
+
+: fs_walk_entry ( entry -- )
+    dup "type" swap {}@ swap "path" swap {}@ fs_root @ path.relative "{} {}" string.format .cr
+;
+
+fs_root @ ` fs_walk_entry dir.walk
+
+
+( Files can be copied, renamed and inspected. )
+fs_root @ "src/main.rs" path.join fs_root @ "copy.rs" path.join file.copy
+fs_root @ "copy.rs" path.join file.stat variable! fs_copy_stat
+"size" fs_copy_stat @ {}@ "Copy size: {}" string.format .cr
+
+fs_root @ "copy.rs" path.join fs_root @ "renamed.rs" path.join file.rename
+fs_root @ "copy.rs" path.join file.exists? "Copy still exists: {}" string.format .cr
+fs_root @ "renamed.rs" path.join file.exists? "Renamed exists: {}" string.format .cr
+
+fs_root @ "src/nested" path.join dir.remove
+fs_root @ "src/nested" path.join file.exists? "Nested exists: {}" string.format .cr
+
+try
+    fs_root @ "missing" path.join file.stat
+catch
+    drop "Missing stat caught." .cr
+endcatch
+
+
+( Path words pull paths apart without touching the file system. )
+"a/b/c.tar.gz" path.parent "Parent: {}" string.format .cr
+"a/b/c.tar.gz" path.file-name "File name: {}" string.format .cr
+"a/b/c.tar.gz" path.extension "Extension: {}" string.format .cr
+"a/b/c" path.extension "No extension: '{}'" string.format .cr
+"/x/y/z" "/x/q" path.relative "Relative: {}" string.format .cr
+"/x/y" "/x/y" path.relative "Same relative: {}" string.format .cr
+
+
+( The working directory can be read and changed. )
+cwd@ variable! fs_old_cwd
+
+fs_root @ "src" path.join cwd!
+cwd@ path.canonical fs_root @ "src" path.join path.canonical = "Changed cwd: {}" string.format .cr
+"main.rs" file.exists? "Relative to new cwd: {}" string.format .cr
+
+fs_old_cwd @ cwd!
+cwd@ fs_old_cwd @ = "Restored cwd: {}" string.format .cr
+
+fs_root_fd @ file.close
+fs_root @ file.exists? "Tree exists: {}" string.format .cr
@@ -0,0 +1,114 @@
+( These tests run standard Unix tools, so they only run on Linux. )
+
+
+( Running a command waits for it and captures it's output and exit code. )
+[ "sh" , "-c" , "echo out; echo err >&2; exit 3" ] none process.run
+variable! proc_code variable! proc_err variable! proc_out
+
+proc_out @ "out\n" = "Output: {}" string.format .cr
+proc_err @ "err\n" = "Error: {}" string.format .cr
+proc_code @ "Exit code: {}" string.format .cr
+
+
+( The environment and working directory can be changed for each process. )
+[ "sh" , "-c" , "echo $PROC_TEST_VALUE $(pwd)" ]
+{ "env" -> { "PROC_TEST_VALUE" -> "set" } , "cwd" -> "/" }
+process.run drop drop "Environment and cwd: {}" string.format .
+
+[ "sh" , "-c" , "echo ${HOME:-unset}" ] { "clear-env" -> true } process.run drop drop
+"Cleared environment: {}" string.format .
+
+
+( Spawned processes are written to and read from through fds. )
+[ "tr" , "a-z" , "A-Z" ] none process.spawn variable! proc_upper
+
+proc_upper @ process.pid@ 0 > "Has pid: {}" string.format .cr
+proc_upper @ process.stderr@ none = "Stderr not piped: {}" string.format .cr
+
+"hello process" proc_upper @ process.stdin@ file.line!
+proc_upper @ process.stdin@ file.close
+
+proc_upper @ process.stdout@ file.line@ "Spawned output: {}" string.format .cr
+proc_upper @ process.wait "Spawned exit: {}" string.format .cr
+proc_upper @ process.wait "Waited again: {}" string.format .cr
+
+proc_upper @ process.stdout@ file.close
+
+
+( The output of one process can be piped into the next. )
+[ "printf" , "b\\na\\nc\\n" ] { "stdin" -> "null" } process.spawn variable! proc_first
+
+[ "sort" ] { "stdin" -> proc_first @ process.stdout@ } process.run
+drop drop "Piped: {}" string.format .
+
+proc_first @ process.wait drop
+proc_first @ process.stdout@ none = "Piped fd handed on: {}" string.format .cr
+
+
+( A pipe stays open if the process it was meant for couldn't be started. )
+[ "printf" , "kept\n" ] { "stdin" -> "null" } process.spawn variable! proc_kept
+
+try
+    [ "sorth-no-such-program" ] { "stdin" -> proc_kept @ process.stdout@ } process.run
+catch
+    drop
+endcatch
+
+proc_kept @ process.stdout@ file.line@ "Pipe kept: {}" string.format .cr
+proc_kept @ process.wait drop
+proc_kept @ process.stdout@ file.close
+
+
+( Output that was already read from a pipe can't be handed on to another process. )
+[ "printf" , "one\ntwo\n" ] { "stdin" -> "null" } process.spawn variable! proc_partial
+proc_partial @ process.wait drop
+proc_partial @ process.stdout@ file.line@ drop
+
+try
+    [ "cat" ] { "stdin" -> proc_partial @ process.stdout@ } process.run
+catch
+    drop "Read ahead pipe caught." .cr
+endcatch
+
+proc_partial @ process.stdout@ file.line@ "Rest of pipe: {}" string.format .cr
+proc_partial @ process.stdout@ file.close
+
+
+( Killed processes report the signal as a negative exit code. )
+[ "sleep" , "10" ] { "stdin" -> "null" , "stdout" -> "null" } process.spawn variable! proc_sleep
+
+try
+    proc_sleep @ process.free
+catch
+    drop "Running free caught." .cr
+endcatch
+
+proc_sleep @ process.kill
+proc_sleep @ process.wait "Killed exit: {}" string.format .cr
+
+
+( Processes that are done with are freed from the process table. )
+proc_sleep @ process.free
+proc_upper @ process.free
+proc_first @ process.free
+proc_kept @ process.free
+proc_partial @ process.free
+
+try
+    proc_sleep @ process.pid@
+catch
+    drop "Freed process caught." .cr
+endcatch
+
+
+try
+    [ "sorth-no-such-program" ] none process.run
+catch
+    drop "Missing program caught." .cr
+endcatch
+
+try
+    0 [].new none process.run
+catch
+    drop "Empty command caught." .cr
+endcatch
@@ -0,0 +1,107 @@
+( Polling waits on raw descriptors, so these tests only run on Linux. )
+
+
+( A connection over the loopback interface to poll. )
+"127.0.0.1:0" none socket.listen variable! poll_server
+poll_server @ socket.local-address@ socket.connect variable! poll_client
+
+
+( A listener with a connection waiting is ready to read. )
+[ [ poll_server @ , io.poll/read ] ] 1000 io.poll variable! poll_ready
+
+poll_ready [].size@@ "Listener ready count: {}" string.format .cr
+poll_ready [ 0 ]@@ [ 1 ]@ io.poll/read = "Listener readable: {}" string.format .cr
+
+poll_server @ socket.accept drop variable! poll_connection
+
+
+( Nothing has been sent yet, so only writing is ready. )
+[ [ poll_connection @ , io.poll/read io.poll/write | ] ] 0 io.poll poll_ready !
+
+poll_ready [ 0 ]@@ [ 1 ]@ "Idle events: {}" string.format .cr
+
+[ [ poll_connection @ , io.poll/read ] ] 50 io.poll [].size@ "Timed out count: {}" string.format .cr
+[ [ poll_connection @ , io.poll/read ] ] 2.5 io.poll [].size@ "Float timed out count: {}" string.format .cr
+
+
+( Once data arrives the connection is ready to read. )
+"first" poll_client @ file.line!
+"second" poll_client @ file.line!
+
+[ [ poll_connection @ , io.poll/read ] ] none io.poll poll_ready !
+poll_ready [ 0 ]@@ [ 0 ]@ poll_connection @ = "Ready fd matches: {}" string.format .cr
+poll_ready [ 0 ]@@ [ 1 ]@ "Data events: {}" string.format .cr
+
+
+( Lines that were read ahead still count as ready, even if the socket has nothing left. )
+poll_connection @ file.line@ "Read line: {}" string.format .cr
+
+[ [ poll_connection @ , io.poll/read ] ] 0 io.poll [].size@ "Read ahead ready: {}" string.format .cr
+
+poll_connection @ file.line@ "Read line: {}" string.format .cr
+
+
+( Non-blocking fds give none instead of waiting. )
+poll_connection @ file.nonblocking@ "Starts blocking: {}" string.format .cr
+
+true poll_connection @ file.nonblocking!
+poll_connection @ file.nonblocking@ "Now non-blocking: {}" string.format .cr
+
+poll_connection @ file.line@ none = "Line would block: {}" string.format .cr
+4 poll_connection @ file.@ none = "Read would block: {}" string.format .cr
+poll_connection @ file.read-all none = "Read all would block: {}" string.format .cr
+
+( Checking what's available leaves the fd non-blocking. )
+poll_connection @ socket.available@ "Available: {}" string.format .cr
+poll_connection @ file.nonblocking@ "Still non-blocking: {}" string.format .cr
+
+"partial" poll_client @ file.!
+[ [ poll_connection @ , io.poll/read ] ] 1000 io.poll drop
+
+poll_connection @ file.line@ none = "Partial line held: {}" string.format .cr
+"-line" poll_client @ file.line!
+[ [ poll_connection @ , io.poll/read ] ] 1000 io.poll drop
+poll_connection @ file.line@ "Completed line: {}" string.format .cr
+
+true poll_server @ file.nonblocking!
+poll_server @ socket.accept none = swap none = && "Accept would block: {}" string.format .cr
+
+
+( Closing the other end is reported as readable, with an end of file to read. )
+poll_client @ file.close
+
+[ [ poll_connection @ , io.poll/read ] ] 1000 io.poll poll_ready !
+poll_ready [ 0 ]@@ [ 1 ]@ io.poll/read & 0 <> "Closed readable: {}" string.format .cr
+poll_connection @ file.line@ "Closed line: '{}'" string.format .cr
+
+poll_connection @ file.close
+poll_server @ file.close
+
+
+( Process pipes can be polled and made non-blocking too. )
+[ "sh" , "-c" , "read line; echo got $line" ] none process.spawn variable! poll_process
+
+true poll_process @ process.stdout@ file.nonblocking!
+poll_process @ process.stdout@ file.line@ none = "Pipe would block: {}" string.format .cr
+
+"input" poll_process @ process.stdin@ file.line!
+[ [ poll_process @ process.stdout@ , io.poll/read ] ] 5000 io.poll [].size@
+"Pipe ready count: {}" string.format .cr
+poll_process @ process.stdout@ file.line@ "Pipe line: {}" string.format .cr
+
+poll_process @ process.stdin@ file.close
+poll_process @ process.wait drop
+poll_process @ process.stdout@ file.close
+
+
+try
+    [ poll_server @ ] 0 io.poll
+catch
+    drop "Bad request caught." .cr
+endcatch
+
+try
+    [ [ poll_server @ , io.poll/read ] ] "soon" io.poll
+catch
+    drop "Bad timeout caught." .cr
+endcatch
@@ -0,0 +1,79 @@
+( The clocks only move forward, so only compare them, their values change every run. )
+time.now 1700000000 > "Now after 2023: {}" string.format .cr
+time.now-ns 1700000000000000000 > "Now ns after 2023: {}" string.format .cr
+
+time.monotonic variable! time_start
+0.05 time.sleep
+time.monotonic time_start @ - 0.05 >= "Slept long enough: {}" string.format .cr
+
+time.monotonic-ns variable! time_start_ns
+0 time.sleep
+time.monotonic-ns time_start_ns @ >= "Monotonic ns forward: {}" string.format .cr
+
+
+( Epoch times are broken down into hash tables of their parts. )
+0 time.utc variable! time_parts
+
+"year" time_parts @ {}@ "Epoch year: {}" string.format .cr
+"month" time_parts @ {}@ "Epoch month: {}" string.format .cr
+"day" time_parts @ {}@ "Epoch day: {}" string.format .cr
+"weekday" time_parts @ {}@ "Epoch weekday: {}" string.format .cr
+"offset" time_parts @ {}@ "Epoch offset: {}" string.format .cr
+
+1700000000.25 time.utc time_parts !
+"hour" time_parts @ {}@ "Hour: {}" string.format .cr
+"nanosecond" time_parts @ {}@ "Nanosecond: {}" string.format .cr
+"yearday" time_parts @ {}@ "Yearday: {}" string.format .cr
+time_parts @ time.from-parts "Back to epoch: {}" string.format .cr
+
+
+( Local parts come back to the same epoch time, whatever the local zone is. )
+1700000000 time.local time.from-parts 1700000000 = "Local round trip: {}" string.format .cr
+1700000000 time.local time.from-local 1700000000 = "From local: {}" string.format .cr
+
+
+( Formatting uses strftime patterns, epoch times are in UTC and parts in their own offset. )
+1700000000 "%Y-%m-%d %H:%M:%S" time.format "Formatted: {}" string.format .cr
+1700000000 "%a %d %b %Y" time.format "Formatted names: {}" string.format .cr
+
+
+( Parsing takes ISO-8601 and RFC-3339 dates and times. )
+"2024-02-29T12:30:45.5+02:00" time.parse time_parts !
+
+"day" time_parts @ {}@ "Parsed day: {}" string.format .cr
+"offset" time_parts @ {}@ "Parsed offset: {}" string.format .cr
+time_parts @ "%H:%M:%S%.3f %:z" time.format "Parsed formatted: {}" string.format .cr
+time_parts @ time.from-parts "Parsed epoch: {}" string.format .cr
+
+"2024-02-29 12:30:45Z" time.parse time.from-parts "Space separated: {}" string.format .cr
+"2024-02-29T12:30:45" time.parse time.from-parts "No offset is UTC: {}" string.format .cr
+"2024-02-29" time.parse time.from-parts "Date only: {}" string.format .cr
+
+
+( Hand built parts only need a date, the rest default to zero. )
+{ "year" -> 2000 , "month" -> 1 , "day" -> 2 } time.from-parts "Built epoch: {}" string.format .cr
+
+
+try
+    "yesterday" time.parse
+catch
+    drop "Bad time caught." .cr
+endcatch
+
+try
+    { "year" -> 2023 , "month" -> 2 , "day" -> 29 } time.from-parts
+catch
+    drop "Bad date caught." .cr
+endcatch
+
+try
+    0 "%Q" time.format
+catch
+    drop "Bad pattern caught." .cr
+endcatch
+
+try
+    -1 time.sleep
+catch
+    drop "Negative sleep caught." .cr
+endcatch
@@ -0,0 +1,88 @@
+( Seeding the default generator makes it's numbers repeat. )
+42 random.seed
+1 1000000 random.int variable! random_first
+
+42 random.seed
+1 1000000 random.int random_first @ = "Seed repeats: {}" string.format .cr
+
+
+( A generator created with the same seed gives the same stream as the default one. )
+42 random.new variable! random_generator
+1 1000000 random_generator @ random.gen.int random_first @ = "Generator matches: {}" string.format .cr
+
+7 random_generator @ random.gen.seed
+random_generator @ random.gen.float variable! random_float
+7 random_generator @ random.gen.seed
+random_generator @ random.gen.float random_float @ = "Generator reseeds: {}" string.format .cr
+
+
+( Ints stay within their range, including both ends. )
+true variable! random_in_range
+0 variable! random_index
+
+begin
+    random_index @ 200 <
+while
+    -3 3 random.int dup -3 >= swap 3 <= && random_in_range @ && random_in_range !
+    random_index @ 1 + random_index !
+repeat
+
+random_in_range @ "Ints in range: {}" string.format .cr
+5 5 random.int "Single value range: {}" string.format .cr
+
+
+( Floats are from 0.0 up to 1.0. )
+random.float dup 0.0 >= swap 1.0 < && "Float in range: {}" string.format .cr
+random.bool dup true = swap false = || "Bool is bool: {}" string.format .cr
+
+
+( Choices come from the array, and shuffling keeps all of the values. )
+[ 10 , 20 , 30 ] variable! random_array
+
+random_array @ random.choice dup 10 = over 20 = || swap 30 = || "Choice in array: {}" string.format .cr
+
+random_array @ random.shuffle
+random_array [].size@@ "Shuffled size: {}" string.format .cr
+random_array [ 0 ]@@ random_array [ 1 ]@@ + random_array [ 2 ]@@ + "Shuffled sum: {}" string.format .cr
+
+random_array @ random_generator @ random.gen.shuffle
+random_array [].size@@ "Generator shuffled size: {}" string.format .cr
+random_array @ random_generator @ random.gen.choice 0 > "Generator choice: {}" string.format .cr
+
+
+( Byte buffers, from the generators and from the operating system. )
+16 random.bytes buffer.size@ "Random bytes: {}" string.format .cr
+8 random_generator @ random.gen.bytes buffer.size@ "Generator bytes: {}" string.format .cr
+32 random.secure-bytes buffer.size@ "Secure bytes: {}" string.format .cr
+
+3 random.seed
+5 random.bytes buffer.to-hex variable! random_hex
+3 random.seed
+5 random.bytes buffer.to-hex random_hex @ = "Seeded bytes repeat: {}" string.format .cr
+
+
+random_generator @ random.free
+
+try
+    5 1 random.int
+catch
+    drop "Empty range caught." .cr
+endcatch
+
+try
+    [] random.choice
+catch
+    drop "Empty choice caught." .cr
+endcatch
+
+try
+    0 random.free
+catch
+    drop "Default free caught." .cr
+endcatch
+
+try
+    random_generator @ random.gen.float
+catch
+    drop "Freed generator caught." .cr
+endcatch
@@ -0,0 +1,102 @@
+( Roots, powers and logarithms always give floats. )
+16 math.sqrt "Square root: {}" string.format .cr
+27 math.cbrt "Cube root: {}" string.format .cr
+2 0.5 math.pow "Float power: {}" string.format .cr
+2 10 math.int-pow "Int power: {}" string.format .cr
+0 math.exp "Exp: {}" string.format .cr
+math.e math.ln "Log e: {}" string.format .cr
+1000 math.log10 "Log 10: {}" string.format .cr
+1024 math.log2 "Log 2: {}" string.format .cr
+
+
+( Trigonometry works in radians. )
+0 math.sin "Sine: {}" string.format .cr
+0 math.cos "Cosine: {}" string.format .cr
+math.pi 4.0 / math.tan 1.0 - math.abs math.epsilon 4 * < "Tangent near 1: {}" string.format .cr
+1 math.asin 2 * math.pi = "Arcsine: {}" string.format .cr
+1 math.acos "Arccosine: {}" string.format .cr
+0 math.atan "Arctangent: {}" string.format .cr
+1 -1 math.atan2 math.pi 0.75 * = "Arctangent quadrant: {}" string.format .cr
+3 4 math.hypot "Hypotenuse: {}" string.format .cr
+math.pi 2 * math.tau = "Tau: {}" string.format .cr
+
+
+( Rounding keeps ints as ints, and floats as floats. )
+-2.5 math.floor "Floor: {}" string.format .cr
+-2.5 math.ceil "Ceil: {}" string.format .cr
+-2.5 math.round "Round: {}" string.format .cr
+-2.5 math.trunc "Trunc: {}" string.format .cr
+7 math.floor 2 / "Int floor stays int: {}" string.format .cr
+7.0 math.floor 2 / "Float floor stays float: {}" string.format .cr
+
+-5 math.abs "Int abs: {}" string.format .cr
+-5.5 math.abs "Float abs: {}" string.format .cr
+-12 math.sign "Int sign: {}" string.format .cr
+0 math.sign "Zero sign: {}" string.format .cr
+3.5 math.sign "Float sign: {}" string.format .cr
+
+3 9 math.min "Min: {}" string.format .cr
+3 9 math.max "Max: {}" string.format .cr
+3 2.5 math.min "Mixed min: {}" string.format .cr
+
+
+( Int only functions. )
+12 -18 math.gcd "Gcd: {}" string.format .cr
+4 6 math.lcm "Lcm: {}" string.format .cr
+0 5 math.lcm "Zero lcm: {}" string.format .cr
+
+
+( Predicates for the special float values. )
+math.nan math.nan? "NaN is NaN: {}" string.format .cr
+1.5 math.nan? "Number is NaN: {}" string.format .cr
+math.infinity math.infinite? "Infinity is infinite: {}" string.format .cr
+-1.0 0.0 / math.infinite? "Negative infinity is infinite: {}" string.format .cr
+42 math.finite? "Int is finite: {}" string.format .cr
+math.nan math.finite? "NaN is finite: {}" string.format .cr
+
+
+( Converting between ints and floats. )
+2.5 math.round/nearest math.to-int "Nearest: {}" string.format .cr
+2.5 math.round/even math.to-int "Even: {}" string.format .cr
+2.7 math.round/floor math.to-int "Floor int: {}" string.format .cr
+2.2 math.round/ceil math.to-int "Ceil int: {}" string.format .cr
+-2.7 math.round/trunc math.to-int "Trunc int: {}" string.format .cr
+9 math.round/floor math.to-int "Int stays: {}" string.format .cr
+3 math.to-float 2 / "To float: {}" string.format .cr
+
+
+try
+    2 63 math.int-pow
+catch
+    drop "Int power overflow caught." .cr
+endcatch
+
+try
+    2 -1 math.int-pow
+catch
+    drop "Negative exponent caught." .cr
+endcatch
+
+try
+    -9223372036854775807 1 - math.abs
+catch
+    drop "Abs overflow caught." .cr
+endcatch
+
+try
+    math.infinity math.round/trunc math.to-int
+catch
+    drop "Infinite to int caught." .cr
+endcatch
+
+try
+    1.5 99 math.to-int
+catch
+    drop "Bad rounding mode caught." .cr
+endcatch
+
+try
+    "text" math.abs
+catch
+    drop "Non-number caught." .cr
+endcatch
@@ -1,13 +1,20 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 // For library-based tests
 use sorth::runtime::built_ins::{
-    base_words::register_base_words, ffi_words::register_ffi_words, io_words::register_io_words,
-    terminal_words::register_terminal_words, user_words::register_user_words,
+    base_words::register_base_words, ffi_words::register_ffi_words,
+    filesystem_words::register_filesystem_words, http_words::register_http_words,
+    io_words::register_io_words, process_words::register_process_words,
+    random_words::register_random_words, regex_words::register_regex_words,
+    terminal_words::register_terminal_words, time_words::register_time_words,
+    user_words::register_user_words,
 };
+use sorth::runtime::data_structures::contextual_data::ContextualData;
 use sorth::runtime::interpreter::sorth_interpreter::SorthInterpreter;
-use sorth::runtime::interpreter::{CodeManagement, Interpreter};
+use sorth::runtime::interpreter::{CodeManagement, Interpreter, InterpreterStack};
 use std::fs;
 
 // Helper to get absolute path from manifest dir
@@ -82,6 +89,676 @@ fn assert_01_test_loops_output(output: &str) {
     );
 }
 
+fn assert_10_test_regex_output(output: &str) {
+    for expected in [
+        "Matched: true",
+        "Matched: false",
+        "Found: bob@example.com",
+        "Span: 5 20",
+        "User: bob",
+        "Host: example",
+        "No match: none",
+        "Count: 3",
+        "Last: 333",
+        "Month: 06",
+        "Swapped: world hello",
+        "Second: two",
+        "Bad pattern caught.",
+        "Non-regex caught.",
+        "Many patterns matched: true",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_11_test_json_output(output: &str) {
+    for expected in [
+        "Items: 6",
+        "Exponent: -300",
+        "Scalar: 42",
+        "Unicode size: 6",
+        r#"{"a":1,"b":"line\none \"quoted\"","c":[1,2.5,-300.0,true,null,"x"]}"#,
+        "{\n  \"x\": 10\n}",
+        r#"{"x":3,"y":4}"#,
+        "Wrapped: 2",
+        "Not an object caught.",
+        "JSON error at line 2, column 11",
+        "Deep nesting caught.",
+        "Cyclic value caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_12_test_csv_output(output: &str) {
+    for expected in [
+        "Rows: 3",
+        r#"Note: likes "quotes", commas"#,
+        "Multi-line size: 9",
+        "Name: sue",
+        "Semi: b;c",
+        "Round trip: true",
+        "Header round trip: true",
+        "Mixed keys: true",
+        "Streamed: y, z",
+        "Streamed: 1",
+        "End: none",
+        "Unterminated caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_13_test_msgpack_output(output: &str) {
+    for expected in [
+        "Equal: true",
+        "Int stays int: 1",
+        "Float stays float: 1.0",
+        "Buffer stays buffer: true",
+        "Large int: 70000",
+        "Structure: true",
+        "Field y: 4",
+        "first",
+        "Malformed caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_14_test_binary_layout_output(output: &str) {
+    for expected in [
+        "Packed size: 16",
+        "First byte: 202",
+        "Magic: true",
+        "Name: hdr",
+        "Packet size: 37",
+        "Point: -2",
+        "Label: a longer label",
+        "Nested version: 3",
+        "Position after: 37",
+        "Signed into unsigned caught.",
+        "Largest u16 packed: 16",
+        "Out of range int caught.",
+        "Negative unsigned caught.",
+        "Long string caught.",
+        "Short buffer caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_15_test_buffer_bits_output(output: &str) {
+    for expected in [
+        "First byte: 1",
+        "Big endian: 10203",
+        "Little endian: 30201",
+        "Signed 24-bit: -2",
+        "Default big endian: true",
+        "Default first byte: 1",
+        "Float: 1.5",
+        "Varint bytes: 3",
+        "Unsigned varint: 300",
+        "Signed varint: -3",
+        "Bit offset: 5",
+        "Packed byte: 191",
+        "Bits: 5",
+        "Bit: 1",
+        "More bits: 511",
+        "Aligned position: 2",
+        "Bad size caught.",
+        "Past the end caught.",
+        "Too many bits caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_16_test_buffer_slices_output(output: &str) {
+    for expected in [
+        "Slice size: 8",
+        "Slice position: 2",
+        "Parent position: 0",
+        "Parent sees: 1234",
+        "Inner write: 127",
+        "Still there: 1234",
+        "Copy size: 4",
+        "Slice big endian: true",
+        "Parent big endian: false",
+        "Compare equal: 0",
+        "Compare less: -1",
+        "Found at: 10",
+        "Not found: -1",
+        "String pattern: 14",
+        "Bad range caught.",
+        "Stale slice caught.",
+        "Stale slice printed.",
+        "Stale slice equal: true",
+        "Stale slice is empty: true",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_17_test_encoding_output(output: &str) {
+    for expected in [
+        "Hex: 48656c6c6f",
+        "Base64: SGVsbG8=",
+        "Decoded same: 0",
+        "URL safe: -_8",
+        "URL decoded: fbff",
+        "| Hello",
+        "Size: 5, position: 0",
+        "CRC32: cbf43926",
+        "SHA-256: ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        "Digest size: 32",
+        "SHA-1: a9993e364706816aba3e25717850c26c9cd0d89d",
+        "MD5: 900150983cd24fb0d6963f7d28e17f72",
+        "HMAC: 5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        "Odd hex caught.",
+        "Signed hex caught.",
+        "Bad base64 caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn assert_18_test_ffi_structs_output(output: &str) {
+    for expected in [
+        "Quotient: 3 remainder: 2",
+        "Address: 192.168.1.4",
+        "Clock result: 0",
+        "Clock filled in: true",
+        "Copied tag: 7",
+        "Copied label: hello",
+        "Copied small: -3",
+        "Copied ratio: 2.5",
+        "Packed bytes: 01050403",
+        "Packed by value caught.",
+        "Nested packed by value caught.",
+        "Natural packed by value allowed.",
+        "Wrong structure caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn assert_19_test_ffi_arrays_output(output: &str) {
+    for expected in [
+        "Copied ints: [ 10, -20, 30 ]",
+        "Returned floats: [ 1.5, 2.5, 0 ]",
+        "Pipe result: 0",
+        "Descriptors: 2",
+        "Wrote: 10",
+        "Read: 10",
+        "Read text: hello pipe",
+        "Got groups: true",
+        "Copied strings: [ \"alpha\", \"beta\" ]",
+        "Point updated in place: -5",
+        "Shape color: [ 1, 2, 3 ]",
+        "Shape sides: 4",
+        "Too many items caught.",
+        "Inline array parameter caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn assert_20_test_ffi_callbacks_output(output: &str) {
+    for expected in [
+        "Sorted ints: [ -3, 0, 1, 5, 9 ]",
+        "Comparisons made: true",
+        "Lightest item: 2",
+        "Heaviest item: 1",
+        "Callback error caught: true",
+        "Sorted while freed: [ 2, 4, 6, 8 ]",
+        "Self freed callback is gone.",
+        "Double free caught.",
+        "Unknown word caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn assert_21_test_ffi_pointers_output(output: &str) {
+    for expected in [
+        "Peeked int: -42",
+        "Peeked float: 2.25",
+        "Peeked byte: 214",
+        "Buffer view: -42",
+        "Written through buffer: 7",
+        "Same address: true",
+        "Returned pointer: true",
+        "After memset: -1",
+        "Mantissa: 0.5",
+        "Exponent: 4",
+        "Parsed: 123",
+        "Stopped after: 3",
+        "Stopped at: 120",
+        "Length: 10",
+        "Owned text: duplicated",
+        "Freed with: owned",
+        "Pair second: 4",
+        "Close result: -1",
+        "Errno: 9",
+        "Null: 0",
+        "Null peek caught.",
+        "String poke caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn assert_22_test_ffi_variadic_output(output: &str) {
+    for expected in [
+        "Formatted length: 11",
+        "Formatted: 42-abc-1.50",
+        "No args length: 7",
+        "No args: no args",
+        "Typed: -7 7",
+        "Null: (nil)",
+        "Explicit abi: 12",
+        "Bad abi caught: true",
+        "Misplaced ... caught.",
+        "Bad variadic argument caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn assert_23_test_ffi_header_output(output: &str) {
+    for expected in [
+        "Buffer size: 64",
+        "Flags: 17",
+        "Negative: -64",
+        "Green: 5 blue: 6",
+        "Quotient: 3 remainder: 2",
+        "Length: 5",
+        "Parsed: 255",
+        "Upper: 65",
+        "Clock result: 0",
+        "Clock filled in: true",
+        "Formatted: 7/seven",
+        "Values: [ 1, 2, 3, 4 ]",
+        "Color: 6",
+        "Unmapped: 8",
+        "union header_value: unions are not supported",
+        "struct header_bits: bit fields are not supported",
+        "function header_twice: inline and static functions are not exported",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_24_test_sockets_output(output: &str) {
+    for expected in [
+        "Picked a port: true",
+        "Peer address: true",
+        "Server read: ping",
+        "Client read: pong",
+        "Accepted nodelay: true",
+        "Client nodelay: false",
+        "Client nodelay set: true",
+        "Listener read caught: true",
+        "Sent: 8",
+        "Out position: 8",
+        "Received: 8",
+        "Sender matches: true",
+        "In position: 8",
+        "Datagram: datagram",
+        "UDP nodelay caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_25_test_file_buffers_output(output: &str) {
+    for expected in [
+        "Position after writes: 16",
+        "First int: 1",
+        "Position after skip: 8",
+        "End: end",
+        "Oversized read: 3",
+        "Read into: 12",
+        "Second int: 2",
+        "Line: tail",
+        "Position after line: 13",
+        "Write buffer position: 4",
+        "Truncated size: 2",
+        "Position after truncate: 2",
+        "Read all size: 2",
+        "Read at end: 0",
+        "Bad seek caught.",
+        "Socket read: 4",
+        "Socket read all: 4",
+        "Socket seek caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_26_test_temp_files_output(output: &str) {
+    for expected in [
+        "Temp file exists: true",
+        "Temp names differ: true",
+        "Temp file read: scratch",
+        "Kept after close: true",
+        "Exists after auto-delete: false",
+        "Inner file exists: true",
+        "Directory read caught.",
+        "Exists after directory close: false",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_27_test_filesystem_output(output: &str) {
+    for expected in [
+        "Created nested: true",
+        "Entries: 3",
+        "Name: main.rs",
+        "Type: file",
+        "Size: 13",
+        "Has mtime: true",
+        "Nested type: dir",
+        "Glob *.rs:\nsrc/main.rs\nGlob **/*.rs:\nsrc/main.rs\nsrc/nested/lib.rs\n",
+        "Glob **/n*:\nsrc/nested\nsrc/nested/deep/notes.txt\n",
+        "dir src\nfile src/.hidden.rs\nfile src/main.rs\ndir src/nested\ndir src/nested/deep\nfile src/nested/deep/notes.txt\nfile src/nested/lib.rs\n",
+        "Copy size: 13",
+        "Copy still exists: false",
+        "Renamed exists: true",
+        "Nested exists: false",
+        "Missing stat caught.",
+        "Parent: a/b",
+        "File name: c.tar.gz",
+        "Extension: gz",
+        "No extension: ''",
+        "Relative: ../y/z",
+        "Same relative: .",
+        "Changed cwd: true",
+        "Relative to new cwd: true",
+        "Restored cwd: true",
+        "Tree exists: false",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn assert_28_test_process_output(output: &str) {
+    for expected in [
+        "Output: true",
+        "Error: true",
+        "Exit code: 3",
+        "Environment and cwd: set /",
+        "Cleared environment: unset",
+        "Has pid: true",
+        "Stderr not piped: true",
+        "Spawned output: HELLO PROCESS",
+        "Spawned exit: 0",
+        "Waited again: 0",
+        "Piped: a\nb\nc\n",
+        "Piped fd handed on: true",
+        "Pipe kept: kept",
+        "Read ahead pipe caught.",
+        "Rest of pipe: two",
+        "Running free caught.",
+        "Killed exit: -9",
+        "Freed process caught.",
+        "Missing program caught.",
+        "Empty command caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn assert_29_test_poll_output(output: &str) {
+    for expected in [
+        "Listener readable: true",
+        "Idle events: 2",
+        "Timed out count: 0",
+        "Float timed out count: 0",
+        "Ready fd matches: true",
+        "Data events: 1",
+        "Read ahead ready: 1",
+        "Read line: second",
+        "Starts blocking: false",
+        "Line would block: true",
+        "Read would block: true",
+        "Read all would block: true",
+        "Still non-blocking: true",
+        "Partial line held: true",
+        "Completed line: partial-line",
+        "Accept would block: true",
+        "Closed readable: true",
+        "Closed line: ''",
+        "Pipe would block: true",
+        "Pipe line: got input",
+        "Bad request caught.",
+        "Bad timeout caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_30_test_time_output(output: &str) {
+    for expected in [
+        "Now after 2023: true",
+        "Now ns after 2023: true",
+        "Slept long enough: true",
+        "Monotonic ns forward: true",
+        "Epoch year: 1970",
+        "Epoch weekday: 4",
+        "Epoch offset: 0",
+        "Hour: 22",
+        "Nanosecond: 250000000",
+        "Yearday: 318",
+        "Back to epoch: 1700000000.25",
+        "Local round trip: true",
+        "From local: true",
+        "Formatted: 2023-11-14 22:13:20",
+        "Formatted names: Tue 14 Nov 2023",
+        "Parsed offset: 7200",
+        "Parsed formatted: 12:30:45.500 +02:00",
+        "Parsed epoch: 1709202645.5",
+        "Space separated: 1709209845",
+        "No offset is UTC: 1709209845",
+        "Date only: 1709164800",
+        "Built epoch: 946771200",
+        "Bad time caught.",
+        "Bad date caught.",
+        "Bad pattern caught.",
+        "Negative sleep caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_31_test_random_output(output: &str) {
+    for expected in [
+        "Seed repeats: true",
+        "Generator matches: true",
+        "Generator reseeds: true",
+        "Ints in range: true",
+        "Single value range: 5",
+        "Float in range: true",
+        "Bool is bool: true",
+        "Choice in array: true",
+        "Shuffled size: 3",
+        "Shuffled sum: 60",
+        "Generator shuffled size: 3",
+        "Generator choice: true",
+        "Random bytes: 16",
+        "Generator bytes: 8",
+        "Secure bytes: 32",
+        "Seeded bytes repeat: true",
+        "Empty range caught.",
+        "Empty choice caught.",
+        "Default free caught.",
+        "Freed generator caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
+fn assert_32_test_math_output(output: &str) {
+    for expected in [
+        "Square root: 4",
+        "Cube root: 3",
+        "Float power: 1.4142135623730951",
+        "Int power: 1024",
+        "Exp: 1",
+        "Log e: 1",
+        "Log 10: 3",
+        "Log 2: 10",
+        "Sine: 0",
+        "Cosine: 1",
+        "Tangent near 1: true",
+        "Arcsine: true",
+        "Arccosine: 0",
+        "Arctangent: 0",
+        "Arctangent quadrant: true",
+        "Hypotenuse: 5",
+        "Tau: true",
+        "Floor: -3",
+        "Ceil: -2",
+        "Round: -3",
+        "Trunc: -2",
+        "Int floor stays int: 3",
+        "Float floor stays float: 3.5",
+        "Int abs: 5",
+        "Float abs: 5.5",
+        "Int sign: -1",
+        "Zero sign: 0",
+        "Float sign: 1",
+        "Min: 3",
+        "Max: 9",
+        "Mixed min: 2.5",
+        "Gcd: 6",
+        "Lcm: 12",
+        "Zero lcm: 0",
+        "NaN is NaN: true",
+        "Number is NaN: false",
+        "Infinity is infinite: true",
+        "Negative infinity is infinite: true",
+        "Int is finite: true",
+        "NaN is finite: false",
+        "Nearest: 3",
+        "Even: 2",
+        "Floor int: 2",
+        "Ceil int: 3",
+        "Trunc int: -2",
+        "Int stays: 9",
+        "To float: 1.5",
+        "Int power overflow caught.",
+        "Negative exponent caught.",
+        "Abs overflow caught.",
+        "Infinite to int caught.",
+        "Bad rounding mode caught.",
+        "Non-number caught.",
+    ] {
+        assert!(
+            output.contains(expected),
+            "Missing '{}' in output",
+            expected
+        );
+    }
+}
+
 #[test]
 fn test_00_test_words() {
     let output = run_script("tests/00_test_words.f");
@@ -102,6 +779,244 @@ fn test_01_test_loops() {
     assert_01_test_loops_output(&output);
 }
 
+#[test]
+fn test_10_test_regex() {
+    let output = run_script("tests/10_test_regex.f");
+    println!(
+        "\n--- Output of 10_test_regex.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_10_test_regex_output(&output);
+}
+
+#[test]
+fn test_11_test_json() {
+    let output = run_script("tests/11_test_json.f");
+    println!(
+        "\n--- Output of 11_test_json.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_11_test_json_output(&output);
+}
+
+#[test]
+fn test_12_test_csv() {
+    let output = run_script("tests/12_test_csv.f");
+    println!(
+        "\n--- Output of 12_test_csv.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_12_test_csv_output(&output);
+}
+
+#[test]
+fn test_13_test_msgpack() {
+    let output = run_script("tests/13_test_msgpack.f");
+    println!(
+        "\n--- Output of 13_test_msgpack.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_13_test_msgpack_output(&output);
+}
+
+#[test]
+fn test_14_test_binary_layout() {
+    let output = run_script("tests/14_test_binary_layout.f");
+    println!(
+        "\n--- Output of 14_test_binary_layout.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_14_test_binary_layout_output(&output);
+}
+
+#[test]
+fn test_15_test_buffer_bits() {
+    let output = run_script("tests/15_test_buffer_bits.f");
+    println!(
+        "\n--- Output of 15_test_buffer_bits.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_15_test_buffer_bits_output(&output);
+}
+
+#[test]
+fn test_16_test_buffer_slices() {
+    let output = run_script("tests/16_test_buffer_slices.f");
+    println!(
+        "\n--- Output of 16_test_buffer_slices.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_16_test_buffer_slices_output(&output);
+}
+
+#[test]
+fn test_17_test_encoding() {
+    let output = run_script("tests/17_test_encoding.f");
+    println!(
+        "\n--- Output of 17_test_encoding.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_17_test_encoding_output(&output);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_18_test_ffi_structs() {
+    let output = run_script("tests/18_test_ffi_structs.f");
+    println!(
+        "\n--- Output of 18_test_ffi_structs.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_18_test_ffi_structs_output(&output);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_19_test_ffi_arrays() {
+    let output = run_script("tests/19_test_ffi_arrays.f");
+    println!(
+        "\n--- Output of 19_test_ffi_arrays.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_19_test_ffi_arrays_output(&output);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_20_test_ffi_callbacks() {
+    let output = run_script("tests/20_test_ffi_callbacks.f");
+    println!(
+        "\n--- Output of 20_test_ffi_callbacks.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_20_test_ffi_callbacks_output(&output);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_21_test_ffi_pointers() {
+    let output = run_script("tests/21_test_ffi_pointers.f");
+    println!(
+        "\n--- Output of 21_test_ffi_pointers.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_21_test_ffi_pointers_output(&output);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_22_test_ffi_variadic() {
+    let output = run_script("tests/22_test_ffi_variadic.f");
+    println!(
+        "\n--- Output of 22_test_ffi_variadic.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_22_test_ffi_variadic_output(&output);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_23_test_ffi_header() {
+    let output = run_script("tests/23_test_ffi_header.f");
+    println!(
+        "\n--- Output of 23_test_ffi_header.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_23_test_ffi_header_output(&output);
+}
+
+#[test]
+fn test_24_test_sockets() {
+    let output = run_script("tests/24_test_sockets.f");
+    println!(
+        "\n--- Output of 24_test_sockets.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_24_test_sockets_output(&output);
+}
+
+#[test]
+fn test_25_test_file_buffers() {
+    let output = run_script("tests/25_test_file_buffers.f");
+    println!(
+        "\n--- Output of 25_test_file_buffers.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_25_test_file_buffers_output(&output);
+}
+
+#[test]
+fn test_26_test_temp_files() {
+    let output = run_script("tests/26_test_temp_files.f");
+    println!(
+        "\n--- Output of 26_test_temp_files.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_26_test_temp_files_output(&output);
+}
+
+#[test]
+fn test_27_test_filesystem() {
+    let output = run_script("tests/27_test_filesystem.f");
+    println!(
+        "\n--- Output of 27_test_filesystem.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_27_test_filesystem_output(&output);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_28_test_process() {
+    let output = run_script("tests/28_test_process.f");
+    println!(
+        "\n--- Output of 28_test_process.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_28_test_process_output(&output);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_29_test_poll() {
+    let output = run_script("tests/29_test_poll.f");
+    println!(
+        "\n--- Output of 29_test_poll.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_29_test_poll_output(&output);
+}
+
+#[test]
+fn test_30_test_time() {
+    let output = run_script("tests/30_test_time.f");
+    println!(
+        "\n--- Output of 30_test_time.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_30_test_time_output(&output);
+}
+
+#[test]
+fn test_31_test_random() {
+    let output = run_script("tests/31_test_random.f");
+    println!(
+        "\n--- Output of 31_test_random.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_31_test_random_output(&output);
+}
+
+#[test]
+fn test_32_test_math() {
+    let output = run_script("tests/32_test_math.f");
+    println!(
+        "\n--- Output of 32_test_math.f ---\n{}\n-------------------------------",
+        output
+    );
+    assert_32_test_math_output(&output);
+}
+
 #[test]
 fn test_00_test_words_lib() {
     let mut interpreter = SorthInterpreter::new();
@@ -149,3 +1064,526 @@ fn test_01_test_loops_lib() {
     assert!(result.is_ok(), "Script failed: {:?}", result.err());
     // If you add output capturing to the interpreter, call assert_01_test_loops_output here.
 }
+
+// Helper to create an interpreter with the standard library loaded, for tests that need to inspect
+// the data stack after running a script.
+fn lib_interpreter() -> SorthInterpreter {
+    let mut interpreter = SorthInterpreter::new();
+    register_base_words(&mut interpreter);
+    register_io_words(&mut interpreter);
+    register_filesystem_words(&mut interpreter);
+    register_process_words(&mut interpreter);
+    register_http_words(&mut interpreter);
+    register_time_words(&mut interpreter);
+    register_random_words(&mut interpreter);
+    register_terminal_words(&mut interpreter);
+    register_user_words(&mut interpreter);
+    register_ffi_words(&mut interpreter);
+    let std_path = manifest_path("std");
+    interpreter
+        .add_search_path(std_path.to_str().unwrap())
+        .unwrap();
+    interpreter
+        .process_source_file(manifest_path("std.f").to_str().unwrap())
+        .unwrap();
+    interpreter
+}
+
+// Server side of the socket tests: read a line, reply with two lines in a single write, then wait
+// for the client to shut down its writing half before saying goodbye.
+fn serve_socket_test<S: Read + Write>(stream: S) {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let stream = reader.get_mut();
+    writeln!(stream, "echo: {}second line", line).unwrap();
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    reader.get_mut().write_all(b"bye").unwrap();
+}
+
+// Client side of the socket tests, the results are left on the stack for checking.
+fn run_socket_test(address: &str) -> Vec<String> {
+    let script = format!(
+        r#"
+        "{}" socket.connect variable! sock_fd
+
+        "hello" sock_fd @ file.line!
+        sock_fd @ socket.available@
+
+        50 sock_fd @ socket.read-timeout!
+        try
+            sock_fd @ file.line@ drop "no timeout"
+        catch
+            drop "timed out"
+        endcatch
+        0 sock_fd @ socket.read-timeout!
+
+        sock_fd @ file.line@
+        sock_fd @ file.line@
+
+        socket.shut/w sock_fd @ socket.shutdown
+        sock_fd @ file.string@
+
+        sock_fd @ file.close
+        "#,
+        address
+    );
+
+    let mut interpreter = lib_interpreter();
+    let result = interpreter.process_source("<socket test>", &script);
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    let mut values = Vec::new();
+
+    while let Ok(value) = interpreter.pop() {
+        values.insert(0, value.to_string());
+    }
+
+    values
+}
+
+fn assert_socket_test_output(values: &[String]) {
+    assert_eq!(
+        values,
+        &["0", "timed out", "echo: hello", "second line", "bye"]
+    );
+}
+
+#[test]
+fn test_socket_tcp_lib() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let server = std::thread::spawn(move || serve_socket_test(listener.accept().unwrap().0));
+
+    let values = run_socket_test(&address);
+    server.join().unwrap();
+
+    assert_socket_test_output(&values);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_socket_unix_lib() {
+    use std::os::unix::net::UnixListener;
+
+    let path = std::env::temp_dir().join(format!("sorth_socket_test_{}", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let listener = UnixListener::bind(&path).unwrap();
+    let server = std::thread::spawn(move || serve_socket_test(listener.accept().unwrap().0));
+
+    let values = run_socket_test(path.to_str().unwrap());
+    server.join().unwrap();
+    let _ = fs::remove_file(&path);
+
+    assert_socket_test_output(&values);
+}
+
+// A read that blocks on one socket must not hold up other fds, in this thread or any other.
+#[test]
+fn test_socket_blocked_read_lib() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    let reader = std::thread::spawn(move || {
+        let script = format!(
+            r#"
+            "{}" socket.connect variable! blocked_fd
+            blocked_fd @ file.line@
+            blocked_fd @ file.close
+            "#,
+            address
+        );
+
+        let mut interpreter = lib_interpreter();
+        let result = interpreter.process_source("<blocked read>", &script);
+        assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+        interpreter.pop().unwrap().to_string()
+    });
+
+    let (mut peer, _) = listener.accept().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    let path = std::env::temp_dir().join(format!("sorth_blocked_read_{}", std::process::id()));
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        let script = format!(
+            r#"
+            "{}" file.r/w file.create variable! other_fd
+            "other" other_fd @ file.line!
+            other_fd @ file.close
+            "#,
+            path.display()
+        );
+
+        let mut interpreter = lib_interpreter();
+        let result = interpreter.process_source("<other fds>", &script);
+        let _ = fs::remove_file(&path);
+
+        sender.send(result.is_ok()).unwrap();
+    });
+
+    let other_result = receiver.recv_timeout(std::time::Duration::from_secs(10));
+
+    writeln!(peer, "unblocked").unwrap();
+    assert_eq!(reader.join().unwrap(), "unblocked");
+    assert_eq!(
+        other_result,
+        Ok(true),
+        "Other fds were held up by a blocked read"
+    );
+}
+
+// Server side of the http.request test.  The request is echoed back in a chunked body, split
+// over two chunks with an extension and followed by a trailer.  A second connection is answered
+// with a header line that's too long for the client to accept.
+fn serve_http_request_test(listener: TcpListener) {
+    serve_http_echo_test(&listener);
+
+    let (mut stream, _) = listener.accept().unwrap();
+    let long_header = format!("HTTP/1.1 200 OK\r\nX-Long: {}\r\n\r\n", "a".repeat(100_000));
+    let _ = stream.write_all(long_header.as_bytes());
+}
+
+fn serve_http_echo_test(listener: &TcpListener) {
+    let (stream, _) = listener.accept().unwrap();
+    let mut reader = BufReader::new(stream);
+    let mut head = Vec::new();
+
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+
+        if line == "\r\n" {
+            break;
+        }
+
+        head.push(line.trim_end().to_string());
+    }
+
+    let header = |name: &str| {
+        head.iter()
+            .find_map(|line| line.strip_prefix(name))
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    let length: usize = header("Content-Length: ").parse().unwrap();
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).unwrap();
+
+    let echo = format!(
+        "{}|{}|{}",
+        head[0],
+        header("X-Test: "),
+        String::from_utf8(body).unwrap()
+    );
+    let (first, second) = echo.split_at(10);
+
+    write!(
+        reader.get_mut(),
+        "HTTP/1.1 201 Created\r\nTransfer-Encoding: chunked\r\nX-Reply: one\r\nx-reply: two\r\n\r\n\
+         {:x};ext=1\r\n{}\r\n{:x}\r\n{}\r\n0\r\nX-Trailer: done\r\n\r\n",
+        first.len(),
+        first,
+        second.len(),
+        second
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_http_request_lib() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let server = std::thread::spawn(move || serve_http_request_test(listener));
+
+    let script = format!(
+        r#"
+        "post" "http://{0}/echo?x=1" {{ "X-Test" -> "yes" }} "payload" http.request
+        variable! http_response
+
+        "status" http_response @ {{}}@
+        "reason" http_response @ {{}}@
+        "x-reply" "headers" http_response @ {{}}@ {{}}@
+        "body" http_response @ {{}}@
+
+        try
+            "GET" "https://localhost/" none none http.request
+        catch
+            drop "https refused"
+        endcatch
+
+        try
+            "GET" "http://{0}/a HTTP/1.1\r\nX-Injected: yes" none none http.request
+        catch
+            drop "bad target refused"
+        endcatch
+
+        try
+            "GET /injected" "http://{0}/" none none http.request
+        catch
+            drop "bad method refused"
+        endcatch
+
+        try
+            "GET" "http://{0}/long" none none http.request
+        catch
+            drop "long header refused"
+        endcatch
+        "#,
+        address
+    );
+
+    let mut interpreter = lib_interpreter();
+    let result = interpreter.process_source("<http request test>", &script);
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+    server.join().unwrap();
+
+    let mut values = Vec::new();
+
+    while let Ok(value) = interpreter.pop() {
+        values.insert(0, value.to_string());
+    }
+
+    assert_eq!(
+        values,
+        &[
+            "201",
+            "Created",
+            "one, two",
+            "POST /echo?x=1 HTTP/1.1|yes|payload",
+            "https refused",
+            "bad target refused",
+            "bad method refused",
+            "long header refused"
+        ]
+    );
+}
+
+// Client side of the http.serve test.  The script is already listening, so connections queue up
+// until http.serve accepts them.  Each request gets a connection of it's own.
+fn send_http_serve_test(address: String, requests: &[&str]) -> Vec<String> {
+    let mut responses = Vec::new();
+
+    for request in requests {
+        let mut stream = std::net::TcpStream::connect(&address).unwrap();
+
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        responses.push(response);
+    }
+
+    responses
+}
+
+#[test]
+fn test_http_serve_lib() {
+    // The script listens on a free port and hands the socket to http.serve, so that no other
+    // process can take the port in between.
+    let mut interpreter = lib_interpreter();
+    let result = interpreter.process_source(
+        "<http serve listen>",
+        r#"
+        "127.0.0.1:0" none socket.listen variable! http_server
+        http_server @ socket.local-address@
+        "#,
+    );
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    let address = interpreter.pop().unwrap().get_string_val();
+
+    let client = std::thread::spawn(move || {
+        send_http_serve_test(
+            address,
+            &[
+                "GET /hello?name=sorth HTTP/1.1\r\nHost: test\r\n\r\n",
+                "POST /echo HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n4\r\nping\r\n0\r\n\r\n",
+                "nonsense\r\n\r\n",
+                "GET /stop HTTP/1.1\r\n\r\n",
+            ],
+        )
+    });
+
+    let script = r#"
+        none variable! http_request
+
+        : http_test_handler
+            http_request !
+
+            "path" http_request @ {}@ "/hello" =
+            if
+                "query" http_request @ {}@ "hello {}" string.format
+            else
+                {
+                    "status" -> 202 ,
+                    "headers" -> { "X-Method" -> "method" http_request @ {}@ } ,
+                    "body" -> "body" http_request @ {}@ ,
+                    "stop" -> "path" http_request @ {}@ "/stop" =
+                }
+            then
+        ;
+
+        http_server @ ` http_test_handler http.serve
+        http_server @ file.close
+        "served"
+        "#;
+
+    let result = interpreter.process_source("<http serve test>", script);
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    let responses = client.join().unwrap();
+
+    assert!(responses[0].starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(responses[0].contains("Content-Type: text/plain; charset=utf-8\r\n"));
+    assert!(responses[0].ends_with("\r\n\r\nhello name=sorth"));
+
+    assert!(responses[1].starts_with("HTTP/1.1 202 Accepted\r\n"));
+    assert!(responses[1].contains("X-Method: POST\r\n"));
+    assert!(responses[1].contains("Content-Length: 4\r\n"));
+    assert!(responses[1].ends_with("\r\n\r\nping"));
+
+    assert!(responses[2].starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    assert!(responses[3].starts_with("HTTP/1.1 202 Accepted\r\n"));
+
+    assert_eq!(interpreter.pop().unwrap().to_string(), "served");
+}
+
+// A handler that leaves no response on the stack still gets the client a 500 before the error
+// reaches the script.
+#[test]
+fn test_http_serve_no_response_lib() {
+    let mut interpreter = lib_interpreter();
+    let result = interpreter.process_source(
+        "<http serve listen>",
+        r#"
+        "127.0.0.1:0" none socket.listen variable! http_server
+        http_server @ socket.local-address@
+        "#,
+    );
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    let address = interpreter.pop().unwrap().get_string_val();
+
+    let client =
+        std::thread::spawn(move || send_http_serve_test(address, &["GET / HTTP/1.1\r\n\r\n"]));
+
+    let script = r#"
+        : http_empty_handler
+            drop
+        ;
+
+        try
+            http_server @ ` http_empty_handler http.serve
+            "served"
+        catch
+            drop "handler failed"
+        endcatch
+        "#;
+
+    let result = interpreter.process_source("<http serve test>", script);
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    let responses = client.join().unwrap();
+
+    assert!(responses[0].starts_with("HTTP/1.1 500 Internal Server Error\r\n"));
+    assert_eq!(interpreter.pop().unwrap().to_string(), "handler failed");
+}
+
+#[test]
+fn test_regex_reset_lib() {
+    let mut interpreter = lib_interpreter();
+    register_regex_words(&mut interpreter);
+    interpreter.mark_context();
+
+    let result = interpreter.process_source("<regex test>", r#""a+" regex.compile"#);
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    let regex = interpreter.pop().unwrap();
+
+    // A compiled regex keeps it's own pattern across a reset, and ints are never taken as one.
+    interpreter.reset().unwrap();
+    interpreter.push(regex);
+
+    let result = interpreter.process_source(
+        "<regex test>",
+        r#"
+        "aaa" swap regex.match? variable! kept_match
+        try
+            "aaa" 1 regex.match?
+        catch
+            drop drop "int refused"
+        endcatch
+        kept_match @
+        "#,
+    );
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    assert_eq!(interpreter.pop().unwrap().to_string(), "true");
+    assert_eq!(interpreter.pop().unwrap().to_string(), "int refused");
+}
+
+#[test]
+fn test_temp_files_reset_lib() {
+    let mut interpreter = lib_interpreter();
+
+    // Mark a known good state to reset back to, the same as the sorth executable does.
+    interpreter.mark_context();
+
+    let result = interpreter.process_source(
+        "<temp files test>",
+        r#"
+        file.r/w file.auto-delete | file.create.tempfile drop
+        file.auto-delete dir.create.temp drop
+        file.r/w file.create.tempfile variable! kept_fd
+        "#,
+    );
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    let kept_path = interpreter.pop().unwrap().to_string();
+    let dir_path = interpreter.pop().unwrap().to_string();
+    let file_path = interpreter.pop().unwrap().to_string();
+
+    // Another interpreter's auto-delete files are left alone when this one is reset.
+    let mut other_interpreter = lib_interpreter();
+    other_interpreter.mark_context();
+
+    let result = other_interpreter.process_source(
+        "<other temp files test>",
+        "file.r/w file.auto-delete | file.create.tempfile drop",
+    );
+    assert!(result.is_ok(), "Script failed: {:?}", result.err());
+
+    let other_path = other_interpreter.pop().unwrap().to_string();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file_mode = fs::metadata(&file_path).unwrap().permissions().mode();
+        let dir_mode = fs::metadata(&dir_path).unwrap().permissions().mode();
+
+        assert_eq!(file_mode & 0o777, 0o600);
+        assert_eq!(dir_mode & 0o777, 0o700);
+    }
+
+    interpreter.reset().unwrap();
+
+    assert!(!Path::new(&file_path).exists());
+    assert!(!Path::new(&dir_path).exists());
+    assert!(Path::new(&kept_path).exists());
+    assert!(Path::new(&other_path).exists());
+
+    other_interpreter.reset().unwrap();
+    assert!(!Path::new(&other_path).exists());
+
+    fs::remove_file(&kept_path).unwrap();
+}